@@ -0,0 +1,62 @@
+/*                        BUILD SCRIPT
+
+With the bundle feature, writes the list of puzzle inputs built into the
+binary, see src/embedded.rs. The inputs are the day_N.dat files of the
+directory in AOC23_BUNDLE_DIR, data by default, for the days listed in
+AOC23_BUNDLE_DAYS, e.g. 1,2,5, or every day with a file if not set.
+
+*/
+
+use std::path::PathBuf;
+
+fn main() {
+    if std::env::var_os("CARGO_FEATURE_BUNDLE").is_none() {
+        return;
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=AOC23_BUNDLE_DIR");
+    println!("cargo:rerun-if-env-changed=AOC23_BUNDLE_DAYS");
+
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let data_dir = manifest_dir.join(std::env::var("AOC23_BUNDLE_DIR").unwrap_or_else(|_| "data".to_string()));
+    println!("cargo:rerun-if-changed={}", data_dir.display());
+
+    // Days asked for by name must have an input, the others are only taken if there is one
+    let days: Vec<(u32, bool)> = match std::env::var("AOC23_BUNDLE_DAYS") {
+        Ok(list) => list
+            .split(',')
+            .map(|d| match d.trim().parse::<u32>() {
+                Ok(day) if (1..=25).contains(&day) => (day, true),
+                _ => panic!("Invalid day '{}' in AOC23_BUNDLE_DAYS", d)
+            })
+            .collect(),
+        Err(_) => (1..=25).map(|day| (day, false)).collect()
+    };
+
+    let mut entries = Vec::<String>::new();
+    for (day, required) in days {
+        let input_file = data_dir.join(format!("day_{}.dat", day));
+        if !input_file.exists() {
+            if required {
+                panic!("No input for day {} at '{}' to bundle", day, input_file.display());
+            }
+            continue;
+        }
+        // Encrypted or compressed inputs are not text, and are only decoded as files are read
+        match std::fs::read(&input_file) {
+            Ok(bytes) if bytes.starts_with(b"AOC23ENC1") || std::str::from_utf8(&bytes).is_err() => {
+                panic!("The input for day {} at '{}' is not text, encrypted or compressed inputs cannot be bundled", day, input_file.display())
+            },
+            Ok(_) => (),
+            Err(e) => panic!("Failed to read '{}': {}", input_file.display(), e)
+        };
+        entries.push(format!("    ({}, include_str!({:?}))", day, input_file.display().to_string()));
+    }
+
+    let source = format!("pub const INPUTS: &[(u32, &str)] = &[\n{}\n];\n", entries.join(",\n"));
+    let out_file = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    if let Err(e) = std::fs::write(&out_file, source) {
+        panic!("Failed to write '{}': {}", out_file.display(), e);
+    }
+}
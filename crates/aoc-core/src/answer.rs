@@ -0,0 +1,143 @@
+/*                        ANSWERS
+
+The answer to a part of a puzzle. Most are integers, some too large for an
+i64, and a few are text, such as a code read off a grid, so every `Solver`
+returns an `Answer` to give all days the same return type.
+
+Answers serialise as a plain JSON or TOML value, a number or a string, and the
+same number compares equal whether it is held as an `Int` or a `UInt`.
+
+*/
+
+use alloc::string::{String, ToString};
+use serde::{Deserialize, Serialize};
+use core::fmt;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String)
+}
+
+impl Answer {
+    pub fn from_text(text: &str) -> Answer {
+        /* An answer written out by another program, an integer if it reads as one and text otherwise. */
+        let text = text.trim();
+        match (text.parse::<i64>(), text.parse::<u64>()) {
+            (Ok(n), _) => Answer::Int(n),
+            (_, Ok(n)) => Answer::UInt(n),
+            _ => Answer::Text(text.to_string())
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::UInt(n) => write!(f, "{}", n),
+            Answer::Text(t) => write!(f, "{}", t)
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            (Answer::UInt(a), Answer::UInt(b)) => a == b,
+            (Answer::Int(a), Answer::UInt(b)) | (Answer::UInt(b), Answer::Int(a)) => u64::try_from(*a).ok() == Some(*b),
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => false
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        *self == Answer::Int(*other)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Text(t) => t == other,
+            _ => false
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Answer {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Answer {
+        Answer::Int(n)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Answer {
+        Answer::UInt(n as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Answer {
+        Answer::UInt(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Answer {
+        Answer::UInt(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(t: String) -> Answer {
+        Answer::Text(t)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(t: &str) -> Answer {
+        Answer::Text(t.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_answer_equality() {
+        assert_eq!(Answer::Int(4361), Answer::UInt(4361));
+        assert_eq!(Answer::from(4361u32), 4361);
+        assert_ne!(Answer::Int(-1), Answer::UInt(u64::MAX));
+        assert_ne!(Answer::Int(46), Answer::from("46"));
+        assert_eq!(Answer::from("EZFCHJAB".to_string()), "EZFCHJAB");
+    }
+
+    #[test]
+    fn test_answer_display_and_serde() {
+        assert_eq!(Answer::Int(-35).to_string(), "-35");
+        assert_eq!(Answer::UInt(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::from_text("-13\n"), Answer::Int(-13));
+        assert_eq!(Answer::from_text("18446744073709551615"), Answer::UInt(u64::MAX));
+        assert_eq!(Answer::from_text(" EFHJ "), "EFHJ");
+
+        assert_eq!(serde_json::to_string(&vec![Answer::Int(35), Answer::UInt(u64::MAX), Answer::from("abc")]).unwrap(), r#"[35,18446744073709551615,"abc"]"#);
+        assert_eq!(serde_json::from_str::<Answer>("18446744073709551615").unwrap(), Answer::UInt(u64::MAX));
+        assert_eq!(serde_json::from_str::<Answer>("\"abc\"").unwrap(), "abc");
+    }
+}
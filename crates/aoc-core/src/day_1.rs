@@ -0,0 +1,342 @@
+/*                        ADVENT OF CODE DAY 1
+
+Calibration of a snow machine is performed reading a file containing
+sets of characters, the first and last numerical values are combined to form
+two digit numbers. Advanced calibration also takes into accounts word versions
+of numbers, e.g. 'eight'.
+
+The following code uses Regular Expressions to find digits via iterators, and
+a multi-pattern scanner to find word forms.
+
+@author : K. Zarebski
+@date : last modified 2023-12-02
+
+*/
+
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+use regex::Regex;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "std")]
+use crate::util::io::{lines, read_lines};
+
+use crate::error::AocError;
+use crate::answer::Answer;
+use crate::outcome::{warning, Diagnostic, Outcome};
+use crate::solver::{Registration, Solver};
+use crate::util::scan::Scanner;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NoDigitsPolicy {
+    // Fail the calibration, reporting the offending line
+    #[default]
+    Error,
+    // Warn about the line and leave it out of the total
+    Skip,
+    // Count the line as a calibration value of zero
+    TreatAsZero
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CalibrationOptions {
+    pub allow_str_nums: bool,
+    pub no_digits: NoDigitsPolicy
+}
+
+fn number_word_scanner() -> Result<Scanner, AocError> {
+    /* A scanner for the word forms of the digits, each match's pattern index being its value. */
+    let number_words = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+    // Words can share letters, e.g. 'oneight', so all overlapping matches are needed
+    Ok(Scanner::new_overlapping(number_words)?)
+}
+
+fn number_words_in_line(scanner: &Scanner, line: &str) -> Option<((usize, i32), (usize, i32))>  {
+    /* Returns the first and last word-based numbers within a string if present.
+
+    Scans the line for all, possibly overlapping, occurrences of the word form of
+    the first nine digits. The function returns a pair of pairs each representing the index
+    position of the start of the word, and its integer form.
+
+    # Arguments
+
+    * `scanner` - the scanner from `number_word_scanner`, built once for all lines
+    * `line` - the string to process for integers
+
+    # Examples
+
+    ```
+    let scanner = number_word_scanner().unwrap();
+    let first_last_pair = match number_words_in_line(&scanner, "3fiveeightoneightg") {
+        Some(n) => n,
+        None => panic!("Expected number read from words")
+    };
+    ```
+    */
+    tracing::debug!("Finding number words in line '{}'", line);
+
+    let found: Vec<(usize, i32)> = scanner
+        .find_iter(line)
+        .map(|m| (m.start, m.pattern as i32))
+        .collect();
+
+    let min = found.iter().min_by_key(|(i, _)| *i);
+    let max = found.iter().max_by_key(|(i, _)| *i);
+
+    match (min, max) {
+        (Some(first), Some(last)) => Some((*first, *last)),
+        _ => None
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn calibrate_from_data(calibration_file: impl AsRef<Path>, allow_str_nums: bool) -> Result<i32, AocError> {
+    /* Perform a calibration using a calibration file.
+
+    A calibration is performed by reading every line of a calibration file. For the basic
+    calibration this involves taking the first and last digit of each line, combining these
+    into a single two digit value and totaling these.
+
+    For an advanced calibration, the word form of digits is also taken into account,
+    i.e. lines containing 'one' etc will be treated as having the relevant digit.
+
+    # Arguments
+
+    * `calibration_file` - path of file for calibration
+    * `allow_str_nums` - take into account the word form of digits
+
+    # Examples
+
+    ```
+    let total = match calibrate_from_file("/path/to/file.dat", true) {
+        Ok(t) => t,
+        Err(e) => panic!(e)
+    };
+    ```
+    */
+    calibrate_with_options(calibration_file, &CalibrationOptions { allow_str_nums, ..Default::default() })
+}
+
+#[cfg(feature = "std")]
+pub fn calibrate_with_options(calibration_file: impl AsRef<Path>, options: &CalibrationOptions) -> Result<i32, AocError> {
+    /* Perform a calibration using a calibration file with the given options.
+
+    As for `calibrate_from_data`, with `options.no_digits` deciding how lines
+    which contain no digits (or number words, if allowed) are handled.
+
+    # Arguments
+
+    * `calibration_file` - path of file for calibration
+    * `options` - whether to allow word forms of digits and the policy for lines without digits
+
+    # Examples
+
+    ```
+    let options = CalibrationOptions { allow_str_nums: true, no_digits: NoDigitsPolicy::Skip };
+    let total = calibrate_with_options("/path/to/file.dat", &options).unwrap();
+    ```
+    */
+    Ok(calibrate_lines(lines(&calibration_file)?.map(|l| l.map_err(AocError::from)), options)?.into_answer())
+}
+
+#[cfg(feature = "std")]
+pub fn calibrate_from_reader(calibration: impl BufRead, options: &CalibrationOptions) -> Result<i32, AocError> {
+    /* Perform a calibration reading the calibration lines from any buffered reader.
+
+    # Arguments
+
+    * `calibration` - reader of the calibration lines, e.g. the bytes of a string
+    * `options` - whether to allow word forms of digits and the policy for lines without digits
+
+    # Examples
+
+    ```
+    let total = calibrate_from_reader("1abc2\npqr3stu8vwx".as_bytes(), &CalibrationOptions::default()).unwrap();
+    assert_eq!(total, 50);
+    ```
+    */
+    Ok(calibrate_lines(read_lines(calibration).map(|l| l.map_err(AocError::from)), options)?.into_answer())
+}
+
+fn calibrate_lines<L: AsRef<str>>(calibration_lines: impl Iterator<Item = Result<L, AocError>>, options: &CalibrationOptions) -> Result<Outcome<i32>, AocError> {
+    /* Perform a calibration over the lines of a calibration document, noting any lines without digits.
+
+    The lines are those of a &str, or read from a file or reader with the std feature,
+    whose read errors are passed on with the line they occurred on.
+    */
+    let allow_str_nums = options.allow_str_nums;
+    let _span = tracing::info_span!("solve", day = 1, part = if allow_str_nums {2} else {1}).entered();
+
+    let re = match Regex::new(r"[0-9]") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+    let word_scanner = if allow_str_nums {Some(number_word_scanner()?)} else {None};
+
+    let mut total: i32 = 0;
+    let mut diagnostics = Vec::<Diagnostic>::new();
+    for (i, line) in calibration_lines.enumerate() {
+        let line = line?;
+        let file_line = line.as_ref();
+
+        let mut digits = re.find_iter(file_line);
+
+        let mut first_num = match digits.next() {
+            Some(n) => n.as_str().to_string(),
+            None => "".to_string()
+        };
+
+        let mut first_num_index = 1000;
+        
+        if !first_num.is_empty() {
+            first_num_index = match file_line.find(&first_num) {
+                Some(index) => index,
+                None => return Err(AocError::parse_at(i + 1, format!("Failed to retrieve index of found number {}", first_num)))
+            };
+        }
+
+        let mut last_num = match digits.last() {
+            Some(n) => n.as_str().to_string(),
+            None => first_num.clone()
+        };
+
+        let last_num_index = match file_line.rfind(&last_num) {
+            Some(index) => {if last_num.is_empty() {0} else {index}},
+            None => return Err(AocError::parse_at(i + 1, format!("Failed to retrieve index of found number {}", last_num)))
+        };
+
+        if let Some(n) = word_scanner.as_ref().and_then(|s| number_words_in_line(s, file_line)) {
+            first_num = if first_num_index < n.0.0 {first_num.to_string()} else {n.0.1.to_string()};
+            last_num = if last_num_index > n.1.0 {last_num.to_string()} else {n.1.1.to_string()};
+        }
+
+        let num_str = format!("{}{}", first_num, if last_num.is_empty() {first_num.clone()} else {last_num.clone()});
+
+        if num_str.is_empty() {
+            match options.no_digits {
+                NoDigitsPolicy::Error => return Err(AocError::parse_at(i + 1, format!("No digits found in '{}'", file_line))),
+                NoDigitsPolicy::Skip => diagnostics.push(warning(Some(i + 1), format!("No digits found, skipped '{}'", file_line))),
+                NoDigitsPolicy::TreatAsZero => diagnostics.push(warning(Some(i + 1), format!("No digits found, counted '{}' as zero", file_line)))
+            };
+            continue;
+        }
+
+        match num_str.parse::<i32>() {
+            Ok(n) => {
+                tracing::info!(line = i + 1, value = n, "Found calibration value");
+                total += n;
+            },
+            Err(e) => return Err(AocError::parse_at(i + 1, format!("Failed to parse '{}': {}", num_str, e)))
+        };
+    }
+    Ok(Outcome::with_diagnostics(total, diagnostics))
+}
+
+// The day 1 puzzle, part 2 also reading digits written as words
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day1 {
+    pub no_digits: NoDigitsPolicy
+}
+
+impl Solver for Day1 {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(self.solve_with_diagnostics(1, input)?.into_answer())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(self.solve_with_diagnostics(2, input)?.into_answer())
+    }
+
+    // Lines without digits which are skipped or counted as zero are reported as diagnostics
+    fn solve_with_diagnostics(&self, part: u32, input: &str) -> Result<Outcome<Answer>, AocError> {
+        let allow_str_nums = match part {
+            1 => false,
+            2 => true,
+            _ => return Err(AocError::InvalidArgument(format!("Day 1 has no part {}", part)))
+        };
+        let options = CalibrationOptions { allow_str_nums, no_digits: self.no_digits };
+        Ok(calibrate_lines(input.lines().map(Ok), &options)?.map(Answer::from))
+    }
+}
+
+inventory::submit! {
+    Registration { name: "default", build: || Box::new(Day1::default()) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_calibration_no_words() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_1_1.dat");
+        assert_eq!(calibrate_from_data(&test_file, false).unwrap(), 142);
+    }
+
+    #[test]
+    fn test_overlapped_words_and_repeat() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/test_case_1.dat");
+        assert_eq!(calibrate_from_data(&test_file, true).unwrap(), 38);
+    }
+
+    #[test]
+    fn test_calibration_words() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_1_2.dat");
+        assert_eq!(calibrate_from_data(&test_file, true).unwrap(), 281);
+    }
+
+    #[test]
+    fn test_number_words_in_line() {
+        let scanner = number_word_scanner().unwrap();
+
+        assert_eq!(number_words_in_line(&scanner, "3fiveeightoneightg"), Some(((1, 5), (12, 8))));
+        assert_eq!(number_words_in_line(&scanner, "12345"), None);
+    }
+
+    #[test]
+    fn test_calibration_from_reader() {
+        let options = CalibrationOptions { allow_str_nums: true, no_digits: NoDigitsPolicy::Error };
+        assert_eq!(calibrate_from_reader("two1nine\neightwothree\nabcone2threexyz".as_bytes(), &options).unwrap(), 29 + 83 + 13);
+
+        let error = calibrate_from_reader("1abc2\nnothing".as_bytes(), &options).unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: Some(2), .. }), "{}", error);
+    }
+
+    #[test]
+    fn test_no_digits_policy() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_1_no_digits.dat");
+
+        let error = calibrate_from_data(&test_file, false).unwrap_err();
+        assert!(matches!(error, AocError::Parse { line: Some(2), .. }), "{}", error);
+
+        for no_digits in [NoDigitsPolicy::Skip, NoDigitsPolicy::TreatAsZero] {
+            let options = CalibrationOptions { allow_str_nums: false, no_digits };
+            assert_eq!(calibrate_with_options(&test_file, &options).unwrap(), 89);
+        }
+
+        let options = CalibrationOptions { allow_str_nums: true, no_digits: NoDigitsPolicy::Skip };
+        assert_eq!(calibrate_with_options(&test_file, &options).unwrap(), 89 + 38);
+
+        // The solver keeps the lines it left out as diagnostics rather than only logging them
+        let input = std::fs::read_to_string(&test_file).unwrap();
+        let outcome = Day1 { no_digits: NoDigitsPolicy::Skip }.solve_with_diagnostics(1, &input).unwrap();
+        assert_eq!(outcome.answer, 89);
+        assert_eq!(outcome.diagnostics.iter().map(|d| d.line).collect::<Vec<Option<usize>>>(), vec![Some(2), Some(4)]);
+        assert!(outcome.diagnostics[0].to_string().starts_with("line 2: No digits found, skipped"));
+        assert!(Day1 { no_digits: NoDigitsPolicy::TreatAsZero }.solve_with_diagnostics(2, &input).unwrap().diagnostics[0].message.contains("as zero"));
+        assert!(Day1::default().solve_with_diagnostics(1, &input).is_err());
+        assert!(Day1::default().solve_with_diagnostics(3, &input).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,675 @@
+/*                        ADVENT OF CODE DAY 2
+
+A color cube game is defined as taking a handful of cubes from a bag and determining based
+on this information the total contents of the bag.
+
+For this exercise, a collection of games are defined in the form:
+
+```
+Game X: R0 red, G0 green, B0 blue;...;RN red GN green, BN blue
+```
+
+where each semicolon separation represents a cube set.
+
+The elf hosting the game asks how many of the games defined would be possible when the
+bag contents is: 12 red, 13 green and 14 blue cubes.
+
+Further to this, the "power" of a game is defined as the product of the maximum number
+of cubes per color within a game across all sets within that game, i.e.:
+
+P(R,G,B) = Max(Ri)*Max(Gi)*Max(Bi)
+
+@author : K. Zarebski
+@date : last modified 2023-12-03
+
+*/
+
+use alloc::collections::BTreeMap;
+use alloc::{boxed::Box, format, string::{String, ToString}, vec::Vec};
+use regex::Regex;
+use serde::Serialize;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::outcome::{warning, Diagnostic, Outcome};
+use crate::error::AocError;
+use crate::answer::Answer;
+use crate::solver::{Registration, Solver};
+#[cfg(feature = "std")]
+use crate::util::io::{lines, read_lines};
+
+
+#[derive(Clone,Copy,Debug,Eq,Hash,Ord,PartialEq,PartialOrd)]
+pub enum Color {
+    Red,
+    Green,
+    Blue
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GamePower {
+    pub game_id: i32,
+    pub max_red: i32,
+    pub max_green: i32,
+    pub max_blue: i32,
+    pub power: i32
+}
+
+fn cube_counts(cubes: &BTreeMap<Color, i32>) -> [i32; 3] {
+    /* The number of red, green and blue cubes in a bag, zero for any color not given. */
+    [Color::Red, Color::Green, Color::Blue].map(|c| match cubes.get(&c) {
+        Some(n) => *n,
+        None => 0
+    })
+}
+
+impl GamePower {
+    pub fn permitted(&self, available_cubes: &BTreeMap<Color, i32>) -> bool {
+        /* Whether the game is possible with the given cubes, i.e. no color exceeds those available. */
+        let [red, green, blue] = cube_counts(available_cubes);
+        self.max_red <= red && self.max_green <= green && self.max_blue <= blue
+    }
+
+    pub fn deficit(&self, available_cubes: &BTreeMap<Color, i32>) -> BTreeMap<Color, u32> {
+        /* The number of extra cubes of each color needed to make the game possible.
+
+        Every color is present in the result, with zero for colors which already suffice,
+        so a permitted game has a deficit of zero throughout.
+
+        # Examples
+
+        ```
+        let extra_red = game.deficit(&cubes)[&Color::Red];
+        ```
+        */
+        [(Color::Red, self.max_red), (Color::Green, self.max_green), (Color::Blue, self.max_blue)]
+            .into_iter()
+            .zip(cube_counts(available_cubes))
+            .map(|((color, needed), available)| (color, (needed - available).max(0) as u32))
+            .collect()
+    }
+}
+
+pub fn game_permitted(game_input: &str, available_cubes: &BTreeMap<Color, i32>) -> Result<bool, AocError> {
+    /* Determine whether the given game is possible with the available cubes.
+
+    Given a set of cubes, read in the string defining a single game of cube sets and determine
+    if the game is possible (i.e. there are enough cubes of each color to represent it)
+
+    # Arguments
+
+    * `game_input` - the string from a game session file defining a single game
+    * `available_cubes` - a map containing the number of cubes of each color available
+
+    # Examples
+
+    ```
+    let cubes = BTreeMap::from([(Color::Red, 12), (Color::Green, 13), (Color::Blue, 14)]);
+
+    let example_game = "Game X: 7 blue, 6 green; 5 red, 9 green; 1 blue, 6 red, 5 green".to_string();
+
+    assert!(game_permitted(&example_game_pass, &cubes).unwrap());
+    ```  
+
+    */
+    let max_counts = max_cube_counts(game_input)?;
+
+    Ok(max_counts.iter().zip(cube_counts(available_cubes)).all(|(&shown, available)| shown <= available))
+}
+
+fn max_cube_counts(game_input: &str) -> Result<[i32; 3], AocError> {
+    /* Find the maximum number of red, green and blue cubes shown across the sets of a game. */
+    let game_re = match Regex::new(r"([\s\w\d,]+)") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+    let re_red = match Regex::new(r"(\d+) red") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+    let re_blue = match Regex::new(r"(\d+) blue") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+    let re_green = match Regex::new(r"(\d+) green") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let re_colors = [re_red, re_green, re_blue];
+    let mut max_counts = [0, 0, 0];
+
+    for set in game_re.find_iter(game_input) {
+        let res_string = set.as_str().to_string();
+
+        for (i, capture_re) in re_colors.iter().enumerate() {
+            if let Some(g1) = capture_re.captures_iter(&res_string).next().and_then(|r| r.get(1)) {
+                match g1.as_str().parse::<i32>() {
+                    Ok(n) => {
+                        max_counts[i] = if n > max_counts[i] {n} else {max_counts[i]};
+                    },
+                    Err(e) => return Err(AocError::parse(format!("Failed to parse '{}': {}", g1.as_str(), e)))
+                };
+            }
+        }
+    }
+    Ok(max_counts)
+}
+
+pub fn game_power(game_input: &str) -> Result<i32, AocError> {
+    /* Calculate the game power for the given game input.
+
+    Calculates the power of a game consisting of N sets of colored cubes as:
+
+    P(R,G,B) = Max(Ri)*Max(Gi)*Max(Bi)
+
+
+    # Arguments
+
+    * `game_input` - the string from a game session file defining a single game
+
+
+    # Examples
+
+    let example_game = "Game X: 7 blue, 6 green; 5 red, 9 green; 1 blue, 6 red, 5 green".to_string();
+
+    game_power(&example_game).unwrap();
+    ```
+     */
+    Ok(max_cube_counts(game_input)?.iter().product())
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_of_permitted_game_ids(game_record: impl AsRef<Path>, available_cubes: &BTreeMap<Color, i32>) -> Result<i32, AocError> {
+    /* Get the total of all permitted game identifiers.
+
+    For a given input file containing definitions of multiple game rounds, return the total defined as the addition
+    of all identifiers for games which are possible with the specified set of color cubes. A game is defined as:
+
+    ```
+    Game X: R0 red, G0 green, B0 blue;...;RN red GN green, BN blue
+    ```
+
+    # Arguments
+
+    * `game_record` - a file containing lines defining games with N sets of cubes.
+    * `available_cubes` - a map defining how many of each color of cube is available.
+
+    
+    # Examples
+
+    ```
+    let cubes = BTreeMap::from([(Color::Red, 12), (Color::Green, 13), (Color::Blue, 14)]);
+
+    match aoc23::logging::init() {
+        Ok(l) => l,
+        Err(_) => ()
+    };
+
+    get_total_of_permitted_game_ids("/path/to/file", &cubes).unwrap();
+    ```
+
+    */
+    Ok(get_total_of_permitted_game_ids_with_diagnostics(game_record, available_cubes)?.into_answer())
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_of_permitted_game_ids_from_reader(game_record: impl BufRead, available_cubes: &BTreeMap<Color, i32>) -> Result<i32, AocError> {
+    /* Get the total of all permitted game identifiers, reading the games from any buffered reader.
+
+    # Examples
+
+    ```
+    let total = get_total_of_permitted_game_ids_from_reader("Game 1: 3 blue, 4 red".as_bytes(), &cubes).unwrap();
+    ```
+    */
+    Ok(permitted_game_ids(read_lines(game_record).map(|l| l.map_err(AocError::from)), available_cubes)?.into_answer())
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_of_permitted_game_ids_with_diagnostics(game_record: impl AsRef<Path>, available_cubes: &BTreeMap<Color, i32>) -> Result<Outcome<i32>, AocError> {
+    /* Get the total of all permitted game identifiers along with warnings for any lines skipped. */
+    permitted_game_ids(lines(&game_record)?.map(|l| l.map_err(AocError::from)), available_cubes)
+}
+
+fn permitted_game_ids<L: AsRef<str>>(game_lines: impl Iterator<Item = Result<L, AocError>>, available_cubes: &BTreeMap<Color, i32>) -> Result<Outcome<i32>, AocError> {
+    /* Total the identifiers of the permitted games over the lines of a game record.
+
+    The lines are those of a &str, or read from a file or reader with the std feature,
+    whose read errors are passed on with the line they occurred on.
+    */
+    let _span = tracing::info_span!("solve", day = 2, part = 1).entered();
+
+    let game_id_re = match Regex::new(r"Game (\d+)") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let mut total = 0;
+    let mut diagnostics = Vec::<Diagnostic>::new();
+
+    for (i, line) in game_lines.enumerate() {
+        let line = line?;
+        let file_line = line.as_ref();
+
+        tracing::info!("Checking validity of game from line: {}", file_line);
+
+        match game_id_re.captures_iter(file_line).next() {
+            Some(r) => {
+                if let Some(g1) = r.get(1) {
+                    match g1.as_str().parse::<i32>() {
+                        Ok(n) => {
+                            if game_permitted(file_line, available_cubes)? {
+                                tracing::debug!(game_id = n, "Game permitted, adding identifier to total");
+                                total += n;
+                            }
+                        },
+                        Err(e) => return Err(AocError::parse(format!("Failed to parse '{}': {}", g1.as_str(), e)))
+                    };
+                }
+            },
+            None => if !file_line.trim().is_empty() {
+                diagnostics.push(warning(Some(i + 1), format!("No game ID found, skipped '{}'", file_line)));
+            }
+        };
+    
+    }
+
+    Ok(Outcome::with_diagnostics(total, diagnostics))
+   
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_game_power(game_record: impl AsRef<Path>) -> Result<i32, AocError> {
+    /* Find the total of all game powers
+
+    Adds all game powers for each game defined within the specified file
+
+
+    # Arguments
+
+    * `game_record` - a file containing lines defining games with N sets of cubes.
+
+
+    # Examples
+
+    ```
+    let total_game_power = match aoc23::day_2::get_total_game_power("/path/to/file") {
+        Ok(t) => t,
+        Err(e) => panic!("{}", e)
+    };
+    ```
+
+    */
+    total_game_power(lines(&game_record)?.map(|l| l.map_err(AocError::from)))
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_game_power_from_reader(game_record: impl BufRead) -> Result<i32, AocError> {
+    /* Find the total of all game powers, reading the games from any buffered reader.
+
+    # Examples
+
+    ```
+    let total_game_power = get_total_game_power_from_reader("Game 1: 3 blue, 4 red".as_bytes()).unwrap();
+    ```
+    */
+    total_game_power(read_lines(game_record).map(|l| l.map_err(AocError::from)))
+}
+
+fn total_game_power<L: AsRef<str>>(game_lines: impl Iterator<Item = Result<L, AocError>>) -> Result<i32, AocError> {
+    /* Add the powers of the games over the lines of a game record. */
+    let _span = tracing::info_span!("solve", day = 2, part = 2).entered();
+
+    let mut total = 0;
+
+    for line in game_lines {
+        let line = line?;
+        let file_line = line.as_ref();
+
+        tracing::info!("Checking validity of game from line: {}", file_line);
+
+        total += game_power(file_line)?;
+    
+    }
+
+    Ok(total)
+   
+}
+
+#[cfg(feature = "std")]
+pub fn get_game_powers(game_record: impl AsRef<Path>) -> Result<Vec<GamePower>, AocError> {
+    /* Break down the power of every game in a file.
+
+    For each game the identifier, maximum number of cubes of each color seen across all sets
+    and the resulting power are returned, in file order. Lines without a game identifier are
+    skipped.
+
+    # Arguments
+
+    * `game_record` - a file containing lines defining games with N sets of cubes.
+
+    # Examples
+
+    ```
+    let powers = get_game_powers("/path/to/file").unwrap();
+    let largest = powers.iter().max_by_key(|g| g.power);
+    ```
+    */
+    game_powers(lines(&game_record)?.map(|l| l.map_err(AocError::from)))
+}
+
+#[cfg(feature = "std")]
+pub fn get_game_powers_from_reader(game_record: impl BufRead) -> Result<Vec<GamePower>, AocError> {
+    /* Break down the power of every game, reading the games from any buffered reader. */
+    game_powers(read_lines(game_record).map(|l| l.map_err(AocError::from)))
+}
+
+pub fn get_game_powers_from_str(game_record: &str) -> Result<Vec<GamePower>, AocError> {
+    /* Break down the power of every game of a game record held in a string. */
+    game_powers(game_record.lines().map(Ok))
+}
+
+fn game_powers<L: AsRef<str>>(game_lines: impl Iterator<Item = Result<L, AocError>>) -> Result<Vec<GamePower>, AocError> {
+    /* Break down the power of every game over the lines of a game record. */
+    let game_id_re = match Regex::new(r"Game (\d+)") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let mut powers = Vec::<GamePower>::new();
+
+    for line in game_lines {
+        let line = line?;
+        let file_line = line.as_ref();
+
+        let game_id = match game_id_re.captures(file_line).and_then(|c| c.get(1)) {
+            Some(g) => match g.as_str().parse::<i32>() {
+                Ok(n) => n,
+                Err(e) => return Err(AocError::parse(format!("Failed to parse '{}': {}", g.as_str(), e)))
+            },
+            None => continue
+        };
+
+        let [max_red, max_green, max_blue] = max_cube_counts(file_line)?;
+
+        powers.push(GamePower { game_id, max_red, max_green, max_blue, power: max_red * max_green * max_blue });
+    }
+
+    Ok(powers)
+}
+
+#[cfg(feature = "std")]
+pub fn get_session_deficit(game_record: impl AsRef<Path>, available_cubes: &BTreeMap<Color, i32>) -> Result<BTreeMap<Color, u32>, AocError> {
+    /* The cubes to add to the bag so that every game in a file becomes possible.
+
+    As a single bag is shared by all games this is the largest deficit of each color
+    across the games, rather than their sum.
+
+    # Arguments
+
+    * `game_record` - a file containing lines defining games with N sets of cubes.
+    * `available_cubes` - a map defining how many of each color of cube is available.
+
+    # Examples
+
+    ```
+    let repair = get_session_deficit("/path/to/file", &cubes).unwrap();
+    ```
+    */
+    let mut session_deficit: BTreeMap<Color, u32> = [Color::Red, Color::Green, Color::Blue]
+        .into_iter()
+        .map(|c| (c, 0))
+        .collect();
+
+    for game in get_game_powers(&game_record)? {
+        for (color, extra) in game.deficit(available_cubes) {
+            if let Some(d) = session_deficit.get_mut(&color) {
+                *d = (*d).max(extra);
+            }
+        }
+    }
+
+    Ok(session_deficit)
+}
+
+#[cfg(feature = "std")]
+pub fn explain_games(game_record: impl AsRef<Path>, available_cubes: &BTreeMap<Color, i32>) -> Result<String, AocError> {
+    /* Describe step by step how both parts are solved for a file of games.
+
+    For every game the maximum number of cubes of each color seen is given, along with
+    whether the game is possible with the available cubes and the resulting power.
+
+    # Arguments
+
+    * `game_record` - a file containing lines defining games with N sets of cubes.
+    * `available_cubes` - a map defining how many of each color of cube is available.
+
+    # Examples
+
+    ```
+    println!("{}", explain_games("/path/to/file", &cubes).unwrap());
+    ```
+    */
+    Ok(explain_game_powers(&get_game_powers(&game_record)?, available_cubes))
+}
+
+#[cfg(feature = "std")]
+pub fn explain_games_from_reader(game_record: impl BufRead, available_cubes: &BTreeMap<Color, i32>) -> Result<String, AocError> {
+    /* Describe how both parts are solved for games read from any buffered reader, see `explain_games`. */
+    Ok(explain_game_powers(&get_game_powers_from_reader(game_record)?, available_cubes))
+}
+
+pub fn explain_games_from_str(game_record: &str, available_cubes: &BTreeMap<Color, i32>) -> Result<String, AocError> {
+    /* Describe how both parts are solved for games held in a string, see `explain_games`. */
+    Ok(explain_game_powers(&get_game_powers_from_str(game_record)?, available_cubes))
+}
+
+fn explain_game_powers(games: &[GamePower], available_cubes: &BTreeMap<Color, i32>) -> String {
+    let available = cube_counts(available_cubes);
+
+    let mut explanation = format!(
+        "A game is possible if it never shows more than {} red, {} green or {} blue cubes.\n\n",
+        available[0], available[1], available[2]
+    );
+    let mut id_total = 0;
+    let mut power_total = 0;
+
+    for game in games {
+        let possible = game.permitted(available_cubes);
+
+        if possible {
+            id_total += game.game_id;
+        }
+        power_total += game.power;
+
+        explanation.push_str(&format!(
+            "Game {}: at most {} red, {} green and {} blue, {}, power {} x {} x {} = {}\n",
+            game.game_id, game.max_red, game.max_green, game.max_blue, if possible {"possible"} else {"impossible"},
+            game.max_red, game.max_green, game.max_blue, game.power
+        ));
+    }
+
+    explanation.push_str(&format!("\nSum of the IDs of possible games: {}\n", id_total));
+    explanation.push_str(&format!("Sum of the powers of all games: {}\n", power_total));
+
+    explanation
+}
+
+// The day 2 puzzle, by default with a bag of 12 red, 13 green and 14 blue cubes
+pub struct Day2 {
+    pub cubes: BTreeMap<Color, i32>
+}
+
+impl Default for Day2 {
+    fn default() -> Self {
+        Day2 { cubes: BTreeMap::from([(Color::Red, 12), (Color::Green, 13), (Color::Blue, 14)]) }
+    }
+}
+
+impl Solver for Day2 {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(self.solve_with_diagnostics(1, input)?.into_answer())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(Answer::from(total_game_power(input.lines().map(Ok))?))
+    }
+
+    // Part 1 skips games it cannot read, which are reported as diagnostics
+    fn solve_with_diagnostics(&self, part: u32, input: &str) -> Result<Outcome<Answer>, AocError> {
+        match part {
+            1 => Ok(permitted_game_ids(input.lines().map(Ok), &self.cubes)?.map(Answer::from)),
+            _ => Ok(Outcome::new(self.solve(part, input)?))
+        }
+    }
+}
+
+inventory::submit! {
+    Registration { name: "default", build: || Box::new(Day2::default()) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_game_permitted() {
+        let mut cubes = BTreeMap::new();
+        cubes.insert(Color::Red, 12);
+        cubes.insert(Color::Green, 13);
+        cubes.insert(Color::Blue, 14);
+
+        let example_game_pass = "Game X: 7 blue, 6 green; 5 red, 9 green; 1 blue, 6 red, 5 green".to_string();
+        let example_game_fail = "Game Y: 12 red, 15 green; 4 red, 6 blue, 5 green".to_string();
+
+        assert!(game_permitted(&example_game_pass, &cubes).unwrap());
+        assert!(!game_permitted(&example_game_fail, &cubes).unwrap());
+        assert!(game_permitted("Game Z: 99999999999 red", &cubes).is_err());
+
+        // Decided by the most cubes of each color over every set, as for the game's power
+        assert!(!game_permitted("Game W: 1 red; 13 red", &cubes).unwrap());
+        assert!(game_permitted("Game V: 12 red; 14 blue", &cubes).unwrap());
+        assert!(!game_permitted("Game U: 1 green", &BTreeMap::from([(Color::Red, 5)])).unwrap());
+    }
+
+    #[test]
+    fn test_game_power() {
+
+        let example_game = "Game X: 7 blue, 6 green; 5 red, 9 green; 1 blue, 6 red, 5 green".to_string();
+
+        assert_eq!(game_power(&example_game).unwrap(), 378);
+    }
+
+    #[test]
+    fn test_total_of_passed_game_ids() {
+        let mut cubes = BTreeMap::new();
+        cubes.insert(Color::Red, 12);
+        cubes.insert(Color::Green, 13);
+        cubes.insert(Color::Blue, 14);
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_2.dat");
+        assert_eq!(get_total_of_permitted_game_ids(&test_file, &cubes).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_explain_games() {
+        let mut cubes = BTreeMap::new();
+        cubes.insert(Color::Red, 12);
+        cubes.insert(Color::Green, 13);
+        cubes.insert(Color::Blue, 14);
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_2.dat");
+        let explanation = explain_games(&test_file, &cubes).unwrap();
+
+        assert!(explanation.contains("Game 1: at most 4 red, 2 green and 6 blue, possible, power 4 x 2 x 6 = 48"));
+        assert!(explanation.contains("Game 3: at most 20 red, 13 green and 6 blue, impossible"));
+        assert!(explanation.contains("Sum of the IDs of possible games: 8"));
+        assert!(explanation.contains(&format!("Sum of the powers of all games: {}", get_total_game_power(&test_file).unwrap())));
+
+        let contents = std::fs::read_to_string(&test_file).unwrap();
+        assert_eq!(explain_games_from_reader(contents.as_bytes(), &cubes).unwrap(), explanation);
+    }
+
+    #[test]
+    fn test_skipped_game_diagnostics() {
+        let mut cubes = BTreeMap::new();
+        cubes.insert(Color::Red, 12);
+        cubes.insert(Color::Green, 13);
+        cubes.insert(Color::Blue, 14);
+
+        let test_file = std::env::temp_dir().join(format!("aoc23_day_2_diagnostics_{}.dat", std::process::id()));
+        std::fs::write(&test_file, "Game 1: 3 blue, 4 red\nGam 2: 1 blue\n\nGame 3: 1 red\n").unwrap();
+        let outcome = get_total_of_permitted_game_ids_with_diagnostics(&test_file, &cubes).unwrap();
+        std::fs::remove_file(&test_file).unwrap();
+
+        assert_eq!(outcome.answer, 4);
+        assert_eq!(outcome.diagnostics.len(), 1);
+        assert_eq!(outcome.diagnostics[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_game_powers() {
+        let mut cubes = BTreeMap::new();
+        cubes.insert(Color::Red, 12);
+        cubes.insert(Color::Green, 13);
+        cubes.insert(Color::Blue, 14);
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_2.dat");
+        let game_powers = get_game_powers(&test_file).unwrap();
+
+        assert_eq!(game_powers.len(), 5);
+        assert_eq!(game_powers[0], GamePower { game_id: 1, max_red: 4, max_green: 2, max_blue: 6, power: 48 });
+        assert!(!game_powers[2].permitted(&cubes));
+        assert_eq!(game_powers.iter().map(|g| g.power).sum::<i32>(), get_total_game_power(&test_file).unwrap());
+        assert_eq!(game_powers.iter().filter(|g| g.permitted(&cubes)).map(|g| g.game_id).sum::<i32>(), 8);
+        assert_eq!(
+            serde_json::to_string(&game_powers[0]).unwrap(),
+            r#"{"game_id":1,"max_red":4,"max_green":2,"max_blue":6,"power":48}"#
+        );
+    }
+
+    #[test]
+    fn test_games_from_reader() {
+        let cubes = Day2::default().cubes;
+        let games = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\n";
+
+        assert_eq!(get_total_of_permitted_game_ids_from_reader(games.as_bytes(), &cubes).unwrap(), 3);
+        assert_eq!(get_total_game_power_from_reader(games.as_bytes()).unwrap(), 48 + 12 + 1560);
+        assert_eq!(get_game_powers_from_reader(games.as_bytes()).unwrap()[2].max_red, 20);
+        assert_eq!(get_game_powers_from_str(games).unwrap(), get_game_powers_from_reader(games.as_bytes()).unwrap());
+        assert_eq!(explain_games_from_str(games, &cubes).unwrap(), explain_games_from_reader(games.as_bytes(), &cubes).unwrap());
+    }
+
+    #[test]
+    fn test_deficit() {
+        let mut cubes = BTreeMap::new();
+        cubes.insert(Color::Red, 12);
+        cubes.insert(Color::Green, 13);
+        cubes.insert(Color::Blue, 14);
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_2.dat");
+        let game_powers = get_game_powers(&test_file).unwrap();
+
+        assert!(game_powers[0].deficit(&cubes).values().all(|&d| d == 0));
+        assert_eq!(game_powers[2].deficit(&cubes)[&Color::Red], 8);
+        assert_eq!(game_powers[2].deficit(&cubes)[&Color::Green], 0);
+
+        let repair = get_session_deficit(&test_file, &cubes).unwrap();
+        for (color, extra) in &repair {
+            cubes.insert(*color, cubes[color] + *extra as i32);
+        }
+        assert!(game_powers.iter().all(|g| g.permitted(&cubes)));
+    }
+}
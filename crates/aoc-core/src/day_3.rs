@@ -0,0 +1,694 @@
+/*                        ADVENT OF CODE DAY 3
+
+The blueprint for a gondola system is presented as inventory numbers arranged
+in rows and offset in position. If the number is neighboured by a symbol not 
+including '.' it is a part number. Furthermore if this symbol is '*' and the
+symbol has exactly two neighbouring numbers in total, then the part is a gear.
+
+The gear ratio is defined as the product of the two numbers either side of the
+'*' symbol.
+
+@author : K. Zarebski
+@date : last modified 2023-12-03
+
+*/
+
+use alloc::{boxed::Box, collections::BTreeMap, format, string::{String, ToString}, vec::Vec};
+use regex::Regex;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::error::AocError;
+use crate::answer::Answer;
+use crate::solver::{Registration, Solver};
+use crate::util::hash::FxHashMap;
+#[cfg(feature = "std")]
+use crate::util::io::{lines, read_lines, InputSource, Lines};
+
+#[cfg(feature = "std")]
+fn read_blueprint<R: BufRead>(blueprint_lines: Lines<R>) -> Result<Vec<String>, AocError> {
+    /* Collect the rows of a blueprint, which is searched once for symbols and once for numbers. */
+    let mut blueprint = Vec::<String>::new();
+    for line in blueprint_lines {
+        blueprint.push(line?);
+    }
+    Ok(blueprint)
+}
+
+// Objects found in a blueprint alongside the coordinates at which each starts
+type Objects<'a> = (Vec<&'a str>, Vec<(usize, usize)>);
+
+fn get_objects<'a, S: AsRef<str>>(regex_str: &str, blueprint: &'a [S]) -> Result<Objects<'a>, AocError> {
+    /* Retrieve objects from a blueprint matching the given regular expression.
+
+    The retrieved objects include the symbols found and the coordinates of their locations,
+    the objects being borrowed from the rows of the blueprint.
+
+    # Arguments
+
+    * `regex_str` - a regular expression defining the objects to search for.
+    * `blueprint` - the rows of the blueprint to search.
+
+    # Returns
+
+    A pair containing two vectors of equal length:
+        - The objects found
+        - The coordinates of the object start positions
+
+    # Example
+
+    ```
+    let (symbol_strs, symbol_coords) = get_objects(r"[^\d\.]", &blueprint)?;
+    ```
+    */
+    let _span = tracing::info_span!("parse", day = 3, pattern = regex_str).entered();
+
+    tracing::debug!("Reading part data from {} rows using regex '{}'", blueprint.len(), regex_str);
+    let re = match Regex::new(regex_str) {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let mut coords: Vec<(usize, usize)> = Vec::<(usize, usize)>::new();
+    let mut obj_strs = Vec::<&'a str>::new();
+    for (i, file_line) in blueprint.iter().enumerate() {
+        for number in re.find_iter(file_line.as_ref()) {
+            coords.push((i, number.start()));
+            obj_strs.push(number.as_str());
+        }
+    }
+    Ok((obj_strs, coords))
+}
+
+fn get_object_neighbour_coords(row: usize, column: usize, length: usize) -> Vec<(usize, usize)> {
+    /* Retrieve all possible neighbour coordinates for an object of a given length at a specified coordinate.
+
+    This function looks for all possible coordinates not including negatives that surround an object
+    orientated in the horizontal direction:
+    
+    ...xxxxxx....
+    ..xOBJECTx...
+    ...xxxxxx....
+
+    OBJECTx......
+    xxxxxx.......
+    .............
+
+    xxxxxx.......
+    OBJECTx......
+    xxxxxx.......
+
+    # Arguments
+
+    * `row` - the row coordinate of the object
+    * `column` - the column coordinate of the object
+    * `length` - the length of the object in the horizontal direction
+
+    # Returns
+
+    A vector containing all coordinates of neighbouring positions as (i32, i32) pairs.
+
+    # Example
+
+    ```
+   get_object_neighbour_coords(0, 0, 3);
+    ```
+
+    */
+    let mut neighbour_values = Vec::<(usize, usize)>::new();
+    let mut lower_col_bound = column;
+
+    // If the column number is greater than zero we can include
+    // the previous column in neighbours
+    if column > 0 {
+        neighbour_values.push((row, column - 1));
+        lower_col_bound -= 1;
+    }
+
+    neighbour_values.push((row, column + length));
+
+    // Add all positions above and below the object
+    for col in lower_col_bound..=column + length {
+        if row > 0 {
+            neighbour_values.push((row - 1, col));
+        }
+
+        neighbour_values.push((row + 1, col));
+    }
+
+    neighbour_values
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SymbolPosition {
+    // Ordered by position first so maps keyed on symbols iterate in reading order
+    pub position: (usize, usize),
+    pub symbol: char
+}
+
+// An inclusive region of the schematic, rows and columns counted from zero
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize
+}
+
+impl Rect {
+    pub fn contains(&self, position: (usize, usize)) -> bool {
+        (self.top..=self.bottom).contains(&position.0) && (self.left..=self.right).contains(&position.1)
+    }
+
+    pub fn overlaps_row_span(&self, row: usize, column: usize, length: usize) -> bool {
+        /* Whether any of the `length` cells starting at (row, column) lie within the region. */
+        (self.top..=self.bottom).contains(&row) && column <= self.right && column + length > self.left
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Max
+}
+
+impl Aggregation {
+    pub fn apply(&self, values: &[i32]) -> i64 {
+        let values = values.iter().map(|&v| v as i64);
+        match self {
+            Aggregation::Product => values.product(),
+            Aggregation::Sum => values.sum(),
+            Aggregation::Max => values.max().unwrap_or(0)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Schematic {
+    numbers: Vec<(i32, (usize, usize), usize)>,
+    symbols: FxHashMap<(usize, usize), char>
+}
+
+impl Schematic {
+    #[cfg(feature = "std")]
+    pub fn from_file(blueprint_file: impl AsRef<Path>) -> Result<Schematic, AocError> {
+        /* Read the numbers and symbols of a blueprint file.
+
+        # Examples
+
+        ```
+        let schematic = Schematic::from_file("/path/to/file").unwrap();
+        ```
+        */
+        Schematic::from_blueprint(&read_blueprint(lines(&blueprint_file)?)?)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_reader(blueprint: impl BufRead) -> Result<Schematic, AocError> {
+        /* Read the numbers and symbols of a blueprint from any buffered reader.
+
+        # Examples
+
+        ```
+        let schematic = Schematic::from_reader("467..114..\n...*......".as_bytes()).unwrap();
+        ```
+        */
+        Schematic::from_blueprint(&read_blueprint(read_lines(blueprint))?)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_input(blueprint: &InputSource) -> Result<Schematic, AocError> {
+        /* Read the numbers and symbols of a blueprint borrowed from an input source, without copying its rows.
+
+        # Examples
+
+        ```
+        let schematic = Schematic::from_input(&InputSource::mmap("/path/to/file").unwrap()).unwrap();
+        ```
+        */
+        Schematic::from_blueprint(&blueprint.lines()?.collect::<Vec<&str>>())
+    }
+
+    pub fn parse(blueprint: &str) -> Result<Schematic, AocError> {
+        /* Read the numbers and symbols of a blueprint held in a string, borrowing its rows.
+
+        # Examples
+
+        ```
+        let schematic = Schematic::parse("467..114..\n...*......").unwrap();
+        ```
+        */
+        Schematic::from_blueprint(&blueprint.lines().collect::<Vec<&str>>())
+    }
+
+    fn from_blueprint<S: AsRef<str>>(blueprint: &[S]) -> Result<Schematic, AocError> {
+        let (symbol_strs, symbol_coords) = get_objects(r"[^\d\.]", blueprint)?;
+        let (number_strs, number_coords) = get_objects(r"\d+", blueprint)?;
+
+        let mut numbers = Vec::<(i32, (usize, usize), usize)>::new();
+        for (num_str, coord) in number_strs.iter().zip(number_coords) {
+            let integer_num = match num_str.parse::<i32>() {
+                Ok(n) => n,
+                Err(e) => return Err(AocError::parse(format!("Failed to parse number '{}': {}", num_str, e)))
+            };
+            numbers.push((integer_num, coord, num_str.len()));
+        }
+
+        let mut symbols = FxHashMap::<(usize, usize), char>::default();
+        for (symbol_str, coord) in symbol_strs.iter().zip(symbol_coords) {
+            match symbol_str.chars().next() {
+                Some(c) => symbols.insert(coord, c),
+                None => None
+            };
+        }
+
+        Ok(Schematic { numbers, symbols })
+    }
+
+    fn is_part(&self, row: usize, column: usize, length: usize) -> bool {
+        /* Whether the number of the given length starting at (row, column) neighbours any symbol. */
+        get_object_neighbour_coords(row, column, length)
+            .iter()
+            .any(|c| self.symbols.contains_key(c))
+    }
+
+    pub fn parts_by_symbol(&self) -> BTreeMap<SymbolPosition, Vec<i32>> {
+        /* Group part numbers by the symbols they neighbour.
+
+        Every symbol in the schematic appears as a key, with the numbers adjacent to it
+        in reading order. A number touching several symbols is listed under each.
+
+        # Examples
+
+        ```
+        let hash_total: i32 = schematic.parts_by_symbol()
+            .iter()
+            .filter(|(s, _)| s.symbol == '#')
+            .flat_map(|(_, parts)| parts)
+            .sum();
+        ```
+        */
+        let mut parts: BTreeMap<SymbolPosition, Vec<i32>> = self.symbols
+            .iter()
+            .map(|(&position, &symbol)| (SymbolPosition { position, symbol }, Vec::<i32>::new()))
+            .collect();
+
+        for &(number, (row, column), length) in &self.numbers {
+            for neighbour in get_object_neighbour_coords(row, column, length) {
+                if let Some(&symbol) = self.symbols.get(&neighbour) {
+                    if let Some(p) = parts.get_mut(&SymbolPosition { position: neighbour, symbol }) {
+                        p.push(number);
+                    }
+                }
+            }
+        }
+
+        parts
+    }
+
+    pub fn part_spans(&self) -> Vec<((usize, usize), usize)> {
+        /* The starting position and number of digits of every part number, in reading order.
+
+        Numbers not neighbouring any symbol are left out, e.g. for drawing the parts of
+        a schematic apart from its other numbers.
+        */
+        self.numbers
+            .iter()
+            .filter(|&&(_, (row, column), length)| self.is_part(row, column, length))
+            .map(|&(_, position, length)| (position, length))
+            .collect()
+    }
+
+    pub fn parts_in(&self, region: &Rect) -> Vec<i32> {
+        /* Part numbers with at least one digit inside the region, in reading order.
+
+        A number counts as a part if it neighbours a symbol anywhere in the schematic,
+        including symbols outside the region.
+
+        # Examples
+
+        ```
+        let top_left: i32 = schematic.parts_in(&Rect { top: 0, left: 0, bottom: 9, right: 9 }).iter().sum();
+        ```
+        */
+        self.numbers
+            .iter()
+            .filter(|&&(_, (row, column), length)| region.overlaps_row_span(row, column, length))
+            .filter(|&&(_, (row, column), length)| self.is_part(row, column, length))
+            .map(|&(number, _, _)| number)
+            .collect()
+    }
+
+    pub fn gears_in(&self, region: &Rect, gear_symbol: char) -> Vec<i64> {
+        /* Gear ratios of the gears whose symbol lies inside the region, in reading order.
+
+        The two numbers making up a gear may extend outside the region.
+
+        # Examples
+
+        ```
+        let ratios = schematic.gears_in(&Rect { top: 0, left: 0, bottom: 9, right: 9 }, '*');
+        ```
+        */
+        self.parts_by_symbol()
+            .iter()
+            .filter(|(s, parts)| s.symbol == gear_symbol && parts.len() == 2 && region.contains(s.position))
+            .map(|(_, parts)| Aggregation::Product.apply(parts))
+            .collect()
+    }
+
+    pub fn aggregate_neighbours(&self, symbol: char, neighbours: usize, aggregation: Aggregation) -> Vec<i64> {
+        /* Aggregate the part numbers around every occurrence of a symbol with exactly N neighbours.
+
+        # Arguments
+
+        * `symbol` - the symbol to consider
+        * `neighbours` - the exact number of adjacent part numbers required
+        * `aggregation` - how to combine the adjacent part numbers
+
+        # Returns
+
+        One aggregated value per qualifying symbol, in reading order.
+
+        # Examples
+
+        ```
+        // The gear ratios
+        let gear_ratios = schematic.aggregate_neighbours('*', 2, Aggregation::Product);
+        ```
+        */
+        self.parts_by_symbol()
+            .iter()
+            .filter(|(s, parts)| s.symbol == symbol && parts.len() == neighbours)
+            .map(|(_, parts)| aggregation.apply(parts))
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn get_part_numbers(blueprint_file: impl AsRef<Path>) -> Result<Vec<i32>, AocError> {
+    /* Get all numbers within a blueprint file that are part numbers.
+
+    Returns all numbers which have at least one neighbouring symbol, as as such
+    are defined as part numbers.
+
+    # Arguments
+
+    * `blueprint_file` - file containing blueprint data
+
+
+    # Returns
+
+    A vector containing all number identifiers for parts.
+
+    # Example
+
+    ```
+    let part_numbers = get_part_numbers("/path/to/file").unwrap();
+    ```
+    
+    */
+    part_numbers(&read_blueprint(lines(&blueprint_file)?)?)
+}
+
+#[cfg(feature = "std")]
+pub fn get_part_numbers_from_reader(blueprint: impl BufRead) -> Result<Vec<i32>, AocError> {
+    /* Get all part numbers within a blueprint read from any buffered reader. */
+    part_numbers(&read_blueprint(read_lines(blueprint))?)
+}
+
+#[cfg(feature = "std")]
+pub fn get_part_numbers_from_input(blueprint: &InputSource) -> Result<Vec<i32>, AocError> {
+    /* Get all part numbers within a blueprint borrowed from an input source. */
+    part_numbers(&blueprint.lines()?.collect::<Vec<&str>>())
+}
+
+fn part_numbers<S: AsRef<str>>(blueprint: &[S]) -> Result<Vec<i32>, AocError> {
+    /* Get all part numbers within the rows of a blueprint. */
+    let _span = tracing::info_span!("solve", day = 3, part = 1).entered();
+
+    tracing::debug!("Finding number and symbol positions");
+
+    let (_, symbol_coords) = get_objects(r"[^\d\.]", blueprint)?;
+    let (number_strs, number_coords) = get_objects(r"\d+", blueprint)?;
+
+    tracing::debug!("Determining numerical values for numbers identified as part numbers");
+    let mut part_numbers = Vec::<i32>::new();
+
+    for (num_str, coord) in number_strs.iter().zip(&number_coords) {
+
+        // Firstly check if the number has a neighbouring symbol in the same row
+        if (coord.1 > 0 && symbol_coords.contains(&(coord.0, coord.1-1))) || symbol_coords.contains(&(coord.0, coord.1 + num_str.len())) {
+            let integer_num = match num_str.parse::<i32>() {
+                Ok(n) => n,
+                Err(e) => return Err(AocError::parse(format!("Failed to parse number '{}': {}", num_str, e)))
+            };
+            part_numbers.push(integer_num);
+            continue;
+        }
+
+        let lower_limit = if coord.1 > 0 {coord.1 - 1} else {coord.1};
+
+
+        // Next check if it has a neighbouring symbol in the row above and below
+        for col_num in lower_limit..=coord.1 + num_str.len() {
+            if (coord.0 > 0 && symbol_coords.contains(&(coord.0 - 1, col_num))) || symbol_coords.contains(&(coord.0 + 1, col_num)) {
+                let integer_num = match num_str.parse::<i32>() {
+                    Ok(n) => n,
+                    Err(e) => return Err(AocError::parse(format!("Failed to parse number '{}': {}", num_str, e)))
+                };
+                part_numbers.push(integer_num);
+                break;
+            }
+        }
+    }
+
+    Ok(part_numbers)
+}
+
+
+#[cfg(feature = "std")]
+pub fn get_gear_ratios(blueprint_file: impl AsRef<Path>, gear_symbol: &str) -> Result<Vec<i64>, AocError> {
+    /* Geat the gear ratios for each gear within a blueprint file.
+
+    For a given blueprint file return the gear ratio for each gear defined within it, this ratio
+    is defined as the product of the two object numbers positioned either side of it.
+
+    # Arguments
+
+    * `blueprint_file` - the file containing the blueprint data.
+    * `gear_symbol` - the symbol representing a single gear.
+
+    # Returns
+
+    A vector containing the gear ratio for each gear within the blueprint file.
+
+
+    # Example
+
+    ```
+    let gear_neighbours = get_gear_ratios("/path/to/file", "*").unwrap();
+    ```
+    */
+    gear_ratios(&Schematic::from_file(&blueprint_file)?, gear_symbol)
+}
+
+#[cfg(feature = "std")]
+pub fn get_gear_ratios_from_reader(blueprint: impl BufRead, gear_symbol: &str) -> Result<Vec<i64>, AocError> {
+    /* Get the gear ratios for each gear within a blueprint read from any buffered reader. */
+    gear_ratios(&Schematic::from_reader(blueprint)?, gear_symbol)
+}
+
+#[cfg(feature = "std")]
+pub fn get_gear_ratios_from_input(blueprint: &InputSource, gear_symbol: &str) -> Result<Vec<i64>, AocError> {
+    /* Get the gear ratios for each gear within a blueprint borrowed from an input source. */
+    gear_ratios(&Schematic::from_input(blueprint)?, gear_symbol)
+}
+
+fn gear_char(gear_symbol: &str) -> Result<char, AocError> {
+    /* The character marking a gear, rejecting symbols which are empty or longer than one character. */
+    let mut gear_chars = gear_symbol.chars();
+    match (gear_chars.next(), gear_chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(AocError::InvalidArgument(format!("Gear symbol must be a single character, got '{}'", gear_symbol)))
+    }
+}
+
+fn gear_ratios(schematic: &Schematic, gear_symbol: &str) -> Result<Vec<i64>, AocError> {
+    /* Get the gear ratios for each gear of a schematic, as i64 since the product of two parts may not fit an i32. */
+    let _span = tracing::info_span!("solve", day = 3, part = 2).entered();
+
+    Ok(schematic.aggregate_neighbours(gear_char(gear_symbol)?, 2, Aggregation::Product))
+}
+
+// The day 3 puzzle, by default with gears marked by '*'
+pub struct Day3 {
+    pub gear_symbol: String
+}
+
+impl Day3 {
+    pub fn new(gear_symbol: &str) -> Result<Day3, AocError> {
+        /* The day 3 puzzle with gears marked by `gear_symbol`, which must be a single character. */
+        gear_char(gear_symbol)?;
+        Ok(Day3 { gear_symbol: gear_symbol.to_string() })
+    }
+}
+
+impl Default for Day3 {
+    fn default() -> Self {
+        Day3 { gear_symbol: "*".to_string() }
+    }
+}
+
+impl Solver for Day3 {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    // The rows are borrowed from the input rather than read into new strings
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(Answer::Int(part_numbers(&input.lines().collect::<Vec<&str>>())?.iter().map(|&n| n as i64).sum()))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        let schematic = Schematic::parse(input)?;
+        Ok(Answer::Int(gear_ratios(&schematic, &self.gear_symbol)?.iter().sum()))
+    }
+}
+
+inventory::submit! {
+    Registration { name: "default", build: || Box::new(Day3::default()) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_get_number_neighbour_coords() {
+        let expected = vec![(0, 3), (1, 0), (1, 1), (1, 2), (1, 3)];
+
+        let neighbours = get_object_neighbour_coords(0, 0, 3);
+
+        for coord in expected {
+            tracing::debug!("Check coord {:?} in {:?}", coord, neighbours);
+            assert!(neighbours.contains(&coord));
+        }
+    }
+
+    #[test]
+    fn test_get_part_numbers() {
+        let expected = vec![467, 35, 633, 617, 592, 755, 664, 598];
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_3.dat");
+        let part_numbers = get_part_numbers(&test_file).unwrap();
+
+        for number in expected {
+            tracing::info!("Checking number {}", number);
+            assert!(part_numbers.contains(&number));
+        }
+    }
+
+    #[test]
+    fn test_get_gear_ratios() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_3.dat");
+        let gear_neighbours = get_gear_ratios(&test_file, "*").unwrap();
+
+        let total: i64 = gear_neighbours.iter().sum::<i64>();
+
+        assert_eq!(total, 467835);
+    }
+
+    #[test]
+    fn test_blueprint_from_reader() {
+        let blueprint = "467..114..\n...*......\n..35..633.\n......#...\n";
+
+        assert_eq!(get_part_numbers_from_reader(blueprint.as_bytes()).unwrap(), vec![467, 35, 633]);
+        assert_eq!(get_gear_ratios_from_reader(blueprint.as_bytes(), "*").unwrap(), vec![467 * 35]);
+
+        // A ratio too large for an i32
+        assert_eq!(get_gear_ratios_from_reader("99999*99999".as_bytes(), "*").unwrap(), vec![9999800001]);
+        assert_eq!(Schematic::from_reader(blueprint.as_bytes()).unwrap().parts_by_symbol().len(), 2);
+
+        for bad_symbol in ["", "**"] {
+            assert!(matches!(get_gear_ratios_from_reader(blueprint.as_bytes(), bad_symbol), Err(AocError::InvalidArgument(_))));
+            assert!(Day3::new(bad_symbol).is_err());
+        }
+        assert_eq!(Day3::new("#").unwrap().part2(blueprint).unwrap(), Answer::Int(0));
+    }
+
+    #[test]
+    fn test_blueprint_from_input() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_3.dat");
+        let input = InputSource::read(&test_file).unwrap();
+
+        assert_eq!(get_part_numbers_from_input(&input).unwrap(), get_part_numbers(&test_file).unwrap());
+        assert_eq!(get_gear_ratios_from_input(&input, "*").unwrap(), get_gear_ratios(&test_file, "*").unwrap());
+        assert_eq!(Schematic::from_input(&input).unwrap().parts_by_symbol(), Schematic::from_file(&test_file).unwrap().parts_by_symbol());
+    }
+
+    #[test]
+    fn test_parts_by_symbol() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_3.dat");
+        let parts = Schematic::from_file(&test_file).unwrap().parts_by_symbol();
+
+        assert_eq!(parts.len(), 6);
+        assert_eq!(parts[&SymbolPosition { position: (1, 3), symbol: '*' }], vec![467, 35]);
+        assert_eq!(parts[&SymbolPosition { position: (3, 6), symbol: '#' }], vec![633]);
+        assert_eq!(parts[&SymbolPosition { position: (4, 3), symbol: '*' }], vec![617]);
+    }
+
+    #[test]
+    fn test_aggregate_neighbours() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_3.dat");
+        let schematic = Schematic::from_file(&test_file).unwrap();
+
+        assert_eq!(schematic.aggregate_neighbours('*', 2, Aggregation::Product), vec![16345, 451490]);
+        assert_eq!(schematic.aggregate_neighbours('*', 2, Aggregation::Sum), vec![502, 1353]);
+        assert_eq!(schematic.aggregate_neighbours('*', 1, Aggregation::Max), vec![617]);
+        assert_eq!(schematic.aggregate_neighbours('$', 1, Aggregation::Sum), vec![664]);
+        assert!(schematic.aggregate_neighbours('*', 3, Aggregation::Product).is_empty());
+    }
+
+    #[test]
+    fn test_region_queries() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_3.dat");
+        let schematic = Schematic::from_file(&test_file).unwrap();
+
+        let everything = Rect { top: 0, left: 0, bottom: 9, right: 9 };
+        assert_eq!(schematic.parts_in(&everything).iter().sum::<i32>(), 4361);
+        assert_eq!(schematic.gears_in(&everything, '*').iter().sum::<i64>(), 467835);
+
+        let top_left = Rect { top: 0, left: 0, bottom: 4, right: 4 };
+        assert_eq!(schematic.parts_in(&top_left), vec![467, 35, 617]);
+        assert_eq!(schematic.gears_in(&top_left, '*'), vec![16345]);
+
+        // 664 starts outside the region but its last digit is inside
+        let bottom_left = Rect { top: 9, left: 2, bottom: 9, right: 2 };
+        assert_eq!(schematic.parts_in(&bottom_left), vec![664]);
+    }
+
+    #[test]
+    fn test_part_spans() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_3.dat");
+        let blueprint = std::fs::read_to_string(&test_file).unwrap();
+        let schematic = Schematic::parse(&blueprint).unwrap();
+
+        // 114 and 58 neighbour no symbol
+        let spans = schematic.part_spans();
+        assert_eq!(spans.len(), 8);
+        assert_eq!(spans[0], ((0, 0), 3));
+        assert!(!spans.contains(&((0, 5), 3)));
+        assert_eq!(schematic.parts_by_symbol(), Schematic::from_file(&test_file).unwrap().parts_by_symbol());
+    }
+}
@@ -0,0 +1,638 @@
+/*                        ADVENT OF CODE DAY 4
+
+A set of scratchcards is represented on an input file alongside
+numbers to be matched for scoring. In the first part of this exercise
+for each game the score is given by:
+
+S(N) = (N-1)**2 || 1
+
+In the second part, the number of matches represents the number of
+duplicate cards won, where these duplicates are given by:
+
+F(Game_ID, Matches) = [Game_ID + 1 .. Game_ID + Matches + 1]
+
+The cards won are themselves then scored and so on with the total
+of all tickets (including the initial set) being calculated.
+
+@author : K. Zarebski
+@date : last modified 2023-12-04
+
+*/
+
+use alloc::{boxed::Box, format, string::{String, ToString}, vec::Vec};
+use regex::Regex;
+use indexmap::IndexMap;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::outcome::{warning, Diagnostic, Outcome};
+use crate::error::AocError;
+use crate::answer::Answer;
+use crate::solver::{Registration, Solver};
+use crate::util::hash::{FxBuildHasher, FxHashMap};
+#[cfg(feature = "std")]
+use crate::util::io::{lines, read_lines};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateNumbers {
+    // Every occurrence of one of the player's numbers which is a winning number is a match
+    #[default]
+    EachOccurrence,
+    // A number is matched at most once however many times it appears on either side
+    Distinct
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scoring {
+    // The first match scores one point and each further match doubles the score, as in part 1
+    #[default]
+    Doubling,
+    // Each match scores one point
+    Count
+}
+
+impl Scoring {
+    pub fn score(&self, total: i32, _value: i32) -> i32 {
+        /* The score of a card after one more match, given its score so far. */
+        match self {
+            Scoring::Doubling => if total < 1 {1} else {total * 2},
+            Scoring::Count => total + 1
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CardOptions {
+    pub duplicates: DuplicateNumbers,
+    // How part 1 scores the matches of a card, part 2 always counts them
+    pub scoring: Scoring
+}
+
+fn get_scratchcard_score<F: Fn(i32, i32) -> i32>(scratchcard_data: &str, scoring: F, options: &CardOptions) -> Result<i32, AocError> {
+    /* For a given set of scratchcards find the total score using the given scoring function.
+
+    Given a function representing the incrementation of score for each matched value calculate
+    the total score for a given scratchcard.
+
+    Scratchcard data is in the form:
+
+    Card X: N1, .., Ni | M1, .., Mi
+
+    Where Ni are the winning numbers and Mi the player's numbers. 
+
+    # Arguments
+
+    * `scratchcard_data` - a string representing the data for a single scratchcard.
+    * `scoring` - a lambda/function for scoring, the function takes the initial score and the matched value and returns the new total
+    * `options` - how numbers repeated on the card are matched
+
+    # Returns
+
+    The total score of the game
+
+    # Example
+
+    ```
+    let scratchcard_data = "Card 1: 1 23 65 323 | 1 323".to_string();
+    let scorer = |total, _| if total < 1 {1} else {total * 2};
+    let score = get_scratchcard_score(&scratchcard_data, &scorer, &CardOptions::default()).unwrap();
+    ```
+
+    */
+    tracing::debug!("Reading part data from '{}' using regex.", scratchcard_data);
+    
+    let (game_specs, card_vals) = match scratchcard_data.split_once('|') {
+        Some(s) => s,
+        None => return Ok(0)
+    };
+
+    let (_, winning_vals) = match game_specs.split_once(':') {
+        Some(s) => s,
+        None => return Err(AocError::parse("Invalid game data entry, cannot parse."))
+    };
+
+    let number_re = match Regex::new(r"\d+") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let winning_vals_iter: Vec<String> = number_re.find_iter(winning_vals)
+        .map(|x| x.as_str().to_string())
+        .collect();
+    let mut score: i32 = 0;
+    let mut matched = Vec::<&str>::new();
+
+    for value in number_re.find_iter(card_vals) {
+        if winning_vals_iter.iter().find(|&x| x == value.as_str()).is_some() {
+            if options.duplicates == DuplicateNumbers::Distinct {
+                if matched.contains(&value.as_str()) {
+                    tracing::debug!("Ignoring repeated value {}", value.as_str());
+                    continue;
+                }
+                matched.push(value.as_str());
+            }
+            tracing::debug!("Scoring value {}", value.as_str());
+            let value_int = match value.as_str().parse::<i32>() {
+               Ok(v) => v,
+               Err(e) => return Err(AocError::parse(format!("Failed to parse '{}': {}", value.as_str(), e)))
+            };
+            tracing::trace!(score, value = value_int, "Applying scorer");
+            score = scoring(score, value_int);
+        }
+    }
+    Ok(score)
+}
+
+fn get_gamecard_scores<F: Fn(i32, i32) -> i32, L: AsRef<str>>(card_table: impl Iterator<Item = Result<L, AocError>>, scorer: F, options: &CardOptions, diagnostics: &mut Vec<Diagnostic>) -> Result<IndexMap<i32, i32, FxBuildHasher>, AocError> {
+    /* Retrieve the scores for each game in a session of scratch cards.
+
+    For each scratchcard calculates the total score using the provided scoring function.
+    
+    # Arguments
+
+    * `card_table` - lines representing data for each scratchcard, with any error reading them.
+    * `scoring` - a lambda/function for scoring, the function takes the initial score and the matched value and returns the new total
+    * `options` - how numbers repeated on a card are matched
+    * `diagnostics` - warnings for any lines skipped are added here
+
+    # Returns
+
+    total score for each scratchcard as a hashmap
+
+    # Example
+
+    ```
+    let scorer = |total, _| if total < 1 {1} else {total * 2};
+        
+    get_gamecard_scores(card_table.lines().map(Ok), &scorer, &CardOptions::default(), &mut diagnostics).unwrap();
+    ```
+    */
+    let _span = tracing::info_span!("parse", day = 4).entered();
+
+    let regex_game_id = match Regex::new(r"Card\s+(\d+)") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let mut gamecard_scores = IndexMap::<i32, i32, FxBuildHasher>::default();
+
+    for (i, line) in card_table.enumerate() {
+        let line = line?;
+        let file_line = line.as_ref();
+        let game_id: i32 = match regex_game_id.captures_iter(file_line).next() {
+            Some(r) => {
+                match r.get(1) {
+                    Some(g1) => match g1.as_str().parse::<i32>() {
+                        Ok(n) => n,
+                        Err(e) => return Err(AocError::parse(format!("Failed to parse '{}': {}", g1.as_str(), e)))
+                    },
+                    None => continue
+                }
+            },
+            None => {
+                if !file_line.trim().is_empty() {
+                    diagnostics.push(warning(Some(i + 1), format!("No card ID found, skipped '{}'", file_line)));
+                }
+                continue
+            }
+        };
+        let score = get_scratchcard_score(file_line, &scorer, options)?;
+        tracing::debug!(game_id, score, "Scored card");
+        gamecard_scores.insert(game_id, score);
+    }
+
+    Ok(gamecard_scores)
+
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_gamecards_score<F: Fn(i32, i32) -> i32>(card_table_file: impl AsRef<Path>, scorer: F) -> Result<i32, AocError> {
+    /* Get the overall total for a session of scratchcards.
+
+    For each scratchcard calculates the total score using the provided scoring function and summates the result.
+    
+    # Arguments
+
+    * `card_table_file` - file containing lines representing data for each scratchcard.
+    * `scoring` - a lambda/function for scoring, the function takes the initial score and the matched value and returns the new total
+
+    # Returns
+
+    total score of all scratchcards
+
+    # Example
+
+    ```
+    let scorer = |total, _| if total < 1 {1} else {total * 2};
+        
+    get_total_gamecards_score(("/path/to/file", &scorer).unwrap();
+    ```
+    */
+    Ok(get_total_gamecards_score_with_diagnostics(card_table_file, scorer)?.into_answer())
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_gamecards_score_with_diagnostics<F: Fn(i32, i32) -> i32>(card_table_file: impl AsRef<Path>, scorer: F) -> Result<Outcome<i32>, AocError> {
+    /* Get the overall total for a session of scratchcards along with warnings for any lines skipped. */
+    get_total_gamecards_score_with_options(card_table_file, scorer, &CardOptions::default())
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_gamecards_score_with_options<F: Fn(i32, i32) -> i32>(card_table_file: impl AsRef<Path>, scorer: F, options: &CardOptions) -> Result<Outcome<i32>, AocError> {
+    /* Get the overall total for a session of scratchcards, choosing how repeated numbers are matched. */
+    total_gamecards_score(lines(&card_table_file)?.map(|l| l.map_err(AocError::from)), scorer, options)
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_gamecards_score_from_reader<F: Fn(i32, i32) -> i32>(card_table: impl BufRead, scorer: F, options: &CardOptions) -> Result<Outcome<i32>, AocError> {
+    /* Get the overall total for a session of scratchcards read from any buffered reader.
+
+    # Example
+
+    ```
+    let scorer = |total, _| if total < 1 {1} else {total * 2};
+
+    let outcome = get_total_gamecards_score_from_reader("Card 1: 41 48 | 48 41".as_bytes(), &scorer, &CardOptions::default()).unwrap();
+    ```
+    */
+    total_gamecards_score(read_lines(card_table).map(|l| l.map_err(AocError::from)), scorer, options)
+}
+
+fn total_gamecards_score<F: Fn(i32, i32) -> i32, L: AsRef<str>>(card_table: impl Iterator<Item = Result<L, AocError>>, scorer: F, options: &CardOptions) -> Result<Outcome<i32>, AocError> {
+    let _span = tracing::info_span!("solve", day = 4, part = 1).entered();
+
+    let mut diagnostics = Vec::<Diagnostic>::new();
+    let gamecard_scores = get_gamecard_scores(card_table, &scorer, options, &mut diagnostics)?;
+
+    let total_score = gamecard_scores.values().sum();
+
+    Ok(Outcome::with_diagnostics(total_score, diagnostics))
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_cards_won<F: Fn(i32, i32) -> i32>(card_table_file: impl AsRef<Path>, scorer: F) -> Result<i32, AocError> {
+    /* For a given set of scratchcard data use the proper scoring system of winning cards per game.
+
+    The alternate scoring system whereby cards are won for each match found, and matches for
+    the won cards are also taken into account. The given scoring function is used to find the total score.
+
+     # Arguments
+
+    * `card_table_file` - file containing lines representing data for each scratchcard.
+    * `scoring` - a lambda/function for scoring, the function takes the initial score and the matched value and returns the new total
+
+    # Returns
+
+    total score of all cumulative scratchcards after game completion
+
+    # Example
+
+    ```
+    let scorer = |total, _| total + 1;
+        
+    get_total_cards_won(("/path/to/file", &scorer).unwrap();
+    ```
+    */
+    Ok(get_total_cards_won_with_diagnostics(card_table_file, scorer)?.into_answer())
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_cards_won_with_diagnostics<F: Fn(i32, i32) -> i32>(card_table_file: impl AsRef<Path>, scorer: F) -> Result<Outcome<i32>, AocError> {
+    /* Get the total number of cards won along with warnings for skipped lines and cards won
+    beyond the end of the table, which the rules say should not happen. */
+    get_total_cards_won_with_options(card_table_file, scorer, &CardOptions::default())
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_cards_won_with_options<F: Fn(i32, i32) -> i32>(card_table_file: impl AsRef<Path>, scorer: F, options: &CardOptions) -> Result<Outcome<i32>, AocError> {
+    /* Get the total number of cards won, choosing how repeated numbers are matched. */
+    total_cards_won(lines(&card_table_file)?.map(|l| l.map_err(AocError::from)), scorer, options)
+}
+
+#[cfg(feature = "std")]
+pub fn get_total_cards_won_from_reader<F: Fn(i32, i32) -> i32>(card_table: impl BufRead, scorer: F, options: &CardOptions) -> Result<Outcome<i32>, AocError> {
+    /* Get the total number of cards won for a session of scratchcards read from any buffered reader. */
+    total_cards_won(read_lines(card_table).map(|l| l.map_err(AocError::from)), scorer, options)
+}
+
+fn total_cards_won<F: Fn(i32, i32) -> i32, L: AsRef<str>>(card_table: impl Iterator<Item = Result<L, AocError>>, scorer: F, options: &CardOptions) -> Result<Outcome<i32>, AocError> {
+    let _span = tracing::info_span!("solve", day = 4, part = 2).entered();
+
+    tracing::info!("Totaling all cards won this session");
+
+    let mut diagnostics = Vec::<Diagnostic>::new();
+    let gamecard_scores = get_gamecard_scores(card_table, &scorer, options, &mut diagnostics)?;
+
+    let mut card_counter: FxHashMap<i32, i32> = gamecard_scores
+        .keys()
+        .map(|&card_id| (card_id, 1))
+        .collect();
+
+    for (card_id, matches) in &gamecard_scores {
+        let card_quantity = match card_counter.get(card_id) {
+            Some(sc) => *sc,
+            None => return Err(AocError::MissingData(format!("Expected score for card {} but none found", card_id)))
+        };
+
+        for card_index in card_id + 1..=card_id + matches {
+            match card_counter.get_mut(&card_index) {
+                Some(v) => {
+                    *v += card_quantity;
+                },
+                None => {
+                    diagnostics.push(warning(None, format!("Card {} wins a copy of card {} which is not in the table", card_id, card_index)));
+                    card_counter.insert(card_index, card_quantity);
+                }
+            }
+        }
+    }
+
+    Ok(Outcome::with_diagnostics(card_counter.values().sum(), diagnostics))
+}
+
+// The duplication cascade of part 2, from which the effect of changing a single card
+// is found without replaying the game. Each card has a number of copies, won from the
+// cards before it, and a weight, the number of cards one copy of it produces in total.
+// Cards won beyond the end of the table count once, as in `get_total_cards_won`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CardCascade {
+    cards: IndexMap<i32, (i32, i64, i64), FxBuildHasher>
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CardSensitivity {
+    pub card_id: i32,
+    pub matches: i32,
+    pub copies: i64,
+    // Change in the total if the card were removed from the table
+    pub removed: i64,
+    // Change in the total if the card had one more or one fewer match
+    pub one_more_match: i64,
+    pub one_fewer_match: i64
+}
+
+impl CardCascade {
+    #[cfg(feature = "std")]
+    pub fn from_file<F: Fn(i32, i32) -> i32>(card_table_file: impl AsRef<Path>, scorer: F) -> Result<CardCascade, AocError> {
+        /* Build the cascade for a table of cards, scoring matches as for `get_total_cards_won`.
+
+        # Examples
+
+        ```
+        let cascade = CardCascade::from_file("/path/to/file", |total, _| total + 1).unwrap();
+        ```
+        */
+        CardCascade::from_card_table(lines(&card_table_file)?.map(|l| l.map_err(AocError::from)), scorer)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_reader<F: Fn(i32, i32) -> i32>(card_table: impl BufRead, scorer: F) -> Result<CardCascade, AocError> {
+        /* Build the cascade for a table of cards read from any buffered reader. */
+        CardCascade::from_card_table(read_lines(card_table).map(|l| l.map_err(AocError::from)), scorer)
+    }
+
+    #[cfg(feature = "std")]
+    fn from_card_table<F: Fn(i32, i32) -> i32, L: AsRef<str>>(card_table: impl Iterator<Item = Result<L, AocError>>, scorer: F) -> Result<CardCascade, AocError> {
+        let mut diagnostics = Vec::<Diagnostic>::new();
+        let gamecard_scores = get_gamecard_scores(card_table, &scorer, &CardOptions::default(), &mut diagnostics)?;
+
+        Ok(CardCascade::from_matches(gamecard_scores.into_iter().collect()))
+    }
+
+    pub fn from_matches(card_matches: Vec<(i32, i32)>) -> CardCascade {
+        /* Build the cascade from (card ID, matches) pairs in table order. */
+        let mut cards: IndexMap<i32, (i32, i64, i64), FxBuildHasher> = card_matches
+            .iter()
+            .map(|&(card_id, matches)| (card_id, (matches, 1, 1)))
+            .collect();
+
+        // Weights depend only on later cards, so are found working backwards
+        for i in (0..cards.len()).rev() {
+            let (card_id, matches) = match cards.get_index(i) {
+                Some((&id, &(m, _, _))) => (id, m),
+                None => continue
+            };
+            let weight = 1 + (card_id + 1..=card_id + matches).map(|c| CardCascade::weight_of(&cards, c)).sum::<i64>();
+            if let Some((_, card)) = cards.get_index_mut(i) {
+                card.2 = weight;
+            }
+        }
+
+        // Copies depend only on earlier cards
+        for i in 0..cards.len() {
+            let (card_id, matches, copies) = match cards.get_index(i) {
+                Some((&id, &(m, c, _))) => (id, m, c),
+                None => continue
+            };
+            for won in card_id + 1..=card_id + matches {
+                if let Some(card) = cards.get_mut(&won) {
+                    card.1 += copies;
+                }
+            }
+        }
+
+        CardCascade { cards }
+    }
+
+    fn weight_of(cards: &IndexMap<i32, (i32, i64, i64), FxBuildHasher>, card_id: i32) -> i64 {
+        match cards.get(&card_id) {
+            Some(&(_, _, weight)) => weight,
+            None => 1
+        }
+    }
+
+    pub fn total(&self) -> i64 {
+        /* The total number of cards at the end of the game. */
+        self.cards.values().map(|&(_, _, weight)| weight).sum()
+    }
+
+    pub fn removal_change(&self, card_id: i32) -> Option<i64> {
+        /* Change in the total if a card were removed, along with every copy of it won. */
+        let &(_, copies, weight) = self.cards.get(&card_id)?;
+        Some(-copies * weight)
+    }
+
+    pub fn match_change(&self, card_id: i32, matches: i32) -> Option<i64> {
+        /* Change in the total if a card had the given number of matches instead. */
+        let &(_, copies, weight) = self.cards.get(&card_id)?;
+        let new_weight = 1 + (card_id + 1..=card_id + matches.max(0)).map(|c| CardCascade::weight_of(&self.cards, c)).sum::<i64>();
+        Some(copies * (new_weight - weight))
+    }
+
+    pub fn sensitivities(&self) -> Vec<CardSensitivity> {
+        /* The effect on the total of removing each card or changing its matches by one. */
+        self.cards
+            .iter()
+            .map(|(&card_id, &(matches, copies, _))| CardSensitivity {
+                card_id,
+                matches,
+                copies,
+                removed: -copies * CardCascade::weight_of(&self.cards, card_id),
+                one_more_match: self.match_change(card_id, matches + 1).unwrap_or_default(),
+                one_fewer_match: self.match_change(card_id, matches - 1).unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+// The day 4 puzzle, part 1 by default doubling the score for each match and part 2 counting cards won
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day4 {
+    pub options: CardOptions
+}
+
+impl Solver for Day4 {
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(self.solve_with_diagnostics(1, input)?.into_answer())
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(self.solve_with_diagnostics(2, input)?.into_answer())
+    }
+
+    // Cards which cannot be read are skipped in both parts and reported as diagnostics
+    fn solve_with_diagnostics(&self, part: u32, input: &str) -> Result<Outcome<Answer>, AocError> {
+        let outcome = match part {
+            1 => total_gamecards_score(input.lines().map(Ok), |total, value| self.options.scoring.score(total, value), &self.options)?,
+            2 => total_cards_won(input.lines().map(Ok), |total, _| total + 1, &self.options)?,
+            _ => return Err(AocError::InvalidArgument(format!("Day 4 has no part {}", part)))
+        };
+        Ok(outcome.map(Answer::from))
+    }
+}
+
+inventory::submit! {
+    Registration { name: "default", build: || Box::new(Day4::default()) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_scoring() {
+        let test_str ="Game N: 34 45 8 81 40 23 | 8 45 9 12 65 23".to_string();
+
+        let scorer = |total, _| if total < 1 {1} else {total * 2};
+
+        assert_eq!(get_scratchcard_score(&test_str, scorer, &CardOptions::default()).unwrap(), 4);
+
+        let overflowing = "Card 1: 99999999999 | 99999999999";
+        assert!(get_scratchcard_score(overflowing, scorer, &CardOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_total_score() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+
+        let scorer = |total, _| if total < 1 {1} else {total * 2};
+        
+        assert_eq!(get_total_gamecards_score(&test_file, scorer).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_total_cards() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+
+        let scorer = |total, _| total + 1;
+        
+        let total_cards = get_total_cards_won(&test_file, scorer).unwrap();
+        assert_eq!(total_cards, 30);
+    }
+
+    #[test]
+    fn test_card_diagnostics() {
+        let scorer = |total, _| total + 1;
+
+        let test_file = std::env::temp_dir().join(format!("aoc23_day_4_diagnostics_{}.dat", std::process::id()));
+        std::fs::write(&test_file, "Card 1: 1 2 | 1 2\nCrd 2: 3 | 3\nCard 2: 5 | 6\n").unwrap();
+        let part_1 = get_total_gamecards_score_with_diagnostics(&test_file, scorer).unwrap();
+        let part_2 = get_total_cards_won_with_diagnostics(&test_file, scorer).unwrap();
+        std::fs::remove_file(&test_file).unwrap();
+
+        assert_eq!(part_1.answer, 2);
+        assert_eq!(part_1.diagnostics.len(), 1);
+        assert_eq!(part_1.diagnostics[0].line, Some(2));
+
+        // Card 1 wins copies of cards 2 and 3, but there is no card 3
+        assert_eq!(part_2.diagnostics.len(), 2);
+        assert!(part_2.diagnostics[1].message.contains("card 3"));
+    }
+
+    #[test]
+    fn test_cards_from_reader() {
+        let card_table = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3: 1 21 53 59 44 | 69 82 63 72 16 21 14  1\n";
+        let options = CardOptions::default();
+
+        let part_1 = get_total_gamecards_score_from_reader(card_table.as_bytes(), |total, _| if total < 1 {1} else {total * 2}, &options).unwrap();
+        assert_eq!(part_1.answer, 8 + 2 + 2);
+
+        // Card 1 wins copies of cards 2 to 5, only 2 and 3 of which are in the table
+        let part_2 = get_total_cards_won_from_reader(card_table.as_bytes(), |total, _| total + 1, &options).unwrap();
+        assert_eq!(part_2.diagnostics.len(), 2);
+        assert_eq!(CardCascade::from_reader(card_table.as_bytes(), |total, _| total + 1).unwrap().total(), part_2.answer as i64);
+    }
+
+    #[test]
+    fn test_duplicate_numbers() {
+        let scorer = |total, _| total + 1;
+        let distinct = CardOptions { duplicates: DuplicateNumbers::Distinct, ..CardOptions::default() };
+
+        let test_str = "Card 1: 5 5 7 | 5 5 7 9".to_string();
+        assert_eq!(get_scratchcard_score(&test_str, scorer, &CardOptions::default()).unwrap(), 3);
+        assert_eq!(get_scratchcard_score(&test_str, scorer, &distinct).unwrap(), 2);
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+        assert_eq!(get_total_cards_won_with_options(&test_file, scorer, &distinct).unwrap().answer, 30);
+    }
+
+    #[test]
+    fn test_scoring_options() {
+        let card_table = std::fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/test/day_4.dat")).unwrap();
+        let counting = Day4 { options: CardOptions { scoring: Scoring::Count, ..CardOptions::default() } };
+
+        assert_eq!(Day4::default().part1(&card_table).unwrap(), 13);
+        assert_eq!(counting.part1(&card_table).unwrap(), 4 + 2 + 2 + 1);
+        assert_eq!(counting.part2(&card_table).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_card_cascade() {
+        let scorer = |total, _| total + 1;
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+        let cascade = CardCascade::from_file(&test_file, scorer).unwrap();
+
+        assert_eq!(cascade.total(), 30);
+
+        let sensitivities = cascade.sensitivities();
+        assert_eq!(sensitivities.iter().map(|s| s.copies).collect::<Vec<i64>>(), vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(sensitivities.iter().map(|s| s.removed).collect::<Vec<i64>>(), vec![-15, -14, -16, -16, -14, -1]);
+
+        // Check the match changes against replaying the game with the card changed
+        let matches = [(1, 4), (2, 2), (3, 2), (4, 1), (5, 0), (6, 0)];
+        for sensitivity in sensitivities {
+            let adjusted = |delta: i32| matches
+                .iter()
+                .map(|&(c, m)| if c == sensitivity.card_id {(c, (m + delta).max(0))} else {(c, m)})
+                .collect();
+
+            assert_eq!(30 + sensitivity.one_more_match, CardCascade::from_matches(adjusted(1)).total());
+            assert_eq!(30 + sensitivity.one_fewer_match, CardCascade::from_matches(adjusted(-1)).total());
+        }
+
+        assert_eq!(cascade.removal_change(1), Some(-15));
+        assert_eq!(cascade.match_change(7, 1), None);
+    }
+}
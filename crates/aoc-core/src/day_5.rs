@@ -0,0 +1,1962 @@
+use alloc::{boxed::Box, format, string::{String, ToString}, vec, vec::Vec};
+use regex::Regex;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::fs::read_to_string;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+use crate::outcome::{warning, Diagnostic, Outcome};
+use crate::progress::{NoProgress, ProgressEvent, ProgressSink};
+use crate::error::AocError;
+use crate::answer::Answer;
+use crate::solver::{Registration, Solver};
+use crate::util::{blocks, topological_sort, LruCache};
+use crate::util::hash::FxBuildHasher;
+#[cfg(feature = "std")]
+use crate::util::io::InputSource;
+
+// Pieces of seed ranges whose lowest location is remembered unless another capacity is chosen
+pub const DEFAULT_MEMO_CAPACITY: usize = 4096;
+
+// The lowest location reached from a range of values of the map at an index of the seed to location chain
+pub type LocationMemo = LruCache<(usize, i64, i64), Option<i64>>;
+
+// A seed number, as listed on the first line of an almanac
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Seed(pub i64);
+
+// A location number, reached from a seed through every map of an almanac
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location(pub i64);
+
+// An inclusive range of seeds, first..=last
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SeedRange {
+    pub first: Seed,
+    pub last: Seed
+}
+
+impl SeedRange {
+    pub fn new(first: i64, last: i64) -> SeedRange {
+        SeedRange { first: Seed(first), last: Seed(last) }
+    }
+
+    pub fn bounds(&self) -> (i64, i64) {
+        (self.first.0, self.last.0)
+    }
+
+    pub fn size(&self) -> i64 {
+        self.last.0 - self.first.0 + 1
+    }
+
+    pub fn contains(&self, seed: Seed) -> bool {
+        self.first <= seed && seed <= self.last
+    }
+}
+
+// An inclusive range of locations, first..=last
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocationRange {
+    pub first: Location,
+    pub last: Location
+}
+
+impl LocationRange {
+    pub fn new(first: i64, last: i64) -> LocationRange {
+        LocationRange { first: Location(first), last: Location(last) }
+    }
+
+    pub fn bounds(&self) -> (i64, i64) {
+        (self.first.0, self.last.0)
+    }
+
+    pub fn size(&self) -> i64 {
+        self.last.0 - self.first.0 + 1
+    }
+
+    pub fn contains(&self, location: Location) -> bool {
+        self.first <= location && location <= self.last
+    }
+}
+
+// A single line of an almanac map, sending source_start..source_start + length
+// to destination_start..destination_start + length
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RangeMapping {
+    pub destination_start: i64,
+    pub source_start: i64,
+    pub length: i64
+}
+
+impl RangeMapping {
+    pub fn source_end(&self) -> i64 {
+        self.source_start + self.length - 1
+    }
+
+    pub fn destination_end(&self) -> i64 {
+        self.destination_start + self.length - 1
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.destination_start - self.source_start
+    }
+
+    pub fn inverse(&self) -> RangeMapping {
+        RangeMapping {
+            destination_start: self.source_start,
+            source_start: self.destination_start,
+            length: self.length
+        }
+    }
+
+    pub fn map_value(&self, value: i64) -> Option<i64> {
+        if value < self.source_start || value > self.source_end() {
+            return None;
+        }
+        Some(value + self.offset())
+    }
+}
+
+// A single almanac map converting values from one category to another,
+// values outside all of the ranges are unchanged
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CategoryMap {
+    pub source: String,
+    pub destination: String,
+    pub ranges: Vec<RangeMapping>
+}
+
+impl CategoryMap {
+    fn map_value_with_range(&self, value: i64) -> (i64, Option<usize>) {
+        for (i, range) in self.ranges.iter().enumerate() {
+            if let Some(v) = range.map_value(value) {
+                return (v, Some(i));
+            }
+        }
+        (value, None)
+    }
+
+    pub fn map_value(&self, value: i64) -> i64 {
+        self.map_value_with_range(value).0
+    }
+
+    pub fn unmapped_gaps(&self) -> Vec<(i64, i64)> {
+        /* Source intervals not covered by any of the ranges within the map.
+
+        Values within these intervals pass through the map unchanged. Only gaps between
+        zero and the end of the highest range are listed, all values beyond this are also
+        unchanged.
+
+        # Returns
+
+        The inclusive (lower, upper) intervals which are not mapped.
+
+        # Examples
+
+        ```
+        let gaps = almanac.category_map("seed").unwrap().unmapped_gaps();
+        ```
+        */
+        let source_ranges: Vec<(i64, i64)> = self.ranges
+            .iter()
+            .map(|r| (r.source_start, r.source_end()))
+            .collect();
+
+        let mut gaps = Vec::<(i64, i64)>::new();
+        let mut next_unmapped: i64 = 0;
+
+        for (lower, upper) in normalize_ranges(&source_ranges) {
+            if lower > next_unmapped {
+                gaps.push((next_unmapped, lower - 1));
+            }
+            next_unmapped = next_unmapped.max(upper + 1);
+        }
+
+        gaps
+    }
+
+    pub fn is_one_to_one(&self) -> bool {
+        /* Whether every value is reached from exactly one value, so the map can be reversed.
+
+        This holds when neither the source nor the destination ranges overlap and together
+        the destination ranges cover the same values as the source ranges, e.g. not for
+        '10 0 3', where 10..=12 are reached both from 0..=2 and, unchanged, from themselves.
+        */
+        let bounds = |f: fn(&RangeMapping) -> (i64, i64)| -> Vec<(i64, i64)> {
+            let mut b: Vec<(i64, i64)> = self.ranges.iter().filter(|r| r.length > 0).map(f).collect();
+            b.sort();
+            b
+        };
+        let sources = bounds(|r| (r.source_start, r.source_end()));
+        let destinations = bounds(|r| (r.destination_start, r.destination_end()));
+
+        let disjoint = |b: &[(i64, i64)]| b.windows(2).all(|w| w[0].1 < w[1].0);
+        disjoint(&sources) && disjoint(&destinations) && normalize_ranges(&sources) == normalize_ranges(&destinations)
+    }
+
+    pub fn inverse(&self) -> CategoryMap {
+        CategoryMap {
+            source: self.destination.clone(),
+            destination: self.source.clone(),
+            ranges: self.ranges.iter().map(|r| r.inverse()).collect()
+        }
+    }
+
+    pub fn to_block(&self) -> String {
+        /* Write the map in the almanac block format it was read from, see `parse_block`. */
+        let mut block = format!("{}-to-{} map:\n", self.source, self.destination);
+        for range in &self.ranges {
+            block.push_str(&format!("{} {} {}\n", range.destination_start, range.source_start, range.length));
+        }
+        block
+    }
+
+    pub fn parse_block(block: &str) -> Result<CategoryMap, AocError> {
+        /* Read a single map written in the almanac block format.
+
+        # Examples
+
+        ```
+        let map = CategoryMap::parse_block("seed-to-soil map:\n50 98 2\n52 50 48\n").unwrap();
+        ```
+        */
+        match get_conversions(&[block])?.pop() {
+            Some(m) => Ok(m),
+            None => Err(AocError::parse("Expected a map block, found nothing"))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save(&self, file_name: impl AsRef<Path>) -> Result<(), AocError> {
+        /* Write the map to a JSON file, e.g. a composed map to be reused by later runs. */
+        let json = match serde_json::to_string_pretty(self) {
+            Ok(j) => j,
+            Err(e) => return Err(AocError::parse(format!("Failed to serialize the {}-to-{} map: {}", self.source, self.destination, e)))
+        };
+        match std::fs::write(file_name.as_ref(), json) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AocError::io(file_name.as_ref(), e))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load(file_name: impl AsRef<Path>) -> Result<CategoryMap, AocError> {
+        /* Read a map previously written with `save`. */
+        let content = match read_to_string(file_name.as_ref()) {
+            Ok(c) => c,
+            Err(e) => return Err(AocError::io(file_name.as_ref(), e))
+        };
+        match serde_json::from_str(&content) {
+            Ok(m) => Ok(m),
+            Err(e) => Err(AocError::parse(format!("Invalid map file '{}': {}", file_name.as_ref().display(), e)))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConversionStep {
+    pub source: String,
+    pub destination: String,
+    pub input: i64,
+    pub output: i64,
+    // Index of the range within the map which was applied, None if passed through unchanged
+    pub range_index: Option<usize>
+}
+
+// Each category alongside the inclusive ranges of values reached within it, see `Almanac::category_ranges`
+pub type CategoryRanges = Vec<(String, Vec<(i64, i64)>)>;
+
+pub struct Almanac {
+    seeds: Vec<i64>,
+    maps: IndexMap<String, CategoryMap, FxBuildHasher>
+}
+
+impl Almanac {
+    pub fn parse(almanac_data: &str) -> Result<Almanac, AocError> {
+        /* Parse the contents of an almanac.
+
+        The first line of the almanac lists the seeds, the remainder consists of blocks
+        each with a header of the form 'source-to-destination map:' followed by the
+        range definitions for that conversion.
+
+        # Arguments
+
+        * `almanac_data` - the full contents of an almanac file
+
+        # Examples
+
+        ```
+        let almanac = Almanac::parse(&read_to_string("/path/to/file").unwrap()).unwrap();
+        ```
+        */
+        let _span = tracing::info_span!("parse", day = 5).entered();
+
+        let almanac_blocks = blocks(almanac_data);
+
+        let (seed_block, map_blocks) = match almanac_blocks.split_first() {
+            Some(b) => b,
+            None => return Err(AocError::parse("Failed to obtain number of seeds"))
+        };
+
+        let seeds = get_target_seeds(seed_block)?;
+        let maps = get_conversions(map_blocks)?;
+
+        Almanac::from_maps(seeds, maps)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_file(file_name: impl AsRef<Path>) -> Result<Almanac, AocError> {
+        /* Read and parse an almanac file.
+
+        # Arguments
+
+        * `file_name` - path of the almanac data file
+
+        # Examples
+
+        ```
+        let almanac = Almanac::from_file("/path/to/file").unwrap();
+        ```
+        */
+        let file_str = match read_to_string(file_name.as_ref()) {
+            Ok(contents) => contents,
+            Err(e) => return Err(AocError::io(file_name.as_ref(), e))
+        };
+        Almanac::parse(&file_str)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_reader(mut almanac_data: impl BufRead) -> Result<Almanac, AocError> {
+        /* Read and parse an almanac from any buffered reader.
+
+        The almanac is split into blocks at blank lines, so is read in full before parsing.
+        For an almanac already held in a string use `Almanac::parse`.
+
+        # Examples
+
+        ```
+        let almanac = Almanac::from_reader(std::io::stdin().lock()).unwrap();
+        ```
+        */
+        let mut almanac_str = String::new();
+        match almanac_data.read_to_string(&mut almanac_str) {
+            Ok(_) => Almanac::parse(&almanac_str),
+            Err(e) => Err(AocError::io(PathBuf::new(), e))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_input(almanac_data: &InputSource) -> Result<Almanac, AocError> {
+        /* Parse an almanac borrowed from an input source, such as a memory-mapped file.
+
+        # Examples
+
+        ```
+        let almanac = Almanac::from_input(&InputSource::read("/path/to/file").unwrap()).unwrap();
+        ```
+        */
+        Almanac::parse(almanac_data.as_str()?)
+    }
+
+    pub fn from_maps(seeds: Vec<i64>, maps: Vec<CategoryMap>) -> Result<Almanac, AocError> {
+        /* Build an almanac from a set of seeds and category maps.
+
+        Maps are keyed by their source category, allowing stages from one or more parsed
+        almanacs to be recombined. The maps may be given in any order, they are sorted
+        topologically into a single chain of conversions. An error is returned if the
+        maps contain a cycle, more than one map shares a source category, or the maps do
+        not link together into a single chain.
+
+        # Arguments
+
+        * `seeds` - the seed numbers
+        * `maps` - the maps between categories
+
+        # Examples
+
+        ```
+        let reduced = Almanac::from_maps(almanac.seeds().clone(), almanac.maps().take(2).cloned().collect()).unwrap();
+        ```
+        */
+        let maps = order_maps(maps)?;
+
+        Ok(Almanac { seeds, maps })
+    }
+
+    pub fn seeds(&self) -> &Vec<i64> {
+        &self.seeds
+    }
+
+    pub fn maps(&self) -> impl Iterator<Item = &CategoryMap> {
+        self.maps.values()
+    }
+
+    pub fn category_map(&self, source: &str) -> Option<&CategoryMap> {
+        self.maps.get(source)
+    }
+
+    pub fn unmapped_gaps(&self) -> IndexMap<String, Vec<(i64, i64)>, FxBuildHasher> {
+        /* List the unmapped source intervals of every map, keyed by source category.
+
+        # Examples
+
+        ```
+        for (category, gaps) in almanac.unmapped_gaps() {
+            tracing::info!("Values of '{}' passed through unchanged: {:?}", category, gaps);
+        }
+        ```
+        */
+        self.maps
+            .iter()
+            .map(|(category, m)| (category.clone(), m.unmapped_gaps()))
+            .collect()
+    }
+
+    pub fn seed_ranges(&self, use_ranges: bool) -> Vec<SeedRange> {
+        /* The inclusive ranges of seed values listed in the almanac.
+
+        # Arguments
+
+        * `use_ranges` - treat the seeds as pairs of range start and length rather than
+          individual seed values
+
+        # Examples
+
+        ```
+        let seed_ranges = almanac.seed_ranges(true);
+        ```
+        */
+        if use_ranges {
+            self.seeds
+                .chunks(2)
+                .filter(|x| x.len() == 2)
+                .map(|x| SeedRange::new(x[0], x[0] + x[1] - 1))
+                .collect()
+        } else {
+            self.seeds
+                .iter()
+                .map(|&x| SeedRange::new(x, x))
+                .collect()
+        }
+    }
+
+    pub fn location_ranges(&self, use_ranges: bool) -> Result<Vec<(i64, i64)>, AocError> {
+        /* Propagate all seed ranges through the almanac to obtain the location ranges.
+
+        # Arguments
+
+        * `use_ranges` - treat the seeds as pairs of range start and length
+
+        # Returns
+
+        The inclusive location ranges reached, these may overlap or be fragmented,
+        see `normalize_ranges`.
+
+        # Examples
+
+        ```
+        let locations = almanac.location_ranges(false).unwrap();
+        ```
+        */
+        let _span = tracing::info_span!("solve", day = 5, part = if use_ranges {2} else {1}).entered();
+
+        let mut propagated_values = Vec::<(i64, i64)>::new();
+
+        let chain = self.map_chain("seed", "location")?;
+
+        for seed_range in self.seed_ranges(use_ranges) {
+            let propagated_value = get_propagated_values(&seed_range.bounds(), &chain)?;
+            propagated_values.extend(propagated_value);
+        }
+
+        Ok(propagated_values)
+    }
+
+    pub fn category_ranges(&self, use_ranges: bool) -> Result<CategoryRanges, AocError> {
+        /* The values reached by the seeds in each category from seed to location, in the order they are mapped.
+
+        # Arguments
+
+        * `use_ranges` - treat the seeds as pairs of range start and length
+
+        # Returns
+
+        Each category alongside the sorted, merged ranges of values the seeds are mapped
+        to within it, beginning with the seed ranges themselves.
+
+        # Examples
+
+        ```
+        let (category, soil_ranges) = &almanac.category_ranges(true).unwrap()[1];
+        ```
+        */
+        let chain = self.map_chain("seed", "location")?;
+        let seed_ranges: Vec<(i64, i64)> = self.seed_ranges(use_ranges).iter().map(|r| r.bounds()).collect();
+
+        let mut categories = vec![("seed".to_string(), normalize_ranges(&seed_ranges))];
+        for (i, category_map) in chain.iter().enumerate() {
+            let mut reached = Vec::<(i64, i64)>::new();
+            for seed_range in &seed_ranges {
+                reached.extend(get_propagated_values(seed_range, &chain[..=i])?);
+            }
+            categories.push((category_map.destination.clone(), normalize_ranges(&reached)));
+        }
+
+        Ok(categories)
+    }
+
+    pub fn diagnostics(&self, use_ranges: bool) -> Vec<Diagnostic> {
+        /* Warnings about features of the almanac which may give a surprising answer.
+
+        Reports a trailing seed without a range length, seed ranges which overlap, so that
+        seeds are counted more than once, and maps whose source ranges overlap, where only
+        the first matching range is applied.
+        */
+        let mut diagnostics = Vec::<Diagnostic>::new();
+
+        if use_ranges && self.seeds.len() % 2 == 1 {
+            diagnostics.push(warning(None, format!(
+                "Odd number of seed values, the final seed {} has no range length and is ignored",
+                self.seeds[self.seeds.len() - 1]
+            )));
+        }
+
+        let mut seed_ranges = self.seed_ranges(use_ranges);
+        seed_ranges.sort();
+        for pair in seed_ranges.windows(2) {
+            if pair[1].first <= pair[0].last {
+                diagnostics.push(warning(None, format!(
+                    "Seed ranges {}..={} and {}..={} overlap",
+                    pair[0].first.0, pair[0].last.0, pair[1].first.0, pair[1].last.0
+                )));
+            }
+        }
+
+        for category_map in self.maps.values() {
+            let mut sources: Vec<(i64, i64)> = category_map.ranges
+                .iter()
+                .map(|r| (r.source_start, r.source_end()))
+                .collect();
+            sources.sort();
+            if sources.windows(2).any(|w| w[1].0 <= w[0].1) {
+                diagnostics.push(warning(None, format!(
+                    "The {}-to-{} map has overlapping source ranges",
+                    category_map.source, category_map.destination
+                )));
+            }
+        }
+
+        diagnostics
+    }
+
+    pub fn location_ranges_with_diagnostics(&self, use_ranges: bool) -> Result<Outcome<Vec<(i64, i64)>>, AocError> {
+        /* The location ranges reached from the seeds along with any warnings about the almanac. */
+        Ok(Outcome::with_diagnostics(self.location_ranges(use_ranges)?, self.diagnostics(use_ranges)))
+    }
+
+    pub fn min_location(&self, seed_ranges: &[SeedRange]) -> Result<Option<Location>, AocError> {
+        /* Find the lowest location reachable from the given seed ranges.
+
+        Ranges are split at map boundaries and followed depth first, keeping only the
+        lowest location found so far, so the full set of propagated ranges is never
+        held in memory as it is by `location_ranges`.
+
+        # Arguments
+
+        * `seed_ranges` - the seed ranges to start from, e.g. from `seed_ranges`
+
+        # Examples
+
+        ```
+        let lowest = almanac.min_location(&almanac.seed_ranges(true)).unwrap();
+        ```
+        */
+        self.min_location_with_progress(seed_ranges, NoProgress)
+    }
+
+    pub fn min_location_with_progress<P: ProgressSink>(&self, seed_ranges: &[SeedRange], progress: P) -> Result<Option<Location>, AocError> {
+        /* As `min_location`, publishing progress as each seed range is finished. */
+        self.min_location_with_cache(seed_ranges, progress, &mut LruCache::new(DEFAULT_MEMO_CAPACITY))
+    }
+
+    pub fn min_location_with_cache<P: ProgressSink>(&self, seed_ranges: &[SeedRange], mut progress: P, cache: &mut LocationMemo) -> Result<Option<Location>, AocError> {
+        /* As `min_location_with_progress`, remembering the lowest location of each piece of a seed range.
+
+        Each range a seed range is split into at a map is remembered along with the
+        lowest location it reaches, so that the pieces seed ranges have in common, when
+        they repeat or overlap, are only followed through the remaining maps once. The
+        cache is bounded, so an adversarial list of seeds cannot grow it without limit,
+        and may be kept between calls on the same almanac.
+
+        # Examples
+
+        ```
+        let mut cache = LruCache::new(1024);
+        let lowest = almanac.min_location_with_cache(&almanac.seed_ranges(false), NoProgress, &mut cache).unwrap();
+        ```
+        */
+        let _span = tracing::info_span!("solve", day = 5, lazy = true).entered();
+
+        let chain = self.map_chain("seed", "location")?;
+        let mut minimum: Option<i64> = None;
+        let total = seed_ranges.len() as u64;
+
+        for (done, seed_range) in seed_ranges.iter().enumerate() {
+            let lowest = lowest_location(seed_range.bounds(), 0, &chain, cache);
+
+            minimum = match (minimum, lowest) {
+                (Some(m), Some(l)) => Some(m.min(l)),
+                (m, l) => m.or(l)
+            };
+
+            progress.publish(&ProgressEvent { day: 5, stage: "seed ranges", done: done as u64 + 1, total });
+        }
+
+        Ok(minimum.map(Location))
+    }
+
+    pub fn contains_seed(&self, seed: i64, use_ranges: bool) -> bool {
+        /* Whether a seed value is one of the seeds listed within the almanac.
+
+        # Arguments
+
+        * `seed` - the seed value to check
+        * `use_ranges` - treat the seeds as pairs of range start and length
+
+        # Examples
+
+        ```
+        assert!(almanac.contains_seed(82, true));
+        ```
+        */
+        self.seed_ranges(use_ranges)
+            .iter()
+            .any(|r| r.contains(Seed(seed)))
+    }
+
+    pub fn location_coverage(&self, lower: i64, upper: i64, use_ranges: bool) -> Result<f64, AocError> {
+        /* The fraction of a location interval which is reachable from the almanac seeds.
+
+        # Arguments
+
+        * `lower` - the first location of the interval
+        * `upper` - the last location of the interval (inclusive)
+        * `use_ranges` - treat the seeds as pairs of range start and length
+
+        # Examples
+
+        ```
+        let fraction = almanac.location_coverage(0, 99, true).unwrap();
+        ```
+        */
+        if upper < lower {
+            return Err(AocError::InvalidArgument(format!("Invalid location interval {} <= x <= {}", lower, upper)));
+        }
+
+        let clipped: Vec<(i64, i64)> = self.location_ranges(use_ranges)?
+            .iter()
+            .filter(|(l, u)| *u >= lower && *l <= upper)
+            .map(|(l, u)| (*l.max(&lower), *u.min(&upper)))
+            .collect();
+
+        Ok(covered_size(&clipped) as f64 / (upper - lower + 1) as f64)
+    }
+
+    pub fn convert(&self, value: i64, from: &str, to: &str) -> Result<i64, AocError> {
+        /* Map a value from one category to another.
+
+        Follows the chain of maps starting at the `from` category, applying each in turn
+        until the `to` category is reached, e.g. 'seed' to 'fertilizer' or 'water' to
+        'location'.
+
+        # Arguments
+
+        * `value` - the value within the `from` category
+        * `from` - the name of the category the value belongs to
+        * `to` - the name of the category to map the value into
+
+        # Examples
+
+        ```
+        let soil = almanac.convert(79, "seed", "soil").unwrap();
+        ```
+        */
+        let steps = self.trace(value, from, to)?;
+
+        Ok(match steps.last() {
+            Some(step) => step.output,
+            None => value
+        })
+    }
+
+    pub fn trace(&self, value: i64, from: &str, to: &str) -> Result<Vec<ConversionStep>, AocError> {
+        /* Map a value from one category to another recording every intermediate stage.
+
+        Each step of the returned trace gives the categories involved, the values before and
+        after conversion and the index of the range within the map which was applied.
+
+        # Arguments
+
+        * `value` - the value within the `from` category
+        * `from` - the name of the category the value belongs to
+        * `to` - the name of the category to map the value into
+
+        # Examples
+
+        ```
+        for step in almanac.trace(79, "seed", "location").unwrap() {
+            println!("{} {} -> {} {}", step.source, step.input, step.destination, step.output);
+        }
+        ```
+        */
+        let mut converted = value;
+        let mut steps = Vec::<ConversionStep>::new();
+
+        for category_map in self.map_chain(from, to)? {
+            let (output, range_index) = category_map.map_value_with_range(converted);
+            tracing::debug!("Mapping {} {} -> {} {}", category_map.source, converted, category_map.destination, output);
+            steps.push(ConversionStep {
+                source: category_map.source.clone(),
+                destination: category_map.destination.clone(),
+                input: converted,
+                output,
+                range_index
+            });
+            converted = output;
+        }
+
+        Ok(steps)
+    }
+
+    pub fn map_chain(&self, from: &str, to: &str) -> Result<Vec<&CategoryMap>, AocError> {
+        /* The maps converting one category into another, in the order they are applied. */
+        let mut category = from;
+        let mut chain = Vec::<&CategoryMap>::new();
+
+        for _ in 0..=self.maps.len() {
+            if category == to {
+                return Ok(chain);
+            }
+            let category_map = match self.maps.get(category) {
+                Some(m) => m,
+                None => return Err(AocError::MissingData(format!("No conversion path from '{}' to '{}'", from, to)))
+            };
+            chain.push(category_map);
+            category = &category_map.destination;
+        }
+
+        Err(AocError::parse(format!("Conversion from '{}' to '{}' contains a cycle", from, to)))
+    }
+
+    pub fn compose(&self, from: &str, to: &str) -> Result<CategoryMap, AocError> {
+        /* Compose the maps between two categories into a single map.
+
+        The result converts values from `from` directly to `to`, giving the same answer
+        as applying each map in turn. Pieces which leave values unchanged are dropped,
+        as for any other map values outside all ranges pass through as they are.
+
+        # Arguments
+
+        * `from` - the source category
+        * `to` - the destination category
+
+        # Examples
+
+        ```
+        let seed_to_location = almanac.compose("seed", "location").unwrap();
+        seed_to_location.save("/path/to/seed_to_location.json").unwrap();
+        ```
+        */
+        let chain = self.map_chain(from, to)?;
+
+        // All map ranges lie within [0, upper], beyond which every map is the identity
+        let upper = chain.iter().flat_map(|m| m.ranges.iter()).map(|r| r.source_end()).max().unwrap_or(0);
+
+        // Pieces of the source domain as (first, last, offset to the current category)
+        let mut pieces: Vec<(i64, i64, i64)> = vec![(0, upper, 0)];
+
+        for category_map in &chain {
+            let mut composed = Vec::<(i64, i64, i64)>::new();
+
+            for &(first, last, offset) in &pieces {
+                let mut unmapped = vec![(first, last)];
+
+                for range_def in &category_map.ranges {
+                    let mut remaining = Vec::<(i64, i64)>::new();
+
+                    for (lower, upper) in unmapped {
+                        // Compare in the current category, then record the split in the source domain
+                        let overlap = (
+                            (lower + offset).max(range_def.source_start),
+                            (upper + offset).min(range_def.source_end())
+                        );
+                        if overlap.0 > overlap.1 {
+                            remaining.push((lower, upper));
+                            continue;
+                        }
+
+                        composed.push((overlap.0 - offset, overlap.1 - offset, offset + range_def.offset()));
+
+                        if lower + offset < overlap.0 {remaining.push((lower, overlap.0 - offset - 1));}
+                        if upper + offset > overlap.1 {remaining.push((overlap.1 - offset + 1, upper));}
+                    }
+                    unmapped = remaining;
+                }
+
+                composed.extend(unmapped.into_iter().map(|(lower, upper)| (lower, upper, offset)));
+            }
+
+            pieces = composed;
+        }
+
+        pieces.sort();
+
+        Ok(CategoryMap {
+            source: from.to_string(),
+            destination: to.to_string(),
+            ranges: pieces
+                .into_iter()
+                .filter(|&(_, _, offset)| offset != 0)
+                .map(|(first, last, offset)| RangeMapping {
+                    destination_start: first + offset,
+                    source_start: first,
+                    length: last - first + 1
+                })
+                .collect()
+        })
+    }
+
+    pub fn trace_seed(&self, seed: i64) -> Result<Vec<ConversionStep>, AocError> {
+        /* Trace a seed through every stage of the almanac to its location.
+
+        # Arguments
+
+        * `seed` - the seed number to trace
+
+        # Examples
+
+        ```
+        let steps = almanac.trace_seed(79).unwrap();
+        ```
+        */
+        self.trace(seed, "seed", "location")
+    }
+
+    pub fn explain(&self, use_ranges: bool) -> Result<String, AocError> {
+        /* Describe step by step how the lowest location is found.
+
+        Without ranges every seed is followed through each category to its location.
+        With ranges each seed range is shown alongside the location ranges it splits into.
+
+        # Examples
+
+        ```
+        println!("{}", almanac.explain(false).unwrap());
+        ```
+        */
+        let mut explanation = String::new();
+        let mut minimum = i64::MAX;
+
+        if use_ranges {
+            for (lower, upper) in self.seed_ranges(true).iter().map(|r| r.bounds()) {
+                let locations = normalize_ranges(&get_propagated_values(&(lower, upper), &self.map_chain("seed", "location")?)?);
+                let described: Vec<String> = locations.iter().map(|(l, u)| format!("{}..={}", l, u)).collect();
+
+                explanation.push_str(&format!(
+                    "Seeds {}..={} split into {} location range(s): {}\n",
+                    lower, upper, locations.len(), described.join(", ")
+                ));
+                if let Some((l, _)) = locations.first() {
+                    minimum = minimum.min(*l);
+                }
+            }
+        } else {
+            for &seed in &self.seeds {
+                let steps = self.trace_seed(seed)?;
+                let mut line = format!("Seed {}", seed);
+
+                for step in &steps {
+                    line.push_str(&format!(", {} {}", step.destination, step.output));
+                }
+                explanation.push_str(&line);
+                explanation.push_str(".\n");
+
+                if let Some(step) = steps.last() {
+                    minimum = minimum.min(step.output);
+                }
+            }
+        }
+
+        if minimum == i64::MAX {
+            return Err(AocError::MissingData("No seeds found within almanac".to_string()));
+        }
+        explanation.push_str(&format!("\nThe lowest location number is {}\n", minimum));
+
+        Ok(explanation)
+    }
+
+    pub fn invert(&self) -> Result<Almanac, AocError> {
+        /* Create an almanac with every map reversed.
+
+        Where a map is one-to-one (see `CategoryMap::is_one_to_one`) swapping the source and
+        destination of every range gives the inverse conversion, allowing queries such as
+        finding the seed which leads to a given location. An almanac with any other map has
+        no inverse, some values being reached from two others.
+
+        # Examples
+
+        ```
+        let seed = almanac.invert().unwrap().convert(46, "location", "seed").unwrap();
+        ```
+        */
+        if let Some(m) = self.maps.values().find(|m| !m.is_one_to_one()) {
+            return Err(AocError::InvalidArgument(format!("The {}-to-{} map is not one-to-one, so cannot be inverted", m.source, m.destination)));
+        }
+
+        let mut maps: Vec<CategoryMap> = self.maps
+            .values()
+            .map(|m| m.inverse())
+            .collect();
+        maps.reverse();
+
+        Ok(Almanac {
+            seeds: self.seeds.clone(),
+            maps: maps.into_iter().map(|m| (m.source.clone(), m)).collect()
+        })
+    }
+}
+
+fn get_target_seeds(file_entry: &str) -> Result<Vec<i64>, AocError> {
+    let (_, _seed_nums) = match file_entry.split_once(":") {
+        Some(s) => s,
+        None => return Err(AocError::parse("Expected split at ':'"))
+    };
+
+    let number_re = match Regex::new(r"\d+") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let mut seeds = Vec::<i64>::new();
+
+    for entry in number_re.find_iter(file_entry) {
+        match entry.as_str().parse::<i64>() {
+            Ok(v) => seeds.push(v),
+            Err(e) => return Err(AocError::parse(format!("Failed to parse seed '{}': {}", entry.as_str(), e)))
+        };
+    }
+
+    Ok(seeds)
+}
+
+fn order_maps(maps: Vec<CategoryMap>) -> Result<IndexMap<String, CategoryMap, FxBuildHasher>, AocError> {
+    /* Sort category maps into the order in which conversions are applied.
+
+    The categories are sorted topologically using an edge for each map from its
+    source to its destination category.
+
+    # Arguments
+
+    * `maps` - the category maps in any order
+
+    # Returns
+
+    The maps keyed by source category in conversion order.
+    */
+    let mut maps_by_source = IndexMap::<String, CategoryMap, FxBuildHasher>::default();
+
+    for category_map in maps {
+        if maps_by_source.contains_key(&category_map.source) {
+            return Err(AocError::parse(format!("Multiple maps found from category '{}'", category_map.source)));
+        }
+        maps_by_source.insert(category_map.source.clone(), category_map);
+    }
+
+    let edges = maps_by_source
+        .values()
+        .map(|m| (m.source.as_str(), m.destination.as_str()));
+
+    let order = match topological_sort(edges) {
+        Ok(o) => o,
+        Err(cycle) => return Err(AocError::parse(format!("Almanac maps contain a cycle between categories: {}", cycle.join(", "))))
+    };
+
+    let ordered: Vec<CategoryMap> = order
+        .iter()
+        .filter_map(|c| maps_by_source.get(*c))
+        .cloned()
+        .collect();
+
+    for pair in ordered.windows(2) {
+        if pair[0].destination != pair[1].source {
+            return Err(AocError::parse(format!(
+                "Missing link in almanac, no map from '{}' and no map to '{}'",
+                pair[0].destination,
+                pair[1].source
+            )));
+        }
+    }
+
+    Ok(ordered.into_iter().map(|m| (m.source.clone(), m)).collect())
+}
+
+fn get_conversions(map_blocks: &[&str]) -> Result<Vec<CategoryMap>, AocError> {
+    let header_regex = match Regex::new(r"(\w+)-to-(\w+)") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let number_regex = match Regex::new(r"\d+") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let mut functions = Vec::<CategoryMap>::new();
+
+    for block in map_blocks {
+        let mut file_lines = block.lines();
+
+        let header = match file_lines.next() {
+            Some(h) => h,
+            None => continue
+        };
+
+        let (start, end) = match header_regex.captures(header) {
+            Some(c) => match (c.get(1), c.get(2)) {
+                (Some(st), Some(en)) => (st.as_str().to_string(), en.as_str().to_string()),
+                _ => return Err(AocError::parse(format!("Expected two categories in header '{}'", header)))
+            },
+            None => return Err(AocError::parse(format!("Expected map header of the form 'source-to-destination map:', got '{}'", header)))
+        };
+
+        let mut range_definitions = Vec::<RangeMapping>::new();
+
+        for line in file_lines {
+            let mut range_components = Vec::<i64>::new();
+            for number in number_regex.find_iter(line) {
+                match number.as_str().parse::<i64>() {
+                    Ok(v) => range_components.push(v),
+                    Err(e) => return Err(AocError::parse(format!("Failed to parse '{}': {}", number.as_str(), e)))
+                };
+            }
+            match range_components[..] {
+                [] => (),
+                [destination_start, source_start, length] => range_definitions.push(
+                    RangeMapping { destination_start, source_start, length }
+                ),
+                _ => return Err(AocError::parse(format!("Expected three values in range definition '{}'", line)))
+            };
+        }
+
+        functions.push(CategoryMap { source: start, destination: end, ranges: range_definitions });
+    }
+    Ok(functions)
+}
+
+fn lowest_location(range: (i64, i64), level: usize, chain: &[&CategoryMap], memo: &mut LocationMemo) -> Option<i64> {
+    /* The lowest value a range reaches through the maps of a chain from the one at `level` on.
+
+    Ranges are split at map boundaries and each piece followed depth first, the
+    lowest value reached from a range at each map being remembered in the memo.
+    */
+    // Values within a range stay in order, so the lowest location is its start
+    if level == chain.len() {
+        return Some(range.0);
+    }
+    if let Some(lowest) = memo.get(&(level, range.0, range.1)) {
+        return *lowest;
+    }
+
+    let mut pieces = Vec::<(i64, i64)>::new();
+    let mut unmapped = vec![range];
+
+    for range_def in &chain[level].ranges {
+        let mut remaining = Vec::<(i64, i64)>::new();
+
+        for split_range in unmapped {
+            if split_range.1 < range_def.source_start || split_range.0 > range_def.source_end() {
+                remaining.push(split_range);
+                continue;
+            }
+
+            let overlap = (split_range.0.max(range_def.source_start), split_range.1.min(range_def.source_end()));
+            pieces.push((overlap.0 + range_def.offset(), overlap.1 + range_def.offset()));
+
+            if split_range.0 < overlap.0 {remaining.push((split_range.0, overlap.0 - 1));}
+            if split_range.1 > overlap.1 {remaining.push((overlap.1 + 1, split_range.1));}
+        }
+        unmapped = remaining;
+    }
+    pieces.extend(unmapped);
+
+    let lowest = pieces.into_iter().filter_map(|p| lowest_location(p, level + 1, chain, memo)).min();
+    memo.insert((level, range.0, range.1), lowest);
+    lowest
+}
+
+fn get_propagated_values(input_range: &(i64, i64), conversions: &[&CategoryMap]) -> Result<Vec<(i64, i64)>, AocError> {
+    tracing::info!("Propagating range {} <= x <= {} ...", input_range.0, input_range.1);
+
+    let mut pre_propagated_ranges: Vec<(i64, i64)> = vec![*input_range];
+
+    for category_map in conversions {
+        tracing::debug!("Running mapping {}->{}", category_map.source, category_map.destination);
+
+        let mut output_ranges = Vec::<(i64, i64)>::new();
+
+        for io_range in &pre_propagated_ranges {
+            // Portions of the range not yet covered by any of the map ranges
+            let mut unmapped = vec![*io_range];
+
+            for range_def in &category_map.ranges {
+                let source_lower_limit = range_def.source_start;
+                let source_upper_limit = range_def.source_end();
+                let mut remaining = Vec::<(i64, i64)>::new();
+
+                for split_range in &unmapped {
+                    // If no overlap at all continue
+                    if split_range.1 < source_lower_limit || split_range.0 > source_upper_limit {
+                        remaining.push(*split_range);
+                        continue;
+                    }
+
+                    let overlap = (split_range.0.max(source_lower_limit), split_range.1.min(source_upper_limit));
+
+                    tracing::debug!(
+                        "Mapping {} <= x <= {} -> {} <= x <= {}",
+                        overlap.0,
+                        overlap.1,
+                        overlap.0 + range_def.offset(),
+                        overlap.1 + range_def.offset()
+                    );
+                    output_ranges.push((overlap.0 + range_def.offset(), overlap.1 + range_def.offset()));
+
+                    if split_range.0 < overlap.0 {remaining.push((split_range.0, overlap.0 - 1));}
+                    if split_range.1 > overlap.1 {remaining.push((overlap.1 + 1, split_range.1));}
+                }
+                unmapped = remaining;
+            }
+
+            // Any values not covered by a range map to themselves
+            output_ranges.extend(unmapped);
+        }
+
+        tracing::debug!("Mapping result: {:?}", output_ranges);
+
+        pre_propagated_ranges = output_ranges;
+    }
+
+    Ok(pre_propagated_ranges)
+}
+
+pub fn normalize_ranges(ranges: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    /* Merge overlapping and adjacent inclusive ranges into a minimal sorted set.
+
+    # Arguments
+
+    * `ranges` - inclusive (lower, upper) ranges as returned by `Almanac::location_ranges`
+
+    # Returns
+
+    The sorted, non-overlapping ranges covering the same values.
+
+    # Examples
+
+    ```
+    assert_eq!(normalize_ranges(&[(5, 8), (1, 3), (4, 4)]), vec![(1, 8)]);
+    ```
+    */
+    let mut sorted_ranges = ranges.to_vec();
+    sorted_ranges.sort();
+
+    let mut merged = Vec::<(i64, i64)>::new();
+
+    for range in sorted_ranges {
+        match merged.last_mut() {
+            Some(last) if range.0 <= last.1 + 1 => {
+                last.1 = last.1.max(range.1);
+            },
+            _ => merged.push(range)
+        };
+    }
+
+    merged
+}
+
+pub fn covered_size(ranges: &[(i64, i64)]) -> i64 {
+    /* Total number of distinct values covered by a set of inclusive ranges.
+
+    # Arguments
+
+    * `ranges` - inclusive (lower, upper) ranges which may overlap
+
+    # Examples
+
+    ```
+    assert_eq!(covered_size(&[(1, 3), (2, 5)]), 5);
+    ```
+    */
+    normalize_ranges(ranges)
+        .iter()
+        .map(|(lower, upper)| upper - lower + 1)
+        .sum()
+}
+
+// Progress of a brute force run, saved periodically so an interrupted run can resume
+#[derive(Clone, Debug, PartialEq)]
+pub struct BruteforceCheckpoint {
+    pub sample_step: i64,
+    pub done: i64,
+    pub total: i64,
+    pub minimum: i64
+}
+
+impl BruteforceCheckpoint {
+    pub fn parse(content: &str) -> Result<BruteforceCheckpoint, AocError> {
+        let mut values = IndexMap::<&str, i64, FxBuildHasher>::default();
+
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = match line.split_once('=') {
+                Some(kv) => kv,
+                None => return Err(AocError::parse(format!("Invalid checkpoint line '{}'", line)))
+            };
+            let value = match value.trim().parse::<i64>() {
+                Ok(v) => v,
+                Err(e) => return Err(AocError::parse(format!("Invalid checkpoint value for '{}': {}", key.trim(), e)))
+            };
+            values.insert(key.trim(), value);
+        }
+
+        let get = |key: &str| match values.get(key) {
+            Some(v) => Ok(*v),
+            None => Err(AocError::MissingData(format!("Checkpoint is missing '{}'", key)))
+        };
+
+        Ok(BruteforceCheckpoint {
+            sample_step: get("sample_step")?,
+            done: get("done")?,
+            total: get("total")?,
+            minimum: get("minimum")?
+        })
+    }
+
+    pub fn progress_event(&self) -> ProgressEvent {
+        ProgressEvent { day: 5, stage: "bruteforce", done: self.done as u64, total: self.total as u64 }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load(file_name: impl AsRef<Path>) -> Result<Option<BruteforceCheckpoint>, AocError> {
+        /* Read a checkpoint file, returning None if it does not exist. */
+        match read_to_string(file_name.as_ref()) {
+            Ok(content) => BruteforceCheckpoint::parse(&content).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AocError::io(file_name.as_ref(), e))
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save(&self, file_name: impl AsRef<Path>) -> Result<(), AocError> {
+        /* Write the checkpoint, replacing the file in one step so an interruption mid-write
+        cannot leave a truncated checkpoint behind. */
+        let content = format!(
+            "sample_step={}\ndone={}\ntotal={}\nminimum={}\n",
+            self.sample_step, self.done, self.total, self.minimum
+        );
+        let file_name = file_name.as_ref();
+        // Append rather than replace the extension, 'run.ckpt' and 'run.dat' must not share 'run.tmp'
+        let mut temp_name = file_name.as_os_str().to_os_string();
+        temp_name.push(".tmp");
+        let temp_file = PathBuf::from(temp_name);
+
+        match std::fs::write(&temp_file, content).and_then(|_| std::fs::rename(&temp_file, file_name)) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AocError::io(file_name, e))
+        }
+    }
+}
+
+fn bruteforce_from<F>(almanac: &Almanac, sample_step: i64, resume: Option<BruteforceCheckpoint>, mut report: F) -> Result<i64, AocError>
+where F: FnMut(&BruteforceCheckpoint) -> Result<(), AocError> {
+    let _span = tracing::info_span!("solve", day = 5, part = 2, method = "bruteforce", sample_step).entered();
+
+    if sample_step < 1 {
+        return Err(AocError::InvalidArgument(format!("Sample step must be at least one, got {}", sample_step)));
+    }
+
+    let chain = almanac.map_chain("seed", "location")?;
+    let seed_ranges = almanac.seed_ranges(true);
+
+    if seed_ranges.is_empty() {
+        return Err(AocError::MissingData("No seed ranges found within almanac".to_string()));
+    }
+
+    let n_samples = |lower: i64, upper: i64| (upper - lower) / sample_step + 1 + if (upper - lower) % sample_step > 0 {1} else {0};
+    let total: i64 = seed_ranges.iter().map(|r| n_samples(r.first.0, r.last.0)).sum();
+    let report_interval = (total / 100).max(1);
+
+    let mut state = match resume {
+        Some(checkpoint) => {
+            if checkpoint.sample_step != sample_step || checkpoint.total != total || checkpoint.done > total {
+                return Err(AocError::InvalidArgument(format!(
+                    "Checkpoint for {} of {} seeds with step {} does not match this run of {} seeds with step {}",
+                    checkpoint.done, checkpoint.total, checkpoint.sample_step, total, sample_step
+                )));
+            }
+            checkpoint
+        },
+        None => BruteforceCheckpoint { sample_step, done: 0, total, minimum: i64::MAX }
+    };
+
+    // Number of samples in ranges before the current one
+    let mut offset: i64 = 0;
+
+    for (lower, upper) in seed_ranges.iter().map(|r| r.bounds()) {
+        let n = n_samples(lower, upper);
+
+        for k in (state.done - offset).max(0)..n {
+            let seed = (lower + k * sample_step).min(upper);
+            let location = chain.iter().fold(seed, |value, m| m.map_value(value));
+            state.minimum = state.minimum.min(location);
+
+            state.done += 1;
+            if state.done % report_interval == 0 {
+                report(&state)?;
+            }
+        }
+
+        offset += n;
+    }
+
+    report(&state)?;
+
+    Ok(state.minimum)
+}
+
+pub fn solve_part2_bruteforce<P: ProgressSink>(almanac: &Almanac, sample_step: i64, mut progress: P) -> Result<i64, AocError> {
+    /* Find the minimum location for the seed ranges by converting every seed individually.
+
+    This is far slower than propagating whole ranges but provides an independent check
+    of the range based solver. A sample step greater than one only converts every Nth
+    seed of each range (plus the final seed) giving an upper bound on the minimum.
+
+    # Arguments
+
+    * `almanac` - the parsed almanac
+    * `sample_step` - convert every `sample_step`-th seed, 1 checks every seed
+    * `progress` - receives the number of seeds converted so far and the total to convert
+
+    # Examples
+
+    ```
+    let almanac = Almanac::from_file("/path/to/file").unwrap();
+    let minimum = solve_part2_bruteforce(&almanac, 1, LogProgress).unwrap();
+    ```
+    */
+    bruteforce_from(almanac, sample_step, None, |state| {
+        progress.publish(&state.progress_event());
+        Ok(())
+    })
+}
+
+#[cfg(feature = "std")]
+pub fn solve_part2_bruteforce_resumable<P: ProgressSink>(almanac: &Almanac, sample_step: i64, checkpoint_file: impl AsRef<Path>, mut progress: P) -> Result<i64, AocError> {
+    /* Brute force solver which saves its progress to a checkpoint file as it runs.
+
+    If the checkpoint file exists the run continues from the saved position rather
+    than starting again. The checkpoint is removed once the run completes.
+
+    # Arguments
+
+    * `almanac` - the parsed almanac
+    * `sample_step` - convert every `sample_step`-th seed, must match any existing checkpoint
+    * `checkpoint_file` - path of the checkpoint to resume from and save to
+    * `progress` - receives the number of seeds converted so far and the total to convert
+    */
+    let checkpoint_file = checkpoint_file.as_ref();
+    let resume = BruteforceCheckpoint::load(checkpoint_file)?;
+
+    if let Some(checkpoint) = &resume {
+        tracing::info!(done = checkpoint.done, total = checkpoint.total, "Resuming from checkpoint");
+    }
+
+    let minimum = bruteforce_from(almanac, sample_step, resume, |state| {
+        progress.publish(&state.progress_event());
+        state.save(checkpoint_file)
+    })?;
+
+    match std::fs::remove_file(checkpoint_file) {
+        Ok(_) => Ok(minimum),
+        Err(e) => Err(AocError::io(checkpoint_file, e))
+    }
+}
+
+pub fn min_location(ranges: &[LocationRange]) -> Option<Location> {
+    /* The lowest location within any of the given ranges. */
+    ranges.iter().map(|r| r.first).min()
+}
+
+#[cfg(feature = "std")]
+pub fn parse_almanac_conversions(file_name: impl AsRef<Path>, use_ranges: bool) -> Result<Vec<LocationRange>, AocError> {
+    /* Read an almanac file and find the ranges of locations reached from its seeds.
+
+    # Arguments
+
+    * `file_name` - path of the almanac data file
+    * `use_ranges` - treat the seeds as pairs of range start and length
+
+    # Returns
+
+    The location ranges reached, these may overlap or be fragmented.
+
+    # Examples
+
+    ```
+    let locations = parse_almanac_conversions("/path/to/file", true).unwrap();
+    let lowest = min_location(&locations).unwrap();
+    ```
+    */
+    let almanac = Almanac::from_file(file_name)?;
+
+    Ok(almanac
+        .location_ranges(use_ranges)?
+        .into_iter()
+        .map(|(first, last)| LocationRange::new(first, last))
+        .collect())
+}
+
+// The day 5 puzzle, by default part 2 reading the seeds as ranges
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day5 {
+    // Whether the seeds are read as ranges in both parts or neither, rather than only in part 2
+    pub seed_ranges: Option<bool>,
+    // Pieces of seed ranges whose lowest location is remembered, DEFAULT_MEMO_CAPACITY if not given
+    pub memo_capacity: Option<usize>
+}
+
+impl Solver for Day5 {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        self.solve_with_progress(1, input, &mut NoProgress)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        self.solve_with_progress(2, input, &mut NoProgress)
+    }
+
+    fn solve_with_progress(&self, part: u32, input: &str, progress: &mut dyn ProgressSink) -> Result<Answer, AocError> {
+        Ok(self.solve_almanac(part, input, progress)?.into_answer())
+    }
+
+    fn solve_with_diagnostics(&self, part: u32, input: &str) -> Result<Outcome<Answer>, AocError> {
+        self.solve_almanac(part, input, &mut NoProgress)
+    }
+}
+
+impl Day5 {
+    fn solve_almanac(&self, part: u32, input: &str, progress: &mut dyn ProgressSink) -> Result<Outcome<Answer>, AocError> {
+        /* Find the lowest location along with any warnings about the almanac, e.g. overlapping seed ranges. */
+        // Part 1 has a range of one for each seed, part 2 pairs the seeds into ranges
+        let use_ranges = match (part, self.seed_ranges) {
+            (1, Some(r)) | (2, Some(r)) => r,
+            (1, None) => false,
+            (2, None) => true,
+            _ => return Err(AocError::InvalidArgument(format!("Day 5 has no part {}", part)))
+        };
+        let _span = tracing::info_span!("solve", day = 5, part).entered();
+
+        let almanac = Almanac::parse(input)?;
+        let mut cache = LruCache::new(self.memo_capacity.unwrap_or(DEFAULT_MEMO_CAPACITY));
+        match almanac.min_location_with_cache(&almanac.seed_ranges(use_ranges), |e: &ProgressEvent| progress.publish(e), &mut cache)? {
+            Some(l) => Ok(Outcome::with_diagnostics(Answer::Int(l.0), almanac.diagnostics(use_ranges))),
+            None => Err(AocError::MissingData("The almanac lists no seeds".to_string()))
+        }
+    }
+}
+
+// Day 5 solved by converting every seed on its own, as an independent check of `Day5`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day5Bruteforce;
+
+impl Solver for Day5Bruteforce {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        let almanac = Almanac::parse(input)?;
+        let mut lowest: Option<i64> = None;
+
+        for &seed in almanac.seeds() {
+            let location = almanac.convert(seed, "seed", "location")?;
+            lowest = Some(lowest.map_or(location, |l| l.min(location)));
+        }
+
+        match lowest {
+            Some(l) => Ok(Answer::Int(l)),
+            None => Err(AocError::MissingData("The almanac lists no seeds".to_string()))
+        }
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(Answer::Int(solve_part2_bruteforce(&Almanac::parse(input)?, 1, NoProgress)?))
+    }
+}
+
+inventory::submit! {
+    Registration { name: "default", build: || Box::new(Day5::default()) }
+}
+
+inventory::submit! {
+    Registration { name: "bruteforce", build: || Box::new(Day5Bruteforce) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::progress::NoProgress;
+    use std::path::PathBuf;
+
+    fn single_map(destination_start: i64, source_start: i64, length: i64) -> CategoryMap {
+        CategoryMap {
+            source: "test".to_string(),
+            destination: "result".to_string(),
+            ranges: vec![RangeMapping { destination_start, source_start, length }]
+        }
+    }
+
+    fn test_almanac() -> Almanac {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_5.dat");
+        Almanac::from_file(&test_file).unwrap()
+    }
+
+    #[test]
+    fn test_no_match_returns_same_value() {
+        let input= (12, 12);
+        let conversions = single_map(23, 45, 2);
+        let propagated_value = get_propagated_values(&input, &[&conversions])
+            .unwrap()
+            .iter()
+            .map(|x| x.0)
+            .min()
+            .unwrap();
+        assert_eq!(propagated_value, input.0);
+    }
+
+    #[test]
+    fn test_single_step() {
+        let input = (12, 14);
+        let expect = 67;
+        let conversions = single_map(65, 10, 6);
+        let propagated_value = get_propagated_values(&input, &[&conversions]);
+        tracing::debug!("{:?}", propagated_value);
+        let temp = propagated_value.unwrap()
+            .iter()
+            .map(|x| x.0)
+            .min()
+            .unwrap();
+        assert_eq!(temp, expect);
+    }
+
+    #[test]
+    fn test_minimum_location() {
+            
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_5.dat");
+
+        let final_value = parse_almanac_conversions(&test_file, false).unwrap();
+
+        let minimum_val = min_location(&final_value).unwrap();
+
+        assert_eq!(minimum_val, Location(35));
+    }
+
+    #[test]
+    fn test_minimum_location_ranges() {
+            
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_5.dat");
+
+        let final_value = parse_almanac_conversions(&test_file, true).unwrap();
+
+        let minimum_val = min_location(&final_value).unwrap();
+
+        assert_eq!(minimum_val, Location(46));
+        assert!(final_value.iter().any(|r| r.contains(Location(46))));
+        assert_eq!(final_value.iter().map(|r| r.size()).sum::<i64>(), 14 + 13);
+    }
+
+    #[test]
+    fn test_convert_between_categories() {
+        let almanac = test_almanac();
+
+        assert_eq!(almanac.convert(79, "seed", "soil").unwrap(), 81);
+        assert_eq!(almanac.convert(79, "seed", "fertilizer").unwrap(), 81);
+        assert_eq!(almanac.convert(81, "fertilizer", "water").unwrap(), 81);
+        assert_eq!(almanac.convert(79, "seed", "location").unwrap(), 82);
+        assert_eq!(almanac.convert(14, "seed", "seed").unwrap(), 14);
+    }
+
+    #[test]
+    fn test_convert_no_path() {
+        let almanac = test_almanac();
+
+        assert!(almanac.convert(82, "location", "seed").is_err());
+        assert!(almanac.convert(79, "seed", "nonsense").is_err());
+    }
+
+    #[test]
+    fn test_invert_location_to_seed() {
+        let almanac = test_almanac();
+        assert!(almanac.maps().all(|m| m.is_one_to_one()));
+        let inverted = almanac.invert().unwrap();
+
+        assert_eq!(inverted.convert(35, "location", "seed").unwrap(), 13);
+        assert_eq!(inverted.convert(46, "location", "seed").unwrap(), 82);
+        assert_eq!(inverted.convert(81, "soil", "seed").unwrap(), 79);
+
+        for seed in almanac.seeds() {
+            let location = almanac.convert(*seed, "seed", "location").unwrap();
+            assert_eq!(inverted.convert(location, "location", "seed").unwrap(), *seed);
+        }
+    }
+
+    #[test]
+    fn test_trace_seed() {
+        let almanac = test_almanac();
+        let steps = almanac.trace_seed(79).unwrap();
+
+        let values: Vec<i64> = steps.iter().map(|s| s.output).collect();
+        assert_eq!(values, vec![81, 81, 81, 74, 78, 78, 82]);
+
+        let categories: Vec<&str> = steps.iter().map(|s| s.destination.as_str()).collect();
+        assert_eq!(categories, vec!["soil", "fertilizer", "water", "light", "temperature", "humidity", "location"]);
+
+        assert_eq!(steps[0].input, 79);
+        assert_eq!(steps[0].range_index, Some(1));
+        assert_eq!(steps[1].range_index, None);
+    }
+
+    #[test]
+    fn test_category_maps() {
+        let almanac = test_almanac();
+
+        assert_eq!(almanac.maps().count(), 7);
+
+        let seed_to_soil = almanac.category_map("seed").unwrap();
+        assert_eq!(seed_to_soil.destination, "soil");
+        assert_eq!(seed_to_soil.ranges[0], RangeMapping { destination_start: 50, source_start: 98, length: 2 });
+        assert_eq!(seed_to_soil.map_value(79), 81);
+
+        let recombined = Almanac::from_maps(
+            almanac.seeds().clone(),
+            almanac.maps().take(2).cloned().collect()
+        ).unwrap();
+        assert_eq!(recombined.convert(79, "seed", "fertilizer").unwrap(), 81);
+        assert!(recombined.convert(79, "seed", "water").is_err());
+    }
+
+    #[test]
+    fn test_normalize_ranges() {
+        assert_eq!(normalize_ranges(&[(5, 8), (1, 3), (4, 4)]), vec![(1, 8)]);
+        assert_eq!(normalize_ranges(&[(10, 20), (1, 3), (12, 15), (22, 22)]), vec![(1, 3), (10, 20), (22, 22)]);
+        assert!(normalize_ranges(&[]).is_empty());
+        assert_eq!(covered_size(&[(1, 3), (2, 5), (10, 10)]), 6);
+    }
+
+    #[test]
+    fn test_normalized_location_ranges() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_5.dat");
+
+        let final_value: Vec<(i64, i64)> = parse_almanac_conversions(&test_file, true)
+            .unwrap()
+            .iter()
+            .map(|r| r.bounds())
+            .collect();
+        let normalized = normalize_ranges(&final_value);
+
+        assert_eq!(normalized[0].0, 46);
+        assert!(normalized.windows(2).all(|w| w[0].1 + 1 < w[1].0));
+
+        // The maps of the example are one-to-one so every seed reaches a distinct location
+        assert_eq!(covered_size(&final_value), 14 + 13);
+    }
+
+    #[test]
+    fn test_category_ranges() {
+        let almanac = test_almanac();
+        let categories = almanac.category_ranges(true).unwrap();
+
+        assert_eq!(categories.len(), 8);
+        assert_eq!(categories[0], ("seed".to_string(), vec![(55, 67), (79, 92)]));
+        assert_eq!(categories[1].0, "soil");
+        assert_eq!(categories.last().unwrap().0, "location");
+        assert_eq!(categories.last().unwrap().1[0].0, 46);
+        assert!(categories.iter().all(|(_, ranges)| covered_size(ranges) == 27));
+
+        assert_eq!(almanac.category_ranges(false).unwrap().last().unwrap().1[0], (35, 35));
+    }
+
+    #[test]
+    fn test_contains_seed() {
+        let almanac = test_almanac();
+
+        assert!(almanac.contains_seed(79, false));
+        assert!(!almanac.contains_seed(82, false));
+        assert!(almanac.contains_seed(82, true));
+        assert!(almanac.contains_seed(92, true));
+        assert!(!almanac.contains_seed(93, true));
+    }
+
+    #[test]
+    fn test_location_coverage() {
+        let almanac = test_almanac();
+
+        assert_eq!(almanac.location_coverage(46, 46, true).unwrap(), 1.0);
+        assert_eq!(almanac.location_coverage(0, 45, true).unwrap(), 0.0);
+        assert_eq!(almanac.location_coverage(0, 99, false).unwrap(), 0.04);
+        assert!(almanac.location_coverage(10, 5, false).is_err());
+    }
+
+    #[test]
+    fn test_bruteforce_matches_ranges() {
+        let almanac = test_almanac();
+        let mut last_progress = (0, 0);
+
+        let minimum = solve_part2_bruteforce(&almanac, 1, |e: &ProgressEvent| last_progress = (e.done, e.total)).unwrap();
+
+        assert_eq!(minimum, 46);
+        assert_eq!(last_progress, (27, 27));
+        assert_eq!(minimum, normalize_ranges(&almanac.location_ranges(true).unwrap())[0].0);
+    }
+
+    #[test]
+    fn test_bruteforce_sampling() {
+        let almanac = test_almanac();
+        let mut last_progress = (0, 0);
+
+        let minimum = solve_part2_bruteforce(&almanac, 5, |e: &ProgressEvent| last_progress = (e.done, e.total)).unwrap();
+
+        assert!(minimum >= 46);
+        assert_eq!(last_progress, (8, 8));
+        assert!(solve_part2_bruteforce(&almanac, 0, NoProgress).is_err());
+    }
+
+    #[test]
+    fn test_bruteforce_resume() {
+        let almanac = test_almanac();
+        let checkpoint_file = std::env::temp_dir().join(format!("aoc23_day_5_checkpoint_{}.txt", std::process::id()));
+
+        // Interrupted just before reaching seed 82, which gives the minimum location
+        let interrupted = BruteforceCheckpoint { sample_step: 1, done: 3, total: 27, minimum: 1000 };
+        interrupted.save(&checkpoint_file).unwrap();
+        assert_eq!(BruteforceCheckpoint::load(&checkpoint_file).unwrap(), Some(interrupted));
+        assert!(!checkpoint_file.with_extension("txt.tmp").exists());
+
+        let mut first_progress = None;
+        let minimum = solve_part2_bruteforce_resumable(&almanac, 1, &checkpoint_file, |e: &ProgressEvent| {
+            first_progress.get_or_insert(e.done);
+        }).unwrap();
+
+        assert_eq!(minimum, 46);
+        assert_eq!(first_progress, Some(4));
+        assert_eq!(BruteforceCheckpoint::load(&checkpoint_file).unwrap(), None);
+
+        // A checkpoint from a different run is rejected rather than silently reused
+        BruteforceCheckpoint { sample_step: 5, done: 3, total: 8, minimum: 50 }.save(&checkpoint_file).unwrap();
+        assert!(solve_part2_bruteforce_resumable(&almanac, 1, &checkpoint_file, NoProgress).is_err());
+        std::fs::remove_file(&checkpoint_file).unwrap();
+
+        assert!(BruteforceCheckpoint::parse("done=1\ntotal=2").is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        let almanac = test_almanac();
+
+        let explanation = almanac.explain(false).unwrap();
+        assert!(explanation.contains(
+            "Seed 79, soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78, location 82."
+        ));
+        assert!(explanation.ends_with("The lowest location number is 35\n"));
+
+        let explanation = almanac.explain(true).unwrap();
+        assert!(explanation.contains("Seeds 79..=92 split into"));
+        assert!(explanation.ends_with("The lowest location number is 46\n"));
+    }
+
+    #[test]
+    fn test_almanac_diagnostics() {
+        assert!(test_almanac().location_ranges_with_diagnostics(true).unwrap().is_clean());
+
+        let almanac_data = "seeds: 1 5 3 4 9\n\nseed-to-location map:\n0 1 2\n10 2 3\n";
+        let almanac = Almanac::parse(almanac_data).unwrap();
+        let outcome = almanac.location_ranges_with_diagnostics(true).unwrap();
+
+        assert_eq!(outcome.diagnostics.len(), 3);
+        assert!(outcome.diagnostics[0].message.contains("final seed 9"));
+        assert!(outcome.diagnostics[1].message.contains("1..=5 and 3..=6 overlap"));
+        assert!(outcome.diagnostics[2].message.contains("seed-to-location"));
+        assert_eq!(almanac.diagnostics(false).len(), 1);
+    }
+
+    #[test]
+    fn test_reordered_almanac() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_5_reordered.dat");
+        let almanac = Almanac::from_file(&test_file).unwrap();
+
+        let categories: Vec<&str> = almanac.maps().map(|m| m.source.as_str()).collect();
+        assert_eq!(categories, vec!["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity"]);
+
+        let minimum = normalize_ranges(&almanac.location_ranges(false).unwrap())[0].0;
+        assert_eq!(minimum, 35);
+        let minimum = normalize_ranges(&almanac.location_ranges(true).unwrap())[0].0;
+        assert_eq!(minimum, 46);
+    }
+
+    #[test]
+    fn test_extended_almanac() {
+        let almanac_data = "seeds: 1 5\n\nlocation-to-postcode map:\n10 0 3\n\nseed-to-location map:\n0 1 2\n";
+        let almanac = Almanac::parse(almanac_data).unwrap();
+
+        assert_eq!(almanac.convert(1, "seed", "postcode").unwrap(), 10);
+        assert_eq!(normalize_ranges(&almanac.location_ranges(false).unwrap()), vec![(0, 0), (5, 5)]);
+
+        // 10..=12 are reached from both 0..=2 and themselves
+        assert!(!almanac.category_map("location").unwrap().is_one_to_one());
+        assert!(almanac.invert().is_err());
+    }
+
+    #[test]
+    fn test_almanac_from_reader() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_5.dat");
+        let almanac_file = std::fs::File::open(&test_file).unwrap();
+
+        let almanac = Almanac::from_reader(std::io::BufReader::new(almanac_file)).unwrap();
+        assert_eq!(almanac.seeds(), test_almanac().seeds());
+        assert_eq!(almanac.compose("seed", "location").unwrap(), test_almanac().compose("seed", "location").unwrap());
+        assert!(Almanac::from_reader(&b"seeds: 1\n\n\xff"[..]).err().unwrap().to_string().starts_with("Failed to read input"));
+    }
+
+    #[test]
+    fn test_almanac_from_input() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_5.dat");
+
+        let almanac = Almanac::from_input(&InputSource::read(&test_file).unwrap()).unwrap();
+        assert_eq!(almanac.seeds(), test_almanac().seeds());
+        assert_eq!(almanac.compose("seed", "location").unwrap(), test_almanac().compose("seed", "location").unwrap());
+    }
+
+    #[test]
+    fn test_seed_ranges_option() {
+        let input = std::fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/test/day_5.dat")).unwrap();
+
+        assert_eq!(Day5::default().solve(1, &input).unwrap(), 35);
+        assert_eq!(Day5 { seed_ranges: Some(true), ..Day5::default() }.solve(1, &input).unwrap(), 46);
+        assert_eq!(Day5 { seed_ranges: Some(false), ..Day5::default() }.solve(2, &input).unwrap(), 35);
+        assert!(Day5 { seed_ranges: Some(true), ..Day5::default() }.solve(3, &input).is_err());
+    }
+
+    #[test]
+    fn test_invalid_category_chains() {
+        let cycle = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-water map:\n1 2 3\n\nwater-to-soil map:\n1 2 3\n";
+        let error = Almanac::parse(cycle).err().unwrap();
+        assert!(error.to_string().contains("cycle"), "{}", error);
+
+        let missing_link = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nwater-to-location map:\n1 2 3\n";
+        let error = Almanac::parse(missing_link).err().unwrap();
+        assert!(error.to_string().contains("Missing link"), "{}", error);
+
+        let duplicate = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nseed-to-water map:\n1 2 3\n";
+        assert!(Almanac::parse(duplicate).is_err());
+    }
+
+    #[test]
+    fn test_unmapped_gaps() {
+        let almanac = test_almanac();
+        let gaps = almanac.unmapped_gaps();
+
+        assert_eq!(gaps.len(), 7);
+        assert_eq!(gaps["seed"], vec![(0, 49)]);
+        assert!(gaps["soil"].is_empty());
+        assert_eq!(gaps["water"], vec![(0, 17)]);
+        assert_eq!(gaps["humidity"], vec![(0, 55)]);
+
+        let sparse = CategoryMap {
+            source: "a".to_string(),
+            destination: "b".to_string(),
+            ranges: vec![
+                RangeMapping { destination_start: 0, source_start: 20, length: 5 },
+                RangeMapping { destination_start: 5, source_start: 3, length: 4 }
+            ]
+        };
+        assert_eq!(sparse.unmapped_gaps(), vec![(0, 2), (7, 19)]);
+    }
+
+    #[test]
+    fn test_lazy_min_location() {
+        let almanac = test_almanac();
+
+        assert_eq!(almanac.min_location(&almanac.seed_ranges(false)).unwrap(), Some(Location(35)));
+        assert_eq!(almanac.min_location(&almanac.seed_ranges(true)).unwrap(), Some(Location(46)));
+        assert_eq!(almanac.min_location(&[SeedRange::new(79, 79)]).unwrap(), Some(Location(82)));
+        assert_eq!(almanac.min_location(&[]).unwrap(), None);
+
+        // A repeated seed is found in the memo, which never holds more than its capacity
+        let mut cache = LocationMemo::new(64);
+        assert_eq!(almanac.min_location_with_cache(&[SeedRange::new(79, 79)], NoProgress, &mut cache).unwrap(), Some(Location(82)));
+        assert_eq!(cache.peek(&(0, 79, 79)), Some(&Some(82)));
+        cache.insert((0, 79, 79), Some(7));
+        assert_eq!(almanac.min_location_with_cache(&[SeedRange::new(79, 79)], NoProgress, &mut cache).unwrap(), Some(Location(7)));
+
+        // Overlapping seed ranges share the pieces they are split into by the seed to soil map
+        let mut cache = LocationMemo::new(64);
+        almanac.min_location_with_cache(&[SeedRange::new(40, 97)], NoProgress, &mut cache).unwrap();
+        assert_eq!(cache.peek(&(1, 52, 99)), Some(&Some(0)));
+        cache.insert((1, 52, 99), Some(3));
+        assert_eq!(almanac.min_location_with_cache(&[SeedRange::new(45, 100)], NoProgress, &mut cache).unwrap(), Some(Location(3)));
+
+        let mut tiny = LocationMemo::new(2);
+        assert_eq!(almanac.min_location_with_cache(&almanac.seed_ranges(true), NoProgress, &mut tiny).unwrap(), Some(Location(46)));
+        assert_eq!(tiny.len(), 2);
+
+        let input = std::fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/test/day_5.dat")).unwrap();
+        assert_eq!(Day5Bruteforce.part1(&input).unwrap(), 35);
+        assert_eq!(Day5Bruteforce.part2(&input).unwrap(), 46);
+
+        let tiny = Day5 { memo_capacity: Some(0), ..Day5::default() };
+        assert_eq!(tiny.part2(&input).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_compose_and_reload() {
+        let almanac = test_almanac();
+        let composed = almanac.compose("seed", "location").unwrap();
+
+        for seed in 0..120 {
+            assert_eq!(composed.map_value(seed), almanac.convert(seed, "seed", "location").unwrap());
+        }
+
+        let map_file = std::env::temp_dir().join(format!("aoc23_day_5_composed_{}.json", std::process::id()));
+        composed.save(&map_file).unwrap();
+        let saved: serde_json::Value = serde_json::from_str(&read_to_string(&map_file).unwrap()).unwrap();
+        let reloaded = CategoryMap::load(&map_file).unwrap();
+        std::fs::remove_file(&map_file).unwrap();
+
+        assert_eq!(saved["source"], "seed");
+        assert_eq!(saved["ranges"][0]["length"], composed.ranges[0].length);
+        assert_eq!(reloaded, composed);
+        assert_eq!(almanac.compose("soil", "water").unwrap().map_value(53), almanac.convert(53, "soil", "water").unwrap());
+    }
+}
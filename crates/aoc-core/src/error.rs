@@ -0,0 +1,143 @@
+/*                        ERRORS
+
+The error type returned by the day modules and solvers. Each variant carries
+enough context to be reported on its own, and callers can match on the kind,
+e.g. to tell a missing input file apart from a malformed one.
+
+*/
+
+use alloc::string::{String, ToString};
+use core::error::Error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+#[cfg(feature = "std")]
+use crate::util::io::ReadError;
+use crate::util::scan::BuildError;
+
+#[derive(Debug)]
+pub enum AocError {
+    // A file could not be opened, read or written, `line` is set for read errors part way through
+    // and `path` is empty when the input did not come from a file
+    #[cfg(feature = "std")]
+    Io { path: PathBuf, line: Option<usize>, source: io::Error },
+    // The input was read but is not in the expected form
+    Parse { line: Option<usize>, reason: String },
+    Regex(regex::Error),
+    // The patterns given to a `Scanner` could not be compiled
+    Scanner(BuildError),
+    // Something the input was expected to contain is absent, e.g. a map between two categories
+    MissingData(String),
+    // An argument or option is outside the values accepted
+    InvalidArgument(String),
+    // A resource could not be downloaded from adventofcode.com
+    Download { url: String, reason: String },
+    // A solver plugin could not be loaded, see src/plugin.rs of aoc23
+    #[cfg(feature = "std")]
+    Plugin { path: PathBuf, reason: String }
+}
+
+impl AocError {
+    pub fn parse(reason: impl Into<String>) -> AocError {
+        AocError::Parse { line: None, reason: reason.into() }
+    }
+
+    pub fn parse_at(line: usize, reason: impl Into<String>) -> AocError {
+        AocError::Parse { line: Some(line), reason: reason.into() }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> AocError {
+        AocError::Io { path: path.into(), line: None, source }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            AocError::Io { path, line: Some(n), source } if path.as_os_str().is_empty() => write!(f, "Bad input line {}: {}", n, source),
+            #[cfg(feature = "std")]
+            AocError::Io { path, line: None, source } if path.as_os_str().is_empty() => write!(f, "Failed to read input: {}", source),
+            #[cfg(feature = "std")]
+            AocError::Io { path, line: Some(n), source } => write!(f, "Bad file line {} of '{}': {}", n, path.display(), source),
+            #[cfg(feature = "std")]
+            AocError::Io { path, line: None, source } => write!(f, "Failed to access file '{}': {}", path.display(), source),
+            AocError::Parse { line: Some(n), reason } => write!(f, "Line {}: {}", n, reason),
+            AocError::Parse { line: None, reason } => write!(f, "{}", reason),
+            AocError::Regex(e) => write!(f, "Invalid regular expression: {}", e),
+            AocError::Scanner(e) => write!(f, "Failed to build scanner: {}", e),
+            AocError::MissingData(what) => write!(f, "{}", what),
+            AocError::InvalidArgument(what) => write!(f, "{}", what),
+            AocError::Download { url, reason } => write!(f, "Failed to download '{}': {}", url, reason),
+            #[cfg(feature = "std")]
+            AocError::Plugin { path, reason } => write!(f, "Failed to load the plugin '{}': {}", path.display(), reason)
+        }
+    }
+}
+
+// regex and aho-corasick only implement Error with std, so without it no error has a source
+impl Error for AocError {
+    #[cfg(feature = "std")]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            AocError::Regex(e) => Some(e),
+            AocError::Scanner(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ReadError> for AocError {
+    fn from(error: ReadError) -> AocError {
+        AocError::Io { path: error.path, line: error.line, source: error.source }
+    }
+}
+
+impl From<regex::Error> for AocError {
+    fn from(error: regex::Error) -> AocError {
+        AocError::Regex(error)
+    }
+}
+
+impl From<BuildError> for AocError {
+    fn from(error: BuildError) -> AocError {
+        AocError::Scanner(error)
+    }
+}
+
+impl From<AocError> for String {
+    fn from(error: AocError) -> String {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_error_kinds() {
+        let missing: AocError = match crate::util::io::lines("/no/such/file.dat") {
+            Ok(_) => panic!("Expected the file to be missing"),
+            Err(e) => e.into()
+        };
+
+        match &missing {
+            AocError::Io { line: None, source, .. } => assert_eq!(source.kind(), io::ErrorKind::NotFound),
+            e => panic!("Unexpected error {:?}", e)
+        };
+        assert!(missing.source().is_some());
+
+        assert_eq!(AocError::parse_at(3, "No card ID found").to_string(), "Line 3: No card ID found");
+        let refused = AocError::Download { url: "https://adventofcode.com/2023/day/5/input".to_string(), reason: "not available yet".to_string() };
+        assert_eq!(refused.to_string(), "Failed to download 'https://adventofcode.com/2023/day/5/input': not available yet");
+        let unclosed = String::from("(");
+        assert!(matches!(regex::Regex::new(&unclosed).map_err(AocError::from), Err(AocError::Regex(_))));
+    }
+}
@@ -0,0 +1,32 @@
+/*                        SOLVER CORE
+
+The day solvers and the types they share: answers, errors, diagnostics and
+progress events. The aoc23 crate re-exports each of these modules, e.g. as
+`aoc23::day_5`, and adds the runner, fetching and command line around them.
+
+Every solver takes its input as a &str and needs only core and alloc, so they
+can run without the standard library, e.g. on embedded or WASI targets given
+an allocator. The std feature, on by default, adds the entry points reading
+from files and readers, and the files kept by day 5 such as brute force
+checkpoints. The tests need std.
+
+*/
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod answer;
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod error;
+pub mod outcome;
+pub mod progress;
+pub mod solver;
+pub use aoc_utils as util;
+pub use answer::Answer;
+pub use error::AocError;
+pub use solver::{solver, solvers, Solver};
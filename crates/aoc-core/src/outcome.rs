@@ -0,0 +1,86 @@
+/*                        OUTCOMES
+
+An answer accompanied by diagnostics, non-fatal warnings about the input which
+do not stop a solution being found but may explain a surprising answer, such
+as lines which were skipped or seed ranges which overlap.
+
+*/
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    // The one-based input line the warning relates to, if any
+    pub line: Option<usize>,
+    pub message: String
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(n) => write!(f, "line {}: {}", n, self.message),
+            None => write!(f, "{}", self.message)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome<T> {
+    pub answer: T,
+    pub diagnostics: Vec<Diagnostic>
+}
+
+impl<T> Outcome<T> {
+    pub fn new(answer: T) -> Outcome<T> {
+        Outcome { answer, diagnostics: Vec::new() }
+    }
+
+    pub fn with_diagnostics(answer: T, diagnostics: Vec<Diagnostic>) -> Outcome<T> {
+        Outcome { answer, diagnostics }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Outcome<U> {
+        Outcome { answer: f(self.answer), diagnostics: self.diagnostics }
+    }
+
+    pub fn log(&self) {
+        /* Write every diagnostic to the log as a warning. */
+        for diagnostic in &self.diagnostics {
+            tracing::warn!("{}", diagnostic);
+        }
+    }
+
+    pub fn into_answer(self) -> T {
+        /* Log any diagnostics then discard them, keeping only the answer. */
+        self.log();
+        self.answer
+    }
+}
+
+pub fn warning(line: Option<usize>, message: String) -> Diagnostic {
+    Diagnostic { line, message }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_outcome() {
+        let outcome = Outcome::with_diagnostics(21, vec![warning(Some(3), "skipped".to_string())]);
+
+        assert!(!outcome.is_clean());
+        assert_eq!(outcome.diagnostics[0].to_string(), "line 3: skipped");
+        assert_eq!(warning(None, "odd seeds".to_string()).to_string(), "odd seeds");
+
+        let doubled = outcome.map(|x| x * 2);
+        assert_eq!(doubled.diagnostics.len(), 1);
+        assert_eq!(doubled.into_answer(), 42);
+        assert!(Outcome::new(()).is_clean());
+    }
+}
@@ -0,0 +1,116 @@
+/*                        PROGRESS
+
+Coarse progress events published by long running solvers, such as the day 5
+brute force search. A solver accepts any `ProgressSink`, so the caller decides
+whether events are drawn as a progress bar, logged, forwarded over a channel
+to another thread, or simply ignored.
+
+*/
+
+#[cfg(feature = "std")]
+use std::sync::mpsc::Sender;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgressEvent {
+    pub day: u32,
+    pub stage: &'static str,
+    pub done: u64,
+    pub total: u64
+}
+
+impl ProgressEvent {
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {1.0} else {self.done as f64 / self.total as f64}
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.done >= self.total
+    }
+}
+
+pub trait ProgressSink {
+    fn publish(&mut self, event: &ProgressEvent);
+}
+
+impl<F: FnMut(&ProgressEvent)> ProgressSink for F {
+    fn publish(&mut self, event: &ProgressEvent) {
+        self(event)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ProgressSink for Sender<ProgressEvent> {
+    fn publish(&mut self, event: &ProgressEvent) {
+        // A receiver which has gone away no longer wants updates, this should not stop the solver
+        let _ = self.send(event.clone());
+    }
+}
+
+// Discards all events
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn publish(&mut self, _event: &ProgressEvent) {}
+}
+
+// Writes each event to the log at 'info' level
+pub struct LogProgress;
+
+impl ProgressSink for LogProgress {
+    fn publish(&mut self, event: &ProgressEvent) {
+        tracing::info!(
+            day = event.day,
+            stage = event.stage,
+            done = event.done,
+            total = event.total,
+            "Progress {:.1}%", 100.0 * event.fraction()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    fn event(done: u64, total: u64) -> ProgressEvent {
+        ProgressEvent { day: 5, stage: "test", done, total }
+    }
+
+    #[test]
+    fn test_sinks() {
+        let mut seen = Vec::new();
+        let mut sink = |e: &ProgressEvent| seen.push(e.done);
+        sink.publish(&event(1, 4));
+        sink.publish(&event(4, 4));
+        assert_eq!(seen, vec![1, 4]);
+
+        let (sender, receiver) = channel();
+        let mut sink = sender;
+        sink.publish(&event(2, 4));
+        drop(receiver);
+        sink.publish(&event(3, 4));
+
+        NoProgress.publish(&event(0, 0));
+
+        assert_eq!(event(1, 4).fraction(), 0.25);
+        assert!(event(0, 0).is_finished());
+        assert!(!event(3, 4).is_finished());
+    }
+
+    #[test]
+    fn test_channel_across_threads() {
+        let (sender, receiver) = channel();
+
+        let worker = std::thread::spawn(move || {
+            let mut sink = sender;
+            for done in 1..=3 {
+                sink.publish(&event(done, 3));
+            }
+        });
+        worker.join().unwrap();
+
+        let received: Vec<u64> = receiver.iter().map(|e| e.done).collect();
+        assert_eq!(received, vec![1, 2, 3]);
+    }
+}
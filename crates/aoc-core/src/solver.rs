@@ -0,0 +1,172 @@
+/*                        SOLVERS
+
+A common interface over the days, so that puzzles can be listed and run
+without knowing the functions each day module exposes. Every day provides a
+type implementing `Solver` using the puzzle's own parameters by default, e.g.
+the bag of 12 red, 13 green and 14 blue cubes for day 2.
+
+Day modules register their solvers themselves with `inventory::submit!`, so
+adding a day only takes declaring its module in `src/lib.rs`.
+
+*/
+
+use alloc::{boxed::Box, format, vec, vec::Vec};
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::outcome::Outcome;
+use crate::progress::{ProgressEvent, ProgressSink};
+
+// Solvers are Send and Sync so that days can be solved in parallel
+pub trait Solver: Send + Sync {
+    fn day(&self) -> u32;
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError>;
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError>;
+
+    fn solve(&self, part: u32, input: &str) -> Result<Answer, AocError> {
+        /* Solve the given part, 1 or 2, of the puzzle given the puzzle input. */
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(AocError::InvalidArgument(format!("Day {} has no part {}", self.day(), part)))
+        }
+    }
+
+    fn solve_with_diagnostics(&self, part: u32, input: &str) -> Result<Outcome<Answer>, AocError> {
+        /* Solve the given part, keeping any warnings about the input alongside the answer.
+
+        Solvers which notice surprising features of their input, such as lines which had
+        to be skipped, override this, the others return the answer without diagnostics.
+        */
+        Ok(Outcome::new(self.solve(part, input)?))
+    }
+
+    fn solve_with_progress(&self, part: u32, input: &str, progress: &mut dyn ProgressSink) -> Result<Answer, AocError> {
+        /* Solve the given part, publishing progress along the way.
+
+        Solvers which can take a while override this to report their intermediate
+        progress, the others only publish a single event once they are finished.
+        */
+        let answer = self.solve(part, input)?;
+        progress.publish(&ProgressEvent { day: self.day(), stage: "solve", done: 1, total: 1 });
+        Ok(answer)
+    }
+}
+
+pub struct Registration {
+    /* A solver submitted by a day module, built on demand.
+
+    The solver used for a day is named "default", other implementations of the
+    same day are only listed by `alternatives`.
+    */
+    pub name: &'static str,
+    pub build: fn() -> Box<dyn Solver>
+}
+
+inventory::collect!(Registration);
+
+fn registered(name: &str) -> Vec<Box<dyn Solver>> {
+    /* Build the registered solvers with the given name, in day order. */
+    let mut built: Vec<Box<dyn Solver>> = inventory::iter::<Registration>.into_iter()
+        .filter(|r| r.name == name)
+        .map(|r| (r.build)())
+        .collect();
+    built.sort_by_key(|s| s.day());
+    built
+}
+
+pub fn solvers() -> Vec<Box<dyn Solver>> {
+    /* A solver for every implemented day, in day order.
+
+    # Examples
+
+    ```
+    for solver in aoc23::solvers() {
+        tracing::info!("Day {}", solver.day());
+    }
+    ```
+    */
+    registered("default")
+}
+
+pub fn solver(day: u32) -> Option<Box<dyn Solver>> {
+    /* The solver for a single day, if that day has been implemented. */
+    solvers().into_iter().find(|s| s.day() == day)
+}
+
+pub fn alternatives(day: u32) -> Vec<(&'static str, Box<dyn Solver>)> {
+    /* Every implementation of a day, named, the registered solver first.
+
+    Days solved in more than one way list each of them, e.g. for timing one against
+    the other with `bench::compare`, the others only their registered solver.
+    */
+    let mut implementations: Vec<(&'static str, Box<dyn Solver>)> = match solver(day) {
+        Some(s) => vec![("default", s)],
+        None => return Vec::new()
+    };
+
+    let mut others: Vec<(&'static str, Box<dyn Solver>)> = inventory::iter::<Registration>.into_iter()
+        .filter(|r| r.name != "default")
+        .map(|r| (r.name, (r.build)()))
+        .filter(|(_, s)| s.day() == day)
+        .collect();
+    others.sort_by_key(|(name, _)| *name);
+    implementations.append(&mut others);
+
+    implementations
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day_2;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_registered_solvers() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/test");
+
+        let expected = [
+            (1, "day_1_1.dat", 142, "day_1_2.dat", 281),
+            (2, "day_2.dat", 8, "day_2.dat", day_2::get_total_game_power(test_dir.join("day_2.dat")).unwrap() as i64),
+            (3, "day_3.dat", 4361, "day_3.dat", 467835),
+            (4, "day_4.dat", 13, "day_4.dat", 30),
+            (5, "day_5.dat", 35, "day_5.dat", 46)
+        ];
+
+        assert_eq!(solvers().iter().map(|s| s.day()).collect::<Vec<u32>>(), vec![1, 2, 3, 4, 5]);
+
+        for (day, part1_file, part1, part2_file, part2) in expected {
+            let day_solver = solver(day).unwrap();
+            let part1_input = std::fs::read_to_string(test_dir.join(part1_file)).unwrap();
+            let part2_input = std::fs::read_to_string(test_dir.join(part2_file)).unwrap();
+            assert_eq!(day_solver.solve(1, &part1_input).unwrap(), part1, "day {}", day);
+            assert_eq!(day_solver.solve(2, &part2_input).unwrap(), part2, "day {}", day);
+            assert!(day_solver.solve(3, &part1_input).is_err());
+        }
+
+        assert!(solver(6).is_none());
+
+        let names = |day| alternatives(day).into_iter().map(|(name, _)| name).collect::<Vec<&str>>();
+        assert_eq!(names(5), vec!["default", "bruteforce"]);
+        assert_eq!(names(4), vec!["default"]);
+        assert!(names(6).is_empty());
+    }
+
+    #[test]
+    fn test_solve_with_progress() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/test");
+
+        for (day, file, answer, stages) in [(4, "day_4.dat", 30, vec!["solve"]), (5, "day_5.dat", 46, vec!["seed ranges"; 2])] {
+            let input = std::fs::read_to_string(test_dir.join(file)).unwrap();
+            let mut events = Vec::<ProgressEvent>::new();
+            let mut sink = |e: &ProgressEvent| events.push(e.clone());
+
+            assert_eq!(solver(day).unwrap().solve_with_progress(2, &input, &mut sink).unwrap(), answer);
+            assert_eq!(events.iter().map(|e| e.stage).collect::<Vec<&str>>(), stages);
+            assert!(events.last().unwrap().is_finished());
+        }
+    }
+}
@@ -0,0 +1,244 @@
+/*                        BIT GRID
+
+A dense two dimensional grid of booleans stored as bits, eight times smaller
+than a grid of bools. Each row starts on a new word so that operations on
+whole rows work a word at a time.
+
+*/
+
+use alloc::{vec, vec::Vec};
+
+const WORD_BITS: usize = 64;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> BitGrid {
+        /* Create a grid of the given size with all cells unset.
+
+        # Arguments
+
+        * `width` - number of columns
+        * `height` - number of rows
+
+        # Examples
+
+        ```
+        let visited = BitGrid::new(140, 140);
+        ```
+        */
+        let words_per_row = width.div_ceil(WORD_BITS);
+        BitGrid { width, height, words_per_row, words: vec![0; words_per_row * height] }
+    }
+
+    pub fn from_lines(input: &str, set_char: char) -> BitGrid {
+        /* Create a grid from lines of text, setting cells matching the given character.
+
+        The width is that of the longest line, shorter lines are padded with unset cells.
+
+        # Arguments
+
+        * `input` - the text to read
+        * `set_char` - the character representing a set cell, e.g. '#'
+
+        # Examples
+
+        ```
+        let rocks = BitGrid::from_lines("#.#\n.#.", '#');
+        assert_eq!(rocks.count(), 3);
+        ```
+        */
+        let lines: Vec<&str> = input.lines().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut grid = BitGrid::new(width, lines.len());
+
+        for (row, line) in lines.iter().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                if c == set_char {
+                    grid.set(row, column, true);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, row: usize, column: usize) -> (usize, u64) {
+        (row * self.words_per_row + column / WORD_BITS, 1 << (column % WORD_BITS))
+    }
+
+    fn row_words(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        /* Whether the cell at the given position is set, positions outside the grid are unset. */
+        if row >= self.height || column >= self.width {
+            return false;
+        }
+        let (word, mask) = self.index(row, column);
+        self.words[word] & mask != 0
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: bool) {
+        /* Set or unset the cell at the given position.
+
+        # Panics
+
+        If the position lies outside of the grid.
+        */
+        assert!(
+            row < self.height && column < self.width,
+            "Position ({}, {}) outside of {}x{} grid", row, column, self.height, self.width
+        );
+        let (word, mask) = self.index(row, column);
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    pub fn insert(&mut self, row: usize, column: usize) -> bool {
+        /* Set a cell, returning true if it was not previously set.
+
+        Convenient for visited sets during searches.
+        */
+        let was_set = self.get(row, column);
+        self.set(row, column, true);
+        !was_set
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn count_row(&self, row: usize) -> usize {
+        self.row_words(row).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn rows_equal(&self, row_a: usize, row_b: usize) -> bool {
+        self.row_words(row_a) == self.row_words(row_b)
+    }
+
+    pub fn row_difference(&self, row_a: usize, row_b: usize) -> usize {
+        /* Number of cells which differ between two rows. */
+        self.row_words(row_a)
+            .iter()
+            .zip(self.row_words(row_b))
+            .map(|(a, b)| (a ^ b).count_ones() as usize)
+            .sum()
+    }
+
+    fn combine<F: Fn(u64, u64) -> u64>(&mut self, other: &BitGrid, operation: F) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "Cannot combine {}x{} grid with {}x{} grid", self.height, self.width, other.height, other.width
+        );
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word = operation(*word, *other_word);
+        }
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a | b);
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a & b);
+    }
+
+    pub fn iter_set(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        /* Iterate through the positions of all set cells in row order. */
+        (0..self.height).flat_map(move |row| {
+            self.row_words(row)
+                .iter()
+                .enumerate()
+                .flat_map(move |(i, &word)| {
+                    let mut remaining = word;
+                    core::iter::from_fn(move || {
+                        if remaining == 0 {
+                            return None;
+                        }
+                        let bit = remaining.trailing_zeros() as usize;
+                        remaining &= remaining - 1;
+                        Some((row, i * WORD_BITS + bit))
+                    })
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_set_get_count() {
+        let mut grid = BitGrid::new(70, 3);
+
+        grid.set(0, 0, true);
+        grid.set(1, 65, true);
+        grid.set(2, 69, true);
+
+        assert!(grid.get(1, 65));
+        assert!(!grid.get(1, 64));
+        assert!(!grid.get(5, 5));
+        assert_eq!(grid.count(), 3);
+        assert_eq!(grid.count_row(1), 1);
+
+        assert!(!grid.insert(1, 65));
+        assert!(grid.insert(1, 64));
+
+        grid.set(1, 65, false);
+        assert_eq!(grid.count(), 3);
+        assert_eq!(grid.iter_set().collect::<Vec<_>>(), vec![(0, 0), (1, 64), (2, 69)]);
+    }
+
+    #[test]
+    fn test_from_lines_and_rows() {
+        let grid = BitGrid::from_lines("#.#\n.#.\n#.#", '#');
+
+        assert_eq!((grid.height(), grid.width()), (3, 3));
+        assert_eq!(grid.count(), 5);
+        assert!(grid.rows_equal(0, 2));
+        assert!(!grid.rows_equal(0, 1));
+        assert_eq!(grid.row_difference(0, 1), 3);
+    }
+
+    #[test]
+    fn test_combine() {
+        let mut a = BitGrid::from_lines("##.\n...", '#');
+        let b = BitGrid::from_lines(".##\n..#", '#');
+
+        let mut intersection = a.clone();
+        intersection.intersect_with(&b);
+        assert_eq!(intersection.iter_set().collect::<Vec<_>>(), vec![(0, 1)]);
+
+        a.union_with(&b);
+        assert_eq!(a.count(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_out_of_bounds() {
+        BitGrid::new(2, 2).set(2, 0, true);
+    }
+}
@@ -0,0 +1,77 @@
+/*                        BLOCK SPLITTING
+
+Many puzzle inputs consist of paragraphs separated by blank lines, e.g. the
+seeds and maps of the day 5 almanac.
+
+*/
+
+use alloc::vec::Vec;
+
+pub fn blocks(input: &str) -> Vec<&str> {
+    /* Split an input into the blocks of lines separated by blank lines.
+
+    Lines containing only whitespace count as blank, both LF and CRLF line endings
+    are supported and trailing whitespace at the end of each block is removed.
+
+    # Arguments
+
+    * `input` - the full puzzle input
+
+    # Returns
+
+    The non-empty blocks as slices of the input in order of appearance.
+
+    # Examples
+
+    ```
+    let paragraphs = blocks("seeds: 1 2\r\n\r\nseed-to-soil map:\r\n1 2 3\r\n");
+    assert_eq!(paragraphs, vec!["seeds: 1 2", "seed-to-soil map:\r\n1 2 3"]);
+    ```
+    */
+    let mut found_blocks = Vec::<&str>::new();
+    let mut block_start: Option<usize> = None;
+    let mut block_end: usize = 0;
+    let mut offset: usize = 0;
+
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end();
+
+        if content.trim_start().is_empty() {
+            if let Some(start) = block_start.take() {
+                found_blocks.push(&input[start..block_end]);
+            }
+        } else {
+            if block_start.is_none() {
+                block_start = Some(offset);
+            }
+            block_end = offset + content.len();
+        }
+
+        offset += line.len();
+    }
+
+    if let Some(start) = block_start {
+        found_blocks.push(&input[start..block_end]);
+    }
+
+    found_blocks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_blocks() {
+        let input = "a\nb\n\n\nc\n  \nd  \ne\n";
+        assert_eq!(blocks(input), vec!["a\nb", "c", "d  \ne"]);
+    }
+
+    #[test]
+    fn test_blocks_crlf() {
+        let input = "\r\nseeds: 1 2\r\n\r\nseed-to-soil map:\r\n1 2 3\r\n \t\r\n";
+        assert_eq!(blocks(input), vec!["seeds: 1 2", "seed-to-soil map:\r\n1 2 3"]);
+        assert!(blocks("").is_empty());
+        assert!(blocks("\n\r\n").is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+/*                        COMBINATORICS
+
+Iterators over the unordered pairs and ordered k-permutations of the items of
+a slice, e.g. the distances between every pair of galaxies or the pairwise
+intersections of hailstone paths.
+
+*/
+
+use alloc::vec::Vec;
+
+pub struct Pairs<'a, T> {
+    items: &'a [T],
+    i: usize,
+    j: usize
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.j >= self.items.len() {
+            self.i += 1;
+            self.j = self.i + 1;
+        }
+        if self.j >= self.items.len() {
+            return None;
+        }
+        let pair = (&self.items[self.i], &self.items[self.j]);
+        self.j += 1;
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.items.len();
+        let remaining = if self.i + 1 >= n {
+            0
+        } else {
+            // Pairs starting after item i, plus those left for item i itself
+            let after = n - self.i - 1;
+            (after * after.saturating_sub(1)) / 2 + n.saturating_sub(self.j)
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Pairs<'_, T> {}
+
+pub fn pairs<T>(items: &[T]) -> Pairs<'_, T> {
+    /* Iterate through every unordered pair of distinct items, (a, b) with a before b.
+
+    # Examples
+
+    ```
+    let total: i32 = pairs(&[1, 2, 3]).map(|(a, b)| a * b).sum();
+    assert_eq!(total, 11);
+    ```
+    */
+    Pairs { items, i: 0, j: 1 }
+}
+
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    k: usize,
+    indices: Vec<usize>,
+    cycles: Vec<usize>,
+    started: bool,
+    finished: bool
+}
+
+impl<'a, T> Iterator for Permutations<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if self.started {
+            // Advance to the next permutation in lexicographic order of indices
+            let n = self.items.len();
+            let mut advanced = false;
+            for i in (0..self.k).rev() {
+                self.cycles[i] -= 1;
+                if self.cycles[i] == 0 {
+                    self.indices[i..].rotate_left(1);
+                    self.cycles[i] = n - i;
+                } else {
+                    let j = n - self.cycles[i];
+                    self.indices.swap(i, j);
+                    advanced = true;
+                    break;
+                }
+            }
+            if !advanced {
+                self.finished = true;
+                return None;
+            }
+        }
+
+        self.started = true;
+        Some(self.indices[..self.k].iter().map(|&i| &self.items[i]).collect())
+    }
+}
+
+pub fn permutations<T>(items: &[T], k: usize) -> Permutations<'_, T> {
+    /* Iterate through every ordered selection of k distinct items.
+
+    Permutations are produced in lexicographic order of item position.
+
+    # Examples
+
+    ```
+    assert_eq!(permutations(&['a', 'b', 'c'], 2).count(), 6);
+    ```
+    */
+    let n = items.len();
+    Permutations {
+        items,
+        k,
+        indices: (0..n).collect(),
+        cycles: (0..k.min(n)).map(|i| n - i).collect(),
+        started: false,
+        finished: k > n
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pairs() {
+        let found: Vec<(&i32, &i32)> = pairs(&[1, 2, 3, 4]).collect();
+        assert_eq!(found, vec![(&1, &2), (&1, &3), (&1, &4), (&2, &3), (&2, &4), (&3, &4)]);
+
+        let mut iter = pairs(&[1, 2, 3, 4]);
+        assert_eq!(iter.len(), 6);
+        iter.next();
+        iter.next();
+        iter.next();
+        assert_eq!(iter.len(), 3);
+
+        assert_eq!(pairs::<i32>(&[]).count(), 0);
+        assert_eq!(pairs(&[1]).count(), 0);
+    }
+
+    #[test]
+    fn test_permutations() {
+        let found: Vec<String> = permutations(&['a', 'b', 'c'], 2)
+            .map(|p| p.into_iter().collect())
+            .collect();
+        assert_eq!(found, vec!["ab", "ac", "ba", "bc", "ca", "cb"]);
+
+        assert_eq!(permutations(&[1, 2, 3, 4], 4).count(), 24);
+        assert_eq!(permutations(&[1, 2, 3], 0).collect::<Vec<_>>(), vec![Vec::<&i32>::new()]);
+        assert_eq!(permutations(&[1, 2], 3).count(), 0);
+    }
+}
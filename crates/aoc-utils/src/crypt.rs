@@ -0,0 +1,210 @@
+/*                        ENCRYPTED INPUTS
+
+Puzzle inputs are not meant to be shared, so with the `encrypt` feature they
+can be kept encrypted on disk, e.g. in a public repository, and decrypted as
+they are read by `InputSource`, see src/io.rs.
+
+An encrypted input is the magic bytes 'AOC23ENC1', a random 24 byte nonce and
+the input sealed with XChaCha20-Poly1305 under a 32 byte key, which is given
+as 64 hexadecimal digits. The key used while reading inputs is set for the
+whole process with `set_input_key`, so that every input read can be decrypted
+without passing the key down to each reader.
+
+*/
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+pub const ENCRYPTED_MAGIC: &[u8] = b"AOC23ENC1";
+
+const NONCE_LEN: usize = 24;
+
+static INPUT_KEY: RwLock<Option<InputKey>> = RwLock::new(None);
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct InputKey([u8; 32]);
+
+impl InputKey {
+    pub fn from_hex(hex: &str) -> Option<InputKey> {
+        /* A key from its 64 hexadecimal digits, surrounding whitespace ignored. */
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return None;
+        }
+
+        let mut key = [0u8; 32];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+        }
+        Some(InputKey(key))
+    }
+
+    pub fn generate() -> InputKey {
+        /* A new random key. */
+        InputKey(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+// The key is left out so that it does not end up in logs
+impl fmt::Debug for InputKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "InputKey(..)")
+    }
+}
+
+pub fn set_input_key(key: Option<InputKey>) {
+    /* Decrypt, or stop decrypting, the inputs read by this process with the given key. */
+    match INPUT_KEY.write() {
+        Ok(mut k) => *k = key,
+        Err(poisoned) => *poisoned.into_inner() = key
+    };
+}
+
+pub fn input_key() -> Option<InputKey> {
+    match INPUT_KEY.read() {
+        Ok(k) => k.clone(),
+        Err(poisoned) => poisoned.into_inner().clone()
+    }
+}
+
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(ENCRYPTED_MAGIC)
+}
+
+pub fn encrypt(key: &InputKey, plain: &[u8]) -> Vec<u8> {
+    /* Seal an input under the key, with a fresh nonce.
+
+    # Examples
+
+    ```
+    let key = InputKey::generate();
+    let sealed = encrypt(&key, b"Card 1: 41 48 | 83 86");
+    assert_eq!(decrypt(&key, &sealed).unwrap(), b"Card 1: 41 48 | 83 86");
+    ```
+    */
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let cipher = XChaCha20Poly1305::new(&key.0.into());
+
+    // Encryption can only fail for inputs far larger than any puzzle's
+    let sealed = match cipher.encrypt(&nonce, plain) {
+        Ok(s) => s,
+        Err(e) => panic!("Failed to encrypt an input of {} bytes: {}", plain.len(), e)
+    };
+
+    let mut encrypted = Vec::with_capacity(ENCRYPTED_MAGIC.len() + NONCE_LEN + sealed.len());
+    encrypted.extend_from_slice(ENCRYPTED_MAGIC);
+    encrypted.extend_from_slice(&nonce);
+    encrypted.extend_from_slice(&sealed);
+    encrypted
+}
+
+pub fn decrypt(key: &InputKey, encrypted: &[u8]) -> io::Result<Vec<u8>> {
+    /* Open an input sealed by `encrypt`, failing if it was sealed under another key or altered. */
+    let body = match encrypted.strip_prefix(ENCRYPTED_MAGIC) {
+        Some(b) if b.len() >= NONCE_LEN => b,
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "not an encrypted input"))
+    };
+
+    let (nonce, sealed) = body.split_at(NONCE_LEN);
+    match XChaCha20Poly1305::new(&key.0.into()).decrypt(XNonce::from_slice(nonce), sealed) {
+        Ok(plain) => Ok(plain),
+        Err(_) => Err(io::Error::new(io::ErrorKind::InvalidData, "the input could not be decrypted with the key given"))
+    }
+}
+
+pub fn decrypt_with_input_key(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    /* Decrypt an encrypted input with the key set for the process, passing any others through unchanged. */
+    if !is_encrypted(&bytes) {
+        return Ok(bytes);
+    }
+    match input_key() {
+        Some(key) => decrypt(&key, &bytes),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, "the input is encrypted and no key has been given"))
+    }
+}
+
+pub fn encrypt_file(path: impl AsRef<Path>, key: &InputKey) -> io::Result<bool> {
+    /* Encrypt a file in place unless it already is, returning whether it was encrypted.
+
+    The encrypted input is written alongside the file then renamed over it, so
+    an interrupted run never leaves an input which is neither plain nor sealed.
+    */
+    let path = path.as_ref();
+    let contents = std::fs::read(path)?;
+    if is_encrypted(&contents) {
+        return Ok(false);
+    }
+
+    let mut partial_name = path.as_os_str().to_os_string();
+    partial_name.push(".partial");
+    let partial_path = PathBuf::from(partial_name);
+    std::fs::write(&partial_path, encrypt(key, &contents))?;
+    std::fs::rename(&partial_path, path)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encrypt() {
+        let key = InputKey::generate();
+        let plain = b"Time:      7  15   30\nDistance:  9  40  200\n";
+
+        let sealed = encrypt(&key, plain);
+        assert!(is_encrypted(&sealed));
+        assert_ne!(encrypt(&key, plain), sealed);
+        assert_eq!(decrypt(&key, &sealed).unwrap(), plain);
+
+        let mut altered = sealed.clone();
+        *altered.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&key, &altered).is_err());
+        assert!(decrypt(&InputKey::generate(), &sealed).is_err());
+        assert!(decrypt(&key, plain).is_err());
+        assert!(decrypt(&key, ENCRYPTED_MAGIC).is_err());
+
+        let plain_file = std::env::temp_dir().join(format!("aoc23_crypt_test_{}.dat", std::process::id()));
+        std::fs::write(&plain_file, plain).unwrap();
+        let first = encrypt_file(&plain_file, &key).unwrap();
+        let second = encrypt_file(&plain_file, &key).unwrap();
+        let stored = std::fs::read(&plain_file).unwrap();
+        let partial_left = plain_file.with_extension("dat.partial").exists();
+        std::fs::remove_file(&plain_file).unwrap();
+
+        assert!(first);
+        assert!(!partial_left);
+        assert!(!second);
+        assert_eq!(decrypt(&key, &stored).unwrap(), plain);
+    }
+
+    #[test]
+    fn test_input_key() {
+        let key = InputKey::generate();
+        assert_eq!(InputKey::from_hex(&key.to_hex()), Some(key.clone()));
+        assert_eq!(InputKey::from_hex(&format!("  {}\n", key.to_hex().to_uppercase())), Some(key.clone()));
+        assert_eq!(InputKey::from_hex("abc"), None);
+        assert_eq!(InputKey::from_hex(&"zz".repeat(32)), None);
+        assert_eq!(InputKey::from_hex(&"é".repeat(32)), None);
+        assert_eq!(format!("{:?}", key), "InputKey(..)");
+
+        // Only an encrypted input needs the key, so this cannot upset tests reading plain inputs
+        let sealed = encrypt(&key, b"Card 1");
+        set_input_key(Some(key));
+        let opened = decrypt_with_input_key(sealed.clone());
+        set_input_key(None);
+        let unkeyed = decrypt_with_input_key(sealed);
+
+        assert_eq!(opened.unwrap(), b"Card 1");
+        assert!(unkeyed.is_err());
+        assert_eq!(decrypt_with_input_key(b"Card 1".to_vec()).unwrap(), b"Card 1");
+    }
+}
@@ -0,0 +1,87 @@
+/*                        FLOOD FILL
+
+Find connected regions of a grid, e.g. the cells enclosed by a loop or the
+area dug out by a dig plan.
+
+*/
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use super::bitgrid::BitGrid;
+use super::grid::{Connectivity, Grid};
+
+pub fn flood_fill<T, F>(grid: &Grid<T>, start: (usize, usize), connectivity: Connectivity, passable: F) -> Vec<(usize, usize)>
+where T: Clone, F: Fn(&T) -> bool {
+    /* Find all cells reachable from a starting cell moving only through passable cells.
+
+    # Arguments
+
+    * `grid` - the grid to fill
+    * `start` - the (row, column) position to fill from
+    * `connectivity` - whether moves may be made diagonally
+    * `passable` - returns whether a cell's value may be entered
+
+    # Returns
+
+    The positions within the region in the order they were reached, empty if the
+    starting cell is not passable or lies outside the grid.
+
+    # Examples
+
+    ```
+    let grid = Grid::from_lines("..#\n.##\n#..").unwrap();
+    let region = flood_fill(&grid, (0, 0), Connectivity::Four, |&c| c == '.');
+    assert_eq!(region.len(), 3);
+    ```
+    */
+    match grid.get(start.0, start.1) {
+        Some(value) if passable(value) => (),
+        _ => return Vec::new()
+    };
+
+    let mut visited = BitGrid::new(grid.width(), grid.height());
+    let mut queue = VecDeque::from([start]);
+    let mut region = Vec::<(usize, usize)>::new();
+
+    visited.set(start.0, start.1, true);
+
+    while let Some((row, column)) = queue.pop_front() {
+        region.push((row, column));
+
+        for (r, c) in grid.neighbours(row, column, connectivity) {
+            let enterable = match grid.get(r, c) {
+                Some(value) => passable(value),
+                None => false
+            };
+            if enterable && visited.insert(r, c) {
+                queue.push_back((r, c));
+            }
+        }
+    }
+
+    region
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_connectivity() {
+        let grid = Grid::from_lines("..#\n.#.\n#..").unwrap();
+
+        let region = flood_fill(&grid, (0, 0), Connectivity::Four, |&c| c == '.');
+        assert_eq!(region, vec![(0, 0), (0, 1), (1, 0)]);
+
+        let region = flood_fill(&grid, (0, 0), Connectivity::Eight, |&c| c == '.');
+        assert_eq!(region.len(), 6);
+    }
+
+    #[test]
+    fn test_impassable_start() {
+        let grid = Grid::from_lines("#.\n..").unwrap();
+
+        assert!(flood_fill(&grid, (0, 0), Connectivity::Four, |&c| c == '.').is_empty());
+        assert!(flood_fill(&grid, (5, 5), Connectivity::Four, |&c| c == '.').is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+/*                        GEOMETRY
+
+Exact 2D predicates on integer points: orientation, segment and ray
+intersection, and an even-odd ray casting interior test for polygons.
+Intersection points are returned as rationals so that no precision is lost
+for large coordinates such as hailstone trajectories.
+
+*/
+
+use super::rational::Rational;
+
+pub type Point = (i128, i128);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ray {
+    pub origin: Point,
+    pub direction: Point
+}
+
+pub fn cross(o: Point, a: Point, b: Point) -> i128 {
+    /* Cross product of (a - o) and (b - o): positive if o -> a -> b turns anticlockwise,
+    negative if clockwise, and zero if the three points are collinear. */
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+fn on_segment(p: Point, segment: &Segment) -> bool {
+    cross(segment.start, segment.end, p) == 0
+        && p.0 >= segment.start.0.min(segment.end.0)
+        && p.0 <= segment.start.0.max(segment.end.0)
+        && p.1 >= segment.start.1.min(segment.end.1)
+        && p.1 <= segment.start.1.max(segment.end.1)
+}
+
+pub fn segments_intersect(a: &Segment, b: &Segment) -> bool {
+    /* Whether two closed segments share at least one point, including touching
+    end points and overlapping collinear segments. */
+    let d1 = cross(b.start, b.end, a.start).signum();
+    let d2 = cross(b.start, b.end, a.end).signum();
+    let d3 = cross(a.start, a.end, b.start).signum();
+    let d4 = cross(a.start, a.end, b.end).signum();
+
+    if d1 * d2 < 0 && d3 * d4 < 0 {
+        return true;
+    }
+
+    on_segment(a.start, b) || on_segment(a.end, b) || on_segment(b.start, a) || on_segment(b.end, a)
+}
+
+fn line_parameters(p: Point, r: Point, q: Point, s: Point) -> Option<(Rational, Rational)> {
+    /* Parameters (t, u) such that p + t r == q + u s, or None if the lines are parallel. */
+    let denominator = r.0 * s.1 - r.1 * s.0;
+
+    if denominator == 0 {
+        return None;
+    }
+
+    let qp = (q.0 - p.0, q.1 - p.1);
+    let t = Rational::new(qp.0 * s.1 - qp.1 * s.0, denominator);
+    let u = Rational::new(qp.0 * r.1 - qp.1 * r.0, denominator);
+
+    Some((t, u))
+}
+
+fn point_along(p: Point, r: Point, t: Rational) -> (Rational, Rational) {
+    (Rational::from(p.0) + t * Rational::from(r.0), Rational::from(p.1) + t * Rational::from(r.1))
+}
+
+pub fn segment_intersection(a: &Segment, b: &Segment) -> Option<(Rational, Rational)> {
+    /* The single point where two non-parallel segments cross.
+
+    Parallel and collinear segments return None even if they overlap; use
+    `segments_intersect` to test for any contact.
+    */
+    let r = (a.end.0 - a.start.0, a.end.1 - a.start.1);
+    let s = (b.end.0 - b.start.0, b.end.1 - b.start.1);
+    let (t, u) = line_parameters(a.start, r, b.start, s)?;
+
+    if t < Rational::ZERO || t > Rational::ONE || u < Rational::ZERO || u > Rational::ONE {
+        return None;
+    }
+
+    Some(point_along(a.start, r, t))
+}
+
+pub fn ray_intersection(a: &Ray, b: &Ray) -> Option<(Rational, Rational)> {
+    /* The point where two non-parallel rays cross, if it lies ahead of both origins.
+
+    # Examples
+
+    ```
+    // Hailstones whose paths cross in the future
+    let a = Ray { origin: (19, 13), direction: (-2, 1) };
+    let b = Ray { origin: (18, 19), direction: (-1, -1) };
+    let (x, y) = ray_intersection(&a, &b).unwrap();
+    ```
+    */
+    let (t, u) = line_parameters(a.origin, a.direction, b.origin, b.direction)?;
+
+    if t < Rational::ZERO || u < Rational::ZERO {
+        return None;
+    }
+
+    Some(point_along(a.origin, a.direction, t))
+}
+
+pub fn point_in_polygon(p: Point, vertices: &[Point]) -> bool {
+    /* Even-odd ray casting test for whether a point is strictly inside a closed polygon.
+
+    Points lying on the boundary are not considered inside.
+    */
+    let n = vertices.len();
+    let mut inside = false;
+
+    for i in 0..n {
+        let a = vertices[i];
+        let b = vertices[(i + 1) % n];
+
+        if on_segment(p, &Segment { start: a, end: b }) {
+            return false;
+        }
+
+        // Count edges crossing the horizontal ray from p towards +x
+        if (a.1 > p.1) != (b.1 > p.1) {
+            let side = cross(a, b, p);
+            if (side > 0) == (b.1 > a.1) {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn point(x: i128, y: i128) -> (Rational, Rational) {
+        (Rational::from(x), Rational::from(y))
+    }
+
+    #[test]
+    fn test_segments() {
+        let a = Segment { start: (0, 0), end: (4, 4) };
+        let b = Segment { start: (0, 4), end: (4, 0) };
+        let c = Segment { start: (5, 5), end: (6, 6) };
+        let d = Segment { start: (2, 2), end: (6, 6) };
+        let e = Segment { start: (4, 4), end: (8, 0) };
+
+        assert!(segments_intersect(&a, &b));
+        assert_eq!(segment_intersection(&a, &b), Some(point(2, 2)));
+        assert!(!segments_intersect(&a, &c));
+        assert!(segments_intersect(&a, &d));
+        assert_eq!(segment_intersection(&a, &d), None);
+        assert!(segments_intersect(&a, &e));
+        assert_eq!(segment_intersection(&a, &e), Some(point(4, 4)));
+    }
+
+    #[test]
+    fn test_hailstone_rays() {
+        let a = Ray { origin: (19, 13), direction: (-2, 1) };
+        let b = Ray { origin: (18, 19), direction: (-1, -1) };
+        let c = Ray { origin: (20, 19), direction: (1, -5) };
+        let d = Ray { origin: (20, 25), direction: (-2, -2) };
+
+        let (x, y) = ray_intersection(&a, &b).unwrap();
+        assert_eq!(x, Rational::new(43, 3));
+        assert_eq!(y, Rational::new(46, 3));
+
+        // Crossed in the past for the first hailstone
+        assert_eq!(ray_intersection(&a, &c), None);
+
+        // Parallel paths
+        assert_eq!(ray_intersection(&b, &d), None);
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        let notched = [(0, 0), (6, 0), (6, 6), (3, 3), (0, 6)];
+
+        assert!(point_in_polygon((2, 2), &square));
+        assert!(!point_in_polygon((5, 2), &square));
+        assert!(!point_in_polygon((4, 2), &square));
+        assert!(point_in_polygon((1, 4), &notched));
+        assert!(!point_in_polygon((3, 5), &notched));
+        assert!(point_in_polygon((3, 1), &notched));
+    }
+}
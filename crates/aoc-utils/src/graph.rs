@@ -0,0 +1,101 @@
+/*                        GRAPHS
+
+Utilities for directed graphs given as lists of edges, such as the chain of
+category maps in the day 5 almanac.
+
+*/
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::hash::Hash;
+use indexmap::IndexMap;
+
+use super::hash::FxBuildHasher;
+
+pub fn topological_sort<N, I>(edges: I) -> Result<Vec<N>, Vec<N>>
+where N: Clone + Eq + Hash, I: IntoIterator<Item = (N, N)> {
+    /* Order the nodes of a directed graph so that every edge points forwards.
+
+    Uses Kahn's algorithm, repeatedly taking nodes with no remaining incoming edges.
+    Ties are broken by the order in which nodes first appear within the edges so
+    the result is deterministic.
+
+    # Arguments
+
+    * `edges` - (from, to) pairs defining the graph
+
+    # Returns
+
+    The sorted nodes, or if the graph contains a cycle the nodes which could not be
+    ordered, i.e. those on or reachable from a cycle.
+
+    # Examples
+
+    ```
+    let order = topological_sort([("soil", "water"), ("seed", "soil")]).unwrap();
+    assert_eq!(order, vec!["seed", "soil", "water"]);
+    ```
+    */
+    let mut in_degree = IndexMap::<N, usize, FxBuildHasher>::default();
+    let mut successors = IndexMap::<N, Vec<N>, FxBuildHasher>::default();
+
+    for (from, to) in edges {
+        in_degree.entry(from.clone()).or_insert(0);
+        *in_degree.entry(to.clone()).or_insert(0) += 1;
+        successors.entry(from).or_default().push(to);
+    }
+
+    let mut queue: VecDeque<N> = in_degree
+        .iter()
+        .filter(|(_, &n)| n == 0)
+        .map(|(node, _)| node.clone())
+        .collect();
+    let mut order = Vec::<N>::with_capacity(in_degree.len());
+
+    while let Some(node) = queue.pop_front() {
+        if let Some(next_nodes) = successors.get(&node) {
+            for next in next_nodes {
+                if let Some(n) = in_degree.get_mut(next) {
+                    *n -= 1;
+                    if *n == 0 {queue.push_back(next.clone());}
+                }
+            }
+        }
+        order.push(node);
+    }
+
+    if order.len() < in_degree.len() {
+        return Err(
+            in_degree
+                .into_iter()
+                .filter(|(_, n)| *n > 0)
+                .map(|(node, _)| node)
+                .collect()
+        );
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_topological_sort() {
+        let order = topological_sort([("c", "d"), ("a", "b"), ("b", "c"), ("a", "c")]).unwrap();
+        assert_eq!(order, vec!["a", "b", "c", "d"]);
+
+        let order = topological_sort([(1, 2), (3, 4)]).unwrap();
+        assert_eq!(order, vec![1, 3, 2, 4]);
+
+        assert!(topological_sort(Vec::<(u8, u8)>::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let remaining = topological_sort([("a", "b"), ("b", "c"), ("c", "b"), ("c", "d")]).unwrap_err();
+        assert_eq!(remaining, vec!["b", "c", "d"]);
+
+        assert_eq!(topological_sort([(1, 1)]).unwrap_err(), vec![1]);
+    }
+}
@@ -0,0 +1,247 @@
+/*                        GRID
+
+A dense two dimensional grid stored in row-major order, with positions given
+as (row, column) as used for the day 3 schematic. Includes the transformations
+needed by reflection and tilting style puzzles.
+
+*/
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    // Horizontal and vertical neighbours only
+    Four,
+    // Horizontal, vertical and diagonal neighbours
+    Eight
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        /* Create a grid of the given size with every cell set to the fill value.
+
+        # Examples
+
+        ```
+        let distances = Grid::new(10, 5, u32::MAX);
+        ```
+        */
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, String> {
+        /* Create a grid from a vector of rows, all of which must be the same length. */
+        let height = rows.len();
+        let width = match rows.first() {
+            Some(r) => r.len(),
+            None => 0
+        };
+
+        if let Some((i, r)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(format!("Row {} has length {}, expected {}", i, r.len(), width));
+        }
+
+        Ok(Grid { width, height, cells: rows.into_iter().flatten().collect() })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        if row >= self.height || column >= self.width {
+            return None;
+        }
+        self.cells.get(row * self.width + column)
+    }
+
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut T> {
+        if row >= self.height || column >= self.width {
+            return None;
+        }
+        self.cells.get_mut(row * self.width + column)
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: T) -> Result<(), String> {
+        match self.get_mut(row, column) {
+            Some(cell) => {
+                *cell = value;
+                Ok(())
+            },
+            None => Err(format!("Position ({}, {}) outside of {}x{} grid", row, column, self.height, self.width))
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, column: usize) -> Vec<T> {
+        (0..self.height).map(|r| self.cells[r * self.width + column].clone()).collect()
+    }
+
+    pub fn neighbours(&self, row: usize, column: usize, connectivity: Connectivity) -> Vec<(usize, usize)> {
+        /* Positions adjacent to the given cell which lie within the grid.
+
+        # Arguments
+
+        * `row` - row of the cell
+        * `column` - column of the cell
+        * `connectivity` - whether diagonal cells count as neighbours
+
+        # Examples
+
+        ```
+        assert_eq!(grid.neighbours(0, 0, Connectivity::Eight).len(), 3);
+        ```
+        */
+        let mut offsets = vec![(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+        if connectivity == Connectivity::Eight {
+            offsets.extend([(-1, -1), (-1, 1), (1, -1), (1, 1)]);
+        }
+
+        offsets
+            .iter()
+            .filter_map(|(dr, dc): &(isize, isize)| {
+                let r = row.checked_add_signed(*dr)?;
+                let c = column.checked_add_signed(*dc)?;
+                if r < self.height && c < self.width {Some((r, c))} else {None}
+            })
+            .collect()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        /* Iterate through all cells in row order along with their positions. */
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, c)| ((i / self.width.max(1), i % self.width.max(1)), c))
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        /* Swap rows and columns, reflecting the grid about its leading diagonal. */
+        let cells = (0..self.width).flat_map(|c| self.column(c)).collect();
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    pub fn rotate90(&self) -> Grid<T> {
+        /* Rotate the grid a quarter turn clockwise.
+
+        The first row of the original grid becomes the last column.
+        */
+        self.transpose().mirror_horizontal()
+    }
+
+    pub fn mirror_horizontal(&self) -> Grid<T> {
+        /* Reflect the grid left to right, reversing the order of each row. */
+        let cells = (0..self.height)
+            .flat_map(|r| self.row(r).iter().rev().cloned().collect::<Vec<T>>())
+            .collect();
+        Grid { width: self.width, height: self.height, cells }
+    }
+
+    pub fn mirror_vertical(&self) -> Grid<T> {
+        /* Reflect the grid top to bottom, reversing the order of the rows. */
+        let cells = (0..self.height)
+            .rev()
+            .flat_map(|r| self.row(r).to_vec())
+            .collect();
+        Grid { width: self.width, height: self.height, cells }
+    }
+}
+
+impl Grid<char> {
+    pub fn from_lines(input: &str) -> Result<Grid<char>, String> {
+        /* Create a character grid from lines of text, ignoring blank lines.
+
+        # Examples
+
+        ```
+        let schematic = Grid::from_lines(&read_to_string("/path/to/file").unwrap()).unwrap();
+        ```
+        */
+        Grid::from_rows(
+            input
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(|l| l.chars().collect())
+                .collect()
+        )
+    }
+
+    pub fn row_string(&self, row: usize) -> String {
+        self.row(row).iter().collect()
+    }
+
+    pub fn column_string(&self, column: usize) -> String {
+        self.column(column).iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        Grid::from_lines("abc\ndef\n").unwrap()
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let grid = example();
+
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid.get(1, 2), Some(&'f'));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.row_string(0), "abc");
+        assert_eq!(grid.column_string(1), "be");
+        assert!(Grid::from_lines("ab\nc").is_err());
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = example();
+
+        let transposed = grid.transpose();
+        assert_eq!((0..3).map(|r| transposed.row_string(r)).collect::<Vec<_>>(), vec!["ad", "be", "cf"]);
+
+        let rotated = grid.rotate90();
+        assert_eq!((0..3).map(|r| rotated.row_string(r)).collect::<Vec<_>>(), vec!["da", "eb", "fc"]);
+        assert_eq!(rotated.rotate90().rotate90().rotate90(), grid);
+
+        assert_eq!(grid.mirror_horizontal().row_string(0), "cba");
+        assert_eq!(grid.mirror_vertical().row_string(0), "def");
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid = Grid::new(2, 2, 0);
+
+        grid.set(1, 0, 5).unwrap();
+        assert!(grid.set(2, 0, 1).is_err());
+        assert_eq!(grid.column(0), vec![0, 5]);
+        assert_eq!(grid.positions().filter(|(_, &v)| v == 5).map(|(p, _)| p).collect::<Vec<_>>(), vec![(1, 0)]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, '.');
+
+        assert_eq!(grid.neighbours(0, 0, Connectivity::Four), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours(0, 0, Connectivity::Eight).len(), 3);
+        assert_eq!(grid.neighbours(1, 1, Connectivity::Eight).len(), 8);
+        assert_eq!(grid.neighbours(2, 2, Connectivity::Four), vec![(1, 2), (2, 1)]);
+    }
+}
@@ -0,0 +1,67 @@
+/*                        HASHING
+
+The Holiday ASCII String Helper algorithm from day 15 along with a fast,
+non-cryptographic hasher for the hash maps and sets used to store states when
+detecting cycles.
+
+The maps and sets are hashbrown's, which std's own are built on, so that they
+are available without the standard library.
+
+*/
+
+pub use rustc_hash::{FxBuildHasher, FxHasher};
+
+pub type FxHashMap<K, V> = hashbrown::HashMap<K, V, FxBuildHasher>;
+pub type FxHashSet<T> = hashbrown::HashSet<T, FxBuildHasher>;
+
+pub fn aoc_hash(input: &str) -> u8 {
+    /* Apply the HASH algorithm to a string.
+
+    For each character the ASCII code is added to the current value, which is then
+    multiplied by 17 and the remainder on division by 256 kept. Newlines are ignored.
+
+    # Examples
+
+    ```
+    assert_eq!(aoc_hash("HASH"), 52);
+    ```
+    */
+    input
+        .bytes()
+        .filter(|&b| b != b'\n' && b != b'\r')
+        .fold(0u8, |current, b| current.wrapping_add(b).wrapping_mul(17))
+}
+
+pub fn aoc_hash_sequence(input: &str) -> u64 {
+    /* Sum of the HASH of each comma separated step within an initialization sequence. */
+    input
+        .split(',')
+        .map(|step| aoc_hash(step.trim()) as u64)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_aoc_hash() {
+        assert_eq!(aoc_hash("HASH"), 52);
+        assert_eq!(aoc_hash("rn=1"), 30);
+        assert_eq!(aoc_hash("qp"), 1);
+        assert_eq!(aoc_hash(""), 0);
+    }
+
+    #[test]
+    fn test_aoc_hash_sequence() {
+        let sequence = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+        assert_eq!(aoc_hash_sequence(sequence), 1320);
+    }
+
+    #[test]
+    fn test_fx_collections() {
+        let mut seen = FxHashMap::<Vec<u8>, usize>::default();
+        seen.insert(b"state".to_vec(), 3);
+        assert_eq!(seen.get(b"state".as_slice()), Some(&3));
+    }
+}
@@ -0,0 +1,109 @@
+/*                        INTERNER
+
+Maps values such as node or category names to dense u32 ids, so that graph
+and map structures can be keyed by small integers or index directly into
+vectors rather than hashing and cloning strings.
+
+*/
+
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+use super::hash::FxHashMap;
+
+#[derive(Clone, Debug)]
+pub struct Interner<T = String> {
+    ids: FxHashMap<T, u32>,
+    values: Vec<T>
+}
+
+impl<T: Clone + Eq + Hash> Interner<T> {
+    pub fn new() -> Interner<T> {
+        Interner { ids: FxHashMap::default(), values: Vec::new() }
+    }
+
+    pub fn intern<Q>(&mut self, value: &Q) -> u32
+    where T: Borrow<Q>, Q: ToOwned<Owned = T> + Eq + Hash + ?Sized {
+        /* Return the id of a value, assigning the next free id if it has not been seen.
+
+        Only values not already interned are copied.
+
+        # Examples
+
+        ```
+        let mut names = Interner::new();
+        assert_eq!(names.intern("AAA"), 0);
+        assert_eq!(names.intern("BBB"), 1);
+        assert_eq!(names.intern("AAA"), 0);
+        ```
+        */
+        if let Some(&id) = self.ids.get(value) {
+            return id;
+        }
+
+        let id = self.values.len() as u32;
+        let owned = value.to_owned();
+        self.values.push(owned.clone());
+        self.ids.insert(owned, id);
+        id
+    }
+
+    pub fn get<Q>(&self, value: &Q) -> Option<u32>
+    where T: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        self.ids.get(value).copied()
+    }
+
+    pub fn resolve(&self, id: u32) -> Option<&T> {
+        self.values.get(id as usize)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &T)> {
+        self.values.iter().enumerate().map(|(i, v)| (i as u32, v))
+    }
+}
+
+impl<T: Clone + Eq + Hash> Default for Interner<T> {
+    fn default() -> Self {
+        Interner::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intern_names() {
+        let mut names = Interner::<String>::new();
+
+        assert!(names.is_empty());
+        assert_eq!(names.intern("AAA"), 0);
+        assert_eq!(names.intern("BBB"), 1);
+        assert_eq!(names.intern("AAA"), 0);
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.get("BBB"), Some(1));
+        assert_eq!(names.get("ZZZ"), None);
+        assert_eq!(names.resolve(1).map(|s| s.as_str()), Some("BBB"));
+        assert_eq!(names.resolve(2), None);
+        assert_eq!(names.iter().map(|(id, _)| id).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_intern_values() {
+        let mut positions = Interner::<(i64, i64)>::new();
+
+        assert_eq!(positions.intern(&(3, 4)), 0);
+        assert_eq!(positions.intern(&(-1, 0)), 1);
+        assert_eq!(positions.intern(&(3, 4)), 0);
+        assert_eq!(positions.resolve(1), Some(&(-1, 0)));
+    }
+}
@@ -0,0 +1,537 @@
+/*                        INPUT READING
+
+Line by line reading of input files or any other buffered reader. Failures
+to open or read a file are reported with the path and, for read errors, the
+line number at which the failure occurred, giving the same context across all
+days.
+
+An `InputSource` instead holds a whole input, so that parsers can borrow its
+lines and fields as `&str` rather than allocating a `String` per line. With
+the `mmap` feature a large input can be memory mapped rather than read.
+
+With the `archive` feature an input can also be read from within a zip or tar
+archive, given as the path of the archive and the name of the member joined
+by '!/', e.g. 'inputs.zip!/day_7.txt', so that the inputs of a whole year can
+be passed around as one file without unpacking it.
+
+With the `gzip` feature an input starting with the gzip magic bytes, whether a
+file or a member of an archive, is decompressed as it is read, so that large
+generated inputs can be kept compressed. A compressed file is read into memory
+even when asked to be memory mapped.
+
+With the `encrypt` feature an input encrypted by src/crypt.rs is decrypted as
+it is read, with the key set for the process, and then decompressed if need
+be. It too is read into memory rather than mapped.
+
+*/
+
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+// A failure to open a file, or to read one of its lines
+#[derive(Debug)]
+pub struct ReadError {
+    // Empty when reading from something other than a file
+    pub path: PathBuf,
+    // The one-based line being read, None if the file could not be opened
+    pub line: Option<usize>,
+    pub source: io::Error
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(n) if self.path.as_os_str().is_empty() => write!(f, "Bad input line {}: {}", n, self.source),
+            Some(n) => write!(f, "Bad file line {} of '{}': {}", n, self.path.display(), self.source),
+            None => write!(f, "Failed to open file '{}': {}", self.path.display(), self.source)
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+pub struct Lines<R = BufReader<File>> {
+    path: PathBuf,
+    inner: io::Lines<R>,
+    line_number: usize
+}
+
+impl<R> Lines<R> {
+    pub fn line_number(&self) -> usize {
+        /* The one-based number of the line most recently read, zero before the first. */
+        self.line_number
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<String, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.inner.next()?;
+        self.line_number += 1;
+
+        Some(match line {
+            Ok(l) => Ok(l),
+            Err(e) => Err(ReadError { path: self.path.clone(), line: Some(self.line_number), source: e })
+        })
+    }
+}
+
+pub fn lines(source: impl AsRef<Path>) -> Result<Lines, ReadError> {
+    /* Open a file for reading line by line.
+
+    # Examples
+
+    ```
+    for line in lines("/path/to/file")? {
+        let file_line = line?;
+    }
+    ```
+    */
+    let path = source.as_ref();
+
+    let in_file = match File::open(path) {
+        Ok(o) => o,
+        Err(e) => return Err(ReadError { path: path.to_path_buf(), line: None, source: e })
+    };
+
+    Ok(Lines { path: path.to_path_buf(), inner: BufReader::new(in_file).lines(), line_number: 0 })
+}
+
+pub fn read_lines<R: BufRead>(reader: R) -> Lines<R> {
+    /* Read line by line from any buffered reader, such as a string's bytes.
+
+    # Examples
+
+    ```
+    for line in read_lines("first\nsecond".as_bytes()) {
+        let input_line = line?;
+    }
+    ```
+    */
+    Lines { path: PathBuf::new(), inner: reader.lines(), line_number: 0 }
+}
+
+#[cfg(feature = "gzip")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[cfg(feature = "archive")]
+pub const ARCHIVE_SEPARATOR: &str = "!/";
+
+// A whole input, held in memory, mapped from its file or unpacked from an archive
+pub enum InputSource {
+    Text { path: PathBuf, text: String },
+    #[cfg(feature = "mmap")]
+    Mmap { path: PathBuf, map: memmap2::Mmap },
+    // The path is that of the archive and member joined by ARCHIVE_SEPARATOR
+    #[cfg(feature = "archive")]
+    Member { path: PathBuf, archive: PathBuf, text: String }
+}
+
+impl InputSource {
+    pub fn read(source: impl AsRef<Path>) -> Result<InputSource, ReadError> {
+        /* Read a whole file into memory, or with the archive feature a member of an archive. */
+        let path = source.as_ref();
+        #[cfg(feature = "archive")]
+        if let Some((archive, member)) = split_archive_path(path) {
+            return InputSource::from_archive(archive, &member);
+        }
+
+        match std::fs::read(path).and_then(decode) {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(text) => Ok(InputSource::Text { path: path.to_path_buf(), text }),
+                Err(e) => Err(utf8_error(path, e.as_bytes(), e.utf8_error()))
+            },
+            Err(e) => Err(ReadError { path: path.to_path_buf(), line: None, source: e })
+        }
+    }
+
+    #[cfg(feature = "mmap")]
+    pub fn mmap(source: impl AsRef<Path>) -> Result<InputSource, ReadError> {
+        /* Map a file into memory, so that it is paged in as it is parsed rather than copied.
+
+        # Examples
+
+        ```
+        let input = InputSource::mmap("/path/to/file")?;
+        for line in input.lines()? {
+            let input_line: &str = line;
+        }
+        ```
+        */
+        let path = source.as_ref();
+        let file = match File::open(path) {
+            Ok(f) => f,
+            Err(e) => return Err(ReadError { path: path.to_path_buf(), line: None, source: e })
+        };
+
+        // Safety: the map is only read, and inputs are not expected to be changed while being solved
+        match unsafe { memmap2::Mmap::map(&file) } {
+            // A compressed or encrypted input cannot be borrowed from the map, so it is read as usual
+            #[cfg(feature = "gzip")]
+            Ok(map) if map.starts_with(&GZIP_MAGIC) => InputSource::read(path),
+            #[cfg(feature = "encrypt")]
+            Ok(map) if crate::crypt::is_encrypted(&map) => InputSource::read(path),
+            Ok(map) => Ok(InputSource::Mmap { path: path.to_path_buf(), map }),
+            Err(e) => Err(ReadError { path: path.to_path_buf(), line: None, source: e })
+        }
+    }
+
+    #[cfg(feature = "archive")]
+    pub fn from_archive(archive: impl AsRef<Path>, member: &str) -> Result<InputSource, ReadError> {
+        /* Read a member of a zip archive, or of a tar archive if its name ends in '.tar'.
+
+        # Examples
+
+        ```
+        let input = InputSource::from_archive("inputs.zip", "day_7.txt")?;
+        ```
+        */
+        let archive = archive.as_ref();
+        let path = PathBuf::from(format!("{}{}{}", archive.display(), ARCHIVE_SEPARATOR, member));
+
+        let file = match File::open(archive) {
+            Ok(f) => f,
+            Err(e) => return Err(ReadError { path: archive.to_path_buf(), line: None, source: e })
+        };
+        let unpacked = if is_tar(archive) {read_tar_member(file, member)} else {read_zip_member(file, member)};
+        match unpacked.and_then(decode) {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(text) => Ok(InputSource::Member { path, archive: archive.to_path_buf(), text }),
+                Err(e) => Err(utf8_error(&path, e.as_bytes(), e.utf8_error()))
+            },
+            Err(e) => Err(ReadError { path, line: None, source: e })
+        }
+    }
+
+    pub fn from_string(text: String) -> InputSource {
+        /* An input already held in memory, with no file behind it. */
+        InputSource::Text { path: PathBuf::new(), text }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            InputSource::Text { path, .. } => path,
+            #[cfg(feature = "mmap")]
+            InputSource::Mmap { path, .. } => path,
+            #[cfg(feature = "archive")]
+            InputSource::Member { path, .. } => path
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            InputSource::Text { text, .. } => text.as_bytes(),
+            #[cfg(feature = "mmap")]
+            InputSource::Mmap { map, .. } => map,
+            #[cfg(feature = "archive")]
+            InputSource::Member { text, .. } => text.as_bytes()
+        }
+    }
+
+    pub fn as_str(&self) -> Result<&str, ReadError> {
+        /* The input as text, failing with the line of the first invalid UTF-8 if it is not. */
+        match self {
+            InputSource::Text { text, .. } => Ok(text),
+            #[cfg(feature = "mmap")]
+            InputSource::Mmap { path, map } => match std::str::from_utf8(map) {
+                Ok(text) => Ok(text),
+                Err(e) => Err(utf8_error(path, map, e))
+            },
+            #[cfg(feature = "archive")]
+            InputSource::Member { text, .. } => Ok(text)
+        }
+    }
+
+    pub fn into_string(self) -> Result<String, ReadError> {
+        /* The input as owned text, only copied if it was memory mapped. */
+        match self {
+            InputSource::Text { text, .. } => Ok(text),
+            #[cfg(feature = "mmap")]
+            InputSource::Mmap { .. } => Ok(self.as_str()?.to_string()),
+            #[cfg(feature = "archive")]
+            InputSource::Member { text, .. } => Ok(text)
+        }
+    }
+
+    pub fn lines(&self) -> Result<std::str::Lines<'_>, ReadError> {
+        /* The lines of the input, borrowed from it without their line endings. */
+        Ok(self.as_str()?.lines())
+    }
+}
+
+#[cfg(feature = "archive")]
+pub fn split_archive_path(path: &Path) -> Option<(PathBuf, String)> {
+    /* Split a path such as 'inputs.zip!/day_7.txt' into the archive and the name of the member. */
+    let (archive, member) = path.to_str()?.split_once(ARCHIVE_SEPARATOR)?;
+    let extension = Path::new(archive).extension()?.to_str()?.to_ascii_lowercase();
+
+    match extension.as_str() {
+        "zip" | "tar" => Some((PathBuf::from(archive), member.to_string())),
+        _ => None
+    }
+}
+
+#[cfg(feature = "archive")]
+fn is_tar(archive: &Path) -> bool {
+    archive.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("tar"))
+}
+
+#[cfg(feature = "archive")]
+fn read_zip_member(file: File, member: &str) -> io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut entry = archive.by_name(member)?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(feature = "archive")]
+fn read_tar_member(file: File, member: &str) -> io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut archive = tar::Archive::new(file);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.path()? == Path::new(member) {
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes)?;
+            return Ok(bytes);
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, format!("No member '{}' in the archive", member)))
+}
+
+fn decode(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    /* The text of an input as stored, decrypted and then decompressed with the features enabled. */
+    #[cfg(feature = "encrypt")]
+    let bytes = crate::crypt::decrypt_with_input_key(bytes)?;
+    decompress(bytes)
+}
+
+#[cfg(feature = "gzip")]
+fn decompress(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    /* Inflate bytes starting with the gzip magic bytes, passing any others through unchanged. */
+    use std::io::Read;
+
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(bytes);
+    }
+    let mut inflated = Vec::new();
+    flate2::read::MultiGzDecoder::new(bytes.as_slice()).read_to_end(&mut inflated)?;
+    Ok(inflated)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn decompress(bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    Ok(bytes)
+}
+
+fn utf8_error(path: &Path, bytes: &[u8], error: std::str::Utf8Error) -> ReadError {
+    let line = bytes[..error.valid_up_to()].iter().filter(|&&b| b == b'\n').count() + 1;
+    ReadError { path: path.to_path_buf(), line: Some(line), source: io::Error::new(io::ErrorKind::InvalidData, error) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+
+        let mut reader = lines(&test_file).unwrap();
+        assert_eq!(reader.line_number(), 0);
+        assert!(reader.next().unwrap().unwrap().starts_with("Card 1:"));
+        assert_eq!(reader.line_number(), 1);
+        assert_eq!(reader.count(), 5);
+    }
+
+    #[test]
+    fn test_line_errors() {
+        let missing = lines("/no/such/file.dat").err().unwrap();
+        assert!(missing.to_string().starts_with("Failed to open file '/no/such/file.dat'"));
+        assert_eq!(missing.line, None);
+        assert_eq!(missing.source.kind(), io::ErrorKind::NotFound);
+
+        // Invalid UTF-8 on the second line
+        let bad_file = std::env::temp_dir().join(format!("aoc23_io_test_{}.dat", std::process::id()));
+        std::fs::write(&bad_file, b"fine\n\xff\xfe\n").unwrap();
+
+        let results: Vec<Result<String, ReadError>> = lines(&bad_file).unwrap().collect();
+        std::fs::remove_file(&bad_file).unwrap();
+
+        assert_eq!(results[0].as_ref().unwrap(), "fine");
+        assert_eq!(results[1].as_ref().unwrap_err().line, Some(2));
+        assert!(results[1].as_ref().unwrap_err().to_string().starts_with("Bad file line 2 of"));
+    }
+
+    #[test]
+    fn test_read_lines() {
+        let mut reader = read_lines("Card 1: 41 48\nCard 2: 13 32\n".as_bytes());
+        assert_eq!(reader.next().unwrap().unwrap(), "Card 1: 41 48");
+        assert_eq!(reader.line_number(), 1);
+        assert_eq!(reader.count(), 1);
+
+        let results: Vec<Result<String, ReadError>> = read_lines(&b"fine\n\xff\n"[..]).collect();
+        assert_eq!(results[0].as_ref().unwrap(), "fine");
+        assert_eq!(results[1].as_ref().unwrap_err().to_string().split(':').next().unwrap(), "Bad input line 2");
+    }
+
+    #[test]
+    fn test_input_source() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+
+        let input = InputSource::read(&test_file).unwrap();
+        assert_eq!(input.path(), test_file.as_path());
+        assert_eq!(input.lines().unwrap().count(), 6);
+        assert!(input.as_str().unwrap().starts_with("Card 1:"));
+
+        let text = InputSource::from_string("first\r\nsecond\n".to_string());
+        assert_eq!(text.lines().unwrap().collect::<Vec<&str>>(), vec!["first", "second"]);
+        assert_eq!(text.as_bytes().len(), 14);
+        assert_eq!(text.into_string().unwrap(), "first\r\nsecond\n");
+
+        let bad_file = std::env::temp_dir().join(format!("aoc23_source_test_{}.dat", std::process::id()));
+        std::fs::write(&bad_file, b"fine\nfine\n\xff\n").unwrap();
+        let bad = InputSource::read(&bad_file).err().unwrap();
+        std::fs::remove_file(&bad_file).unwrap();
+
+        assert_eq!(bad.line, Some(3));
+        assert!(bad.to_string().starts_with("Bad file line 3 of"));
+        assert_eq!(InputSource::read("/no/such/file.dat").err().unwrap().line, None);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mmap_input_source() {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+
+        let mapped = InputSource::mmap(&test_file).unwrap();
+        assert_eq!(mapped.as_str().unwrap(), InputSource::read(&test_file).unwrap().as_str().unwrap());
+
+        let temp_file = std::env::temp_dir().join(format!("aoc23_mmap_test_{}.dat", std::process::id()));
+        std::fs::write(&temp_file, b"").unwrap();
+        let empty = InputSource::mmap(&temp_file).unwrap();
+        let empty_lines = empty.lines().unwrap().count();
+
+        std::fs::write(&temp_file, b"fine\n\xff\n").unwrap();
+        let bad = InputSource::mmap(&temp_file).unwrap();
+        let bad_line = bad.as_str().err().unwrap().line;
+        drop(bad);
+        std::fs::remove_file(&temp_file).unwrap();
+
+        assert_eq!(empty_lines, 0);
+        assert_eq!(bad_line, Some(2));
+        assert!(InputSource::mmap("/no/such/file.dat").is_err());
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn test_archive_input_source() {
+        use std::io::Write;
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+        let contents = std::fs::read(&test_file).unwrap();
+
+        let zip_file = std::env::temp_dir().join(format!("aoc23_archive_test_{}.zip", std::process::id()));
+        let mut zip_writer = zip::ZipWriter::new(File::create(&zip_file).unwrap());
+        zip_writer.start_file("2023/day_4.dat", zip::write::SimpleFileOptions::default()).unwrap();
+        zip_writer.write_all(&contents).unwrap();
+        zip_writer.finish().unwrap();
+
+        let tar_file = zip_file.with_extension("tar");
+        let mut tar_builder = tar::Builder::new(File::create(&tar_file).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar_builder.append_data(&mut header, "2023/day_4.dat", contents.as_slice()).unwrap();
+        tar_builder.into_inner().unwrap();
+
+        let zipped = InputSource::read(format!("{}!/2023/day_4.dat", zip_file.display()));
+        let tarred = InputSource::read(format!("{}!/2023/day_4.dat", tar_file.display()));
+        let missing = InputSource::read(format!("{}!/2023/day_5.dat", zip_file.display()));
+        let missing_from_tar = InputSource::from_archive(&tar_file, "2023/day_5.dat");
+        std::fs::remove_file(&zip_file).unwrap();
+        std::fs::remove_file(&tar_file).unwrap();
+
+        let zipped = zipped.unwrap();
+        assert_eq!(zipped.as_bytes(), contents.as_slice());
+        assert!(zipped.path().ends_with("day_4.dat"));
+        assert_eq!(tarred.unwrap().as_bytes(), contents.as_slice());
+        assert_eq!(missing.err().unwrap().source.kind(), io::ErrorKind::NotFound);
+        assert_eq!(missing_from_tar.err().unwrap().source.kind(), io::ErrorKind::NotFound);
+
+        assert_eq!(split_archive_path(Path::new("inputs.zip!/day_7.txt")), Some((PathBuf::from("inputs.zip"), "day_7.txt".to_string())));
+        assert_eq!(split_archive_path(Path::new("day_7.txt")), None);
+        assert_eq!(split_archive_path(Path::new("notes!/day_7.txt")), None);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_input_source() {
+        use std::io::Write;
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+        let contents = std::fs::read_to_string(&test_file).unwrap();
+
+        let gzip_file = std::env::temp_dir().join(format!("aoc23_gzip_test_{}.dat", std::process::id()));
+        let mut encoder = flate2::write::GzEncoder::new(File::create(&gzip_file).unwrap(), flate2::Compression::default());
+        encoder.write_all(contents.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let inflated = InputSource::read(&gzip_file).unwrap();
+        #[cfg(feature = "mmap")]
+        let mapped = InputSource::mmap(&gzip_file).unwrap();
+
+        std::fs::write(&gzip_file, [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+        let truncated = InputSource::read(&gzip_file);
+        std::fs::remove_file(&gzip_file).unwrap();
+
+        assert_eq!(inflated.as_str().unwrap(), contents);
+        assert_eq!(inflated.path(), gzip_file.as_path());
+        #[cfg(feature = "mmap")]
+        assert_eq!(mapped.as_str().unwrap(), contents);
+        assert!(truncated.is_err());
+        assert_eq!(decompress(b"Card 1".to_vec()).unwrap(), b"Card 1");
+    }
+
+    #[cfg(feature = "encrypt")]
+    #[test]
+    fn test_encrypted_input_source() {
+        use crate::crypt::{encrypt, InputKey};
+
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_4.dat");
+        let contents = std::fs::read_to_string(&test_file).unwrap();
+
+        // The process wide key is not set, so that other tests are unaffected, leaving the input unreadable
+        let key = InputKey::generate();
+        let encrypted_file = std::env::temp_dir().join(format!("aoc23_encrypted_test_{}.dat", std::process::id()));
+        std::fs::write(&encrypted_file, encrypt(&key, contents.as_bytes())).unwrap();
+
+        let unkeyed = InputSource::read(&encrypted_file);
+        #[cfg(feature = "mmap")]
+        let unkeyed_map = InputSource::mmap(&encrypted_file);
+        std::fs::remove_file(&encrypted_file).unwrap();
+
+        assert_eq!(unkeyed.err().unwrap().source.kind(), io::ErrorKind::InvalidData);
+        #[cfg(feature = "mmap")]
+        assert!(unkeyed_map.is_err());
+    }
+}
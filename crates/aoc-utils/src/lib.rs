@@ -0,0 +1,52 @@
+/*                        SHARED UTILITIES
+
+Reusable building blocks for parsing inputs and solving puzzles which are not
+specific to any one day or year. The aoc23 crate re-exports these as
+`aoc23::util`.
+
+Everything but reading and encrypting input files needs only core and alloc,
+so that the solvers built on these can run without the standard library. The
+std feature, on by default, adds the io and crypt modules.
+
+*/
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod bitgrid;
+pub mod blocks;
+pub mod combi;
+#[cfg(feature = "encrypt")]
+pub mod crypt;
+pub mod fill;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+pub mod intern;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod linalg;
+pub mod lru;
+pub mod math;
+pub mod pqueue;
+pub mod prefix;
+pub mod rational;
+pub mod scan;
+pub mod search;
+pub mod sparse;
+pub mod walker;
+
+pub use crate::bitgrid::BitGrid;
+pub use crate::blocks::blocks;
+pub use crate::fill::flood_fill;
+pub use crate::graph::topological_sort;
+pub use crate::grid::{Connectivity, Grid};
+pub use crate::intern::Interner;
+pub use crate::lru::LruCache;
+pub use crate::pqueue::IndexedPriorityQueue;
+pub use crate::rational::Rational;
+pub use crate::search::{bfs, dijkstra};
+pub use crate::sparse::SparseGrid;
+pub use crate::walker::{Direction, Walker};
@@ -0,0 +1,210 @@
+/*                        LINEAR ALGEBRA
+
+A small dense matrix of exact rationals with Gaussian elimination, for solving
+the systems of linear equations arising from intersecting trajectories or
+fitting polynomials through sampled points.
+
+*/
+
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+
+use super::rational::Rational;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    columns: usize,
+    values: Vec<Rational>
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, columns: usize) -> Matrix {
+        Matrix { rows, columns, values: vec![Rational::ZERO; rows * columns] }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<Rational>>) -> Result<Matrix, String> {
+        /* Create a matrix from a vector of rows, all of which must be the same length.
+
+        # Examples
+
+        ```
+        let m = Matrix::from_rows(vec![vec![Rational::ONE, Rational::ZERO], vec![Rational::ZERO, Rational::ONE]]).unwrap();
+        ```
+        */
+        let n_rows = rows.len();
+        let n_columns = match rows.first() {
+            Some(r) => r.len(),
+            None => 0
+        };
+
+        if let Some((i, r)) = rows.iter().enumerate().find(|(_, r)| r.len() != n_columns) {
+            return Err(format!("Matrix row {} has {} columns, expected {}", i, r.len(), n_columns));
+        }
+
+        Ok(Matrix { rows: n_rows, columns: n_columns, values: rows.into_iter().flatten().collect() })
+    }
+
+    pub fn from_integer_rows(rows: Vec<Vec<i64>>) -> Result<Matrix, String> {
+        Matrix::from_rows(
+            rows.into_iter()
+                .map(|r| r.into_iter().map(Rational::from).collect())
+                .collect()
+        )
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Rational {
+        self.values[row * self.columns + column]
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: Rational) {
+        self.values[row * self.columns + column] = value;
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for column in 0..self.columns {
+            self.values.swap(a * self.columns + column, b * self.columns + column);
+        }
+    }
+
+    pub fn reduced_row_echelon(&self) -> (Matrix, usize) {
+        /* Reduce the matrix to reduced row echelon form using Gauss-Jordan elimination.
+
+        # Returns
+
+        The reduced matrix and the rank of the original matrix.
+        */
+        let mut reduced = self.clone();
+        let mut pivot_row = 0;
+
+        for column in 0..self.columns {
+            if pivot_row == self.rows {
+                break;
+            }
+
+            let pivot = match (pivot_row..self.rows).find(|&r| !reduced.get(r, column).is_zero()) {
+                Some(p) => p,
+                None => continue
+            };
+            reduced.swap_rows(pivot_row, pivot);
+
+            let scale = reduced.get(pivot_row, column).recip();
+            for c in column..self.columns {
+                reduced.set(pivot_row, c, reduced.get(pivot_row, c) * scale);
+            }
+
+            for r in 0..self.rows {
+                let factor = reduced.get(r, column);
+                if r == pivot_row || factor.is_zero() {
+                    continue;
+                }
+                for c in column..self.columns {
+                    reduced.set(r, c, reduced.get(r, c) - factor * reduced.get(pivot_row, c));
+                }
+            }
+
+            pivot_row += 1;
+        }
+
+        (reduced, pivot_row)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.reduced_row_echelon().1
+    }
+}
+
+pub fn solve(coefficients: &Matrix, constants: &[Rational]) -> Result<Vec<Rational>, String> {
+    /* Solve the linear system Ax = b exactly.
+
+    # Arguments
+
+    * `coefficients` - the matrix A
+    * `constants` - the vector b, one entry per row of A
+
+    # Returns
+
+    The unique solution x, or an error if the system has no solution or infinitely many.
+
+    # Examples
+
+    ```
+    let a = Matrix::from_integer_rows(vec![vec![2, 1], vec![1, 3]]).unwrap();
+    let x = solve(&a, &[Rational::from(3i64), Rational::from(5i64)]).unwrap();
+    assert_eq!(x, vec![Rational::new(4, 5), Rational::new(7, 5)]);
+    ```
+    */
+    if constants.len() != coefficients.rows() {
+        return Err(format!(
+            "Expected {} constants for {}x{} system, got {}",
+            coefficients.rows(), coefficients.rows(), coefficients.columns(), constants.len()
+        ));
+    }
+
+    let mut augmented = Matrix::zeros(coefficients.rows(), coefficients.columns() + 1);
+    for (row, constant) in constants.iter().enumerate() {
+        for column in 0..coefficients.columns() {
+            augmented.set(row, column, coefficients.get(row, column));
+        }
+        augmented.set(row, coefficients.columns(), *constant);
+    }
+
+    let (reduced, augmented_rank) = augmented.reduced_row_echelon();
+    let rank = coefficients.rank();
+
+    if rank < augmented_rank {
+        return Err("Linear system is inconsistent and has no solution".to_string());
+    }
+    if rank < coefficients.columns() {
+        return Err(format!(
+            "Linear system is underdetermined, rank {} with {} unknowns", rank, coefficients.columns()
+        ));
+    }
+
+    Ok((0..coefficients.columns()).map(|r| reduced.get(r, coefficients.columns())).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn integers(values: &[i64]) -> Vec<Rational> {
+        values.iter().map(|&v| Rational::from(v)).collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        let a = Matrix::from_integer_rows(vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]]).unwrap();
+        let x = solve(&a, &integers(&[8, -11, -3])).unwrap();
+
+        assert_eq!(x, integers(&[2, 3, -1]));
+    }
+
+    #[test]
+    fn test_polynomial_fit() {
+        // Fit a*n^2 + b*n + c through (0, 3), (1, 6), (2, 13) and extrapolate
+        let a = Matrix::from_integer_rows(vec![vec![0, 0, 1], vec![1, 1, 1], vec![4, 2, 1]]).unwrap();
+        let x = solve(&a, &integers(&[3, 6, 13])).unwrap();
+
+        assert_eq!(x, integers(&[2, 1, 3]));
+        assert_eq!(x[0] * Rational::from(100i64) + x[1] * Rational::from(10i64) + x[2], Rational::from(213i64));
+    }
+
+    #[test]
+    fn test_singular_systems() {
+        let a = Matrix::from_integer_rows(vec![vec![1, 2], vec![2, 4]]).unwrap();
+
+        assert_eq!(a.rank(), 1);
+        assert!(solve(&a, &integers(&[3, 6])).unwrap_err().contains("underdetermined"));
+        assert!(solve(&a, &integers(&[3, 7])).unwrap_err().contains("inconsistent"));
+        assert!(solve(&a, &integers(&[3])).is_err());
+        assert!(Matrix::from_integer_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+}
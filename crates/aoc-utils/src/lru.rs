@@ -0,0 +1,208 @@
+/*                        LRU CACHE
+
+A fixed capacity map which evicts the least recently used entry when full,
+for memoizing recursive solvers whose tables could otherwise grow without
+bound. Entries are held in a doubly linked list threaded through a vector so
+that lookups, insertions and evictions are all constant time.
+
+*/
+
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use super::hash::FxHashMap;
+
+const NONE: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    prev: usize,
+    next: usize
+}
+
+#[derive(Clone, Debug)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: Vec<Entry<K, V>>,
+    indices: FxHashMap<K, usize>,
+    // Most recently used entry
+    head: usize,
+    // Least recently used entry, the next to be evicted
+    tail: usize
+}
+
+impl<K: Clone + Eq + Hash, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        /* Create an empty cache holding at most `capacity` entries.
+
+        A capacity of zero is raised to one, so that a capacity read from the user's
+        settings cannot leave the cache unable to hold the value just inserted.
+        */
+        LruCache { capacity: capacity.max(1), entries: Vec::new(), indices: FxHashMap::default(), head: NONE, tail: NONE }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    fn unlink(&mut self, i: usize) {
+        let (prev, next) = (self.entries[i].prev, self.entries[i].next);
+
+        if prev == NONE { self.head = next; } else { self.entries[prev].next = next; }
+        if next == NONE { self.tail = prev; } else { self.entries[next].prev = prev; }
+    }
+
+    fn push_front(&mut self, i: usize) {
+        self.entries[i].prev = NONE;
+        self.entries[i].next = self.head;
+
+        if self.head != NONE {
+            self.entries[self.head].prev = i;
+        }
+        self.head = i;
+
+        if self.tail == NONE {
+            self.tail = i;
+        }
+    }
+
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        /* Look up a value without marking it as recently used. */
+        self.indices.get(key).map(|&i| &self.entries[i].value)
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        /* Look up a value, marking it as the most recently used. */
+        let i = *self.indices.get(key)?;
+        self.unlink(i);
+        self.push_front(i);
+        Some(&self.entries[i].value)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        /* Insert or replace a value, returning the entry evicted to make room if any.
+
+        # Examples
+
+        ```
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.get(&"a");
+        assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+        ```
+        */
+        if let Some(&i) = self.indices.get(&key) {
+            self.entries[i].value = value;
+            self.unlink(i);
+            self.push_front(i);
+            return None;
+        }
+
+        if self.entries.len() < self.capacity {
+            let i = self.entries.len();
+            self.entries.push(Entry { key: key.clone(), value, prev: NONE, next: NONE });
+            self.indices.insert(key, i);
+            self.push_front(i);
+            return None;
+        }
+
+        // Reuse the slot of the least recently used entry
+        let i = self.tail;
+        self.unlink(i);
+        let old_key = core::mem::replace(&mut self.entries[i].key, key.clone());
+        let old_value = core::mem::replace(&mut self.entries[i].value, value);
+        self.indices.remove(&old_key);
+        self.indices.insert(key, i);
+        self.push_front(i);
+
+        Some((old_key, old_value))
+    }
+
+    pub fn get_or_insert_with<F>(&mut self, key: K, compute: F) -> &V
+    where F: FnOnce() -> V {
+        /* Return the cached value for a key, computing and caching it if absent. */
+        if !self.indices.contains_key(&key) {
+            let value = compute();
+            self.insert(key.clone(), value);
+        }
+        self.get(&key).unwrap()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.indices.clear();
+        self.head = NONE;
+        self.tail = NONE;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eviction_order() {
+        let mut cache = LruCache::new(2);
+
+        assert_eq!(cache.insert("a", 1), None);
+        assert_eq!(cache.insert("b", 2), None);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+        assert!(!cache.contains(&"b"));
+
+        // Peeking does not refresh "a", so it is evicted next
+        assert_eq!(cache.peek(&"a"), Some(&1));
+        assert_eq!(cache.insert("d", 4), Some(("a", 1)));
+        assert_eq!(cache.len(), 2);
+
+        // Replacing a value refreshes it without evicting
+        assert_eq!(cache.insert("c", 30), None);
+        assert_eq!(cache.insert("e", 5), Some(("d", 4)));
+        assert_eq!(cache.peek(&"c"), Some(&30));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.insert("f", 6), None);
+    }
+
+    #[test]
+    fn test_bounded_memoization() {
+        fn arrangements(n: u64, cache: &mut LruCache<u64, u64>) -> u64 {
+            if n < 2 {
+                return 1;
+            }
+            if let Some(&count) = cache.get(&n) {
+                return count;
+            }
+            let count = arrangements(n - 1, cache) + arrangements(n - 2, cache);
+            cache.insert(n, count);
+            count
+        }
+
+        let mut cache = LruCache::new(4);
+        assert_eq!(arrangements(80, &mut cache), 37889062373143906);
+        assert_eq!(cache.len(), 4);
+        assert_eq!(*cache.get_or_insert_with(1000, || 7), 7);
+        assert_eq!(*cache.get_or_insert_with(1000, || 8), 7);
+
+        let mut smallest = LruCache::new(0);
+        assert_eq!(smallest.capacity(), 1);
+        assert_eq!(*smallest.get_or_insert_with(1, || 2), 2);
+        assert_eq!(smallest.insert(3, 4), Some((1, 2)));
+    }
+}
@@ -0,0 +1,154 @@
+/*                        NUMBER THEORY
+
+Integer helpers for puzzles combining cycles of different lengths, where the
+answer is found using the least common multiple or Chinese Remainder Theorem.
+All functions work over i128 so intermediate products do not overflow for
+moduli up to 2^63.
+
+*/
+
+pub fn gcd(a: i128, b: i128) -> i128 {
+    /* Greatest common divisor, always non-negative. */
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub fn lcm(a: i128, b: i128) -> i128 {
+    /* Least common multiple, always non-negative. */
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    /* Find (g, x, y) such that a*x + b*y = g where g is the greatest common divisor. */
+    if b == 0 {
+        return if a < 0 {(-a, -1, 0)} else {(a, 1, 0)};
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    (g, y, x - (a / b) * y)
+}
+
+pub fn mod_pow(base: i128, exponent: u64, modulus: i128) -> i128 {
+    /* Compute base^exponent mod modulus by repeated squaring.
+
+    # Examples
+
+    ```
+    assert_eq!(mod_pow(4, 13, 497), 445);
+    ```
+    */
+    if modulus == 1 {
+        return 0;
+    }
+    let mut result: i128 = 1;
+    let mut base = base.rem_euclid(modulus);
+    let mut exponent = exponent;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result
+}
+
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    /* The value x in [0, modulus) with a*x = 1 mod modulus, if a and modulus are coprime. */
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(modulus))
+}
+
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    /* Solve a system of congruences x = r_i mod m_i using the Chinese Remainder Theorem.
+
+    The moduli need not be coprime, in which case the system may have no solution.
+
+    # Arguments
+
+    * `congruences` - (residue, modulus) pairs
+
+    # Returns
+
+    The smallest non-negative solution and the modulus (the lcm of all moduli) under
+    which it repeats, or None if the congruences are inconsistent.
+
+    # Examples
+
+    ```
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    ```
+    */
+    let mut solution: i128 = 0;
+    let mut combined_modulus: i128 = 1;
+
+    for &(residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+        let residue = residue.rem_euclid(modulus);
+        let (g, p, _) = extended_gcd(combined_modulus, modulus);
+
+        if (residue - solution) % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = ((residue - solution) / g % step * (p % step)).rem_euclid(step);
+
+        solution += combined_modulus * k;
+        combined_modulus *= step;
+        solution = solution.rem_euclid(combined_modulus);
+    }
+
+    Some((solution, combined_modulus))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(48, -18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_mod_pow_inverse() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+
+        let big = 1_000_000_007;
+        let (x, m) = crt(&[(5, big), (7, big + 2)]).unwrap();
+        assert_eq!(m, big * (big + 2));
+        assert_eq!((x % big, x % (big + 2)), (5, 7));
+    }
+}
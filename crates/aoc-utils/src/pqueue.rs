@@ -0,0 +1,193 @@
+/*                        INDEXED PRIORITY QUEUE
+
+A binary min-heap keyed by item which tracks the position of every item so
+that its priority can be changed in place. Searches such as Dijkstra's can
+lower the cost of a queued state rather than pushing a duplicate entry.
+
+*/
+
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use super::hash::FxHashMap;
+
+pub struct IndexedPriorityQueue<K, P> {
+    heap: Vec<(K, P)>,
+    positions: FxHashMap<K, usize>
+}
+
+impl<K: Clone + Eq + Hash, P: Copy + Ord> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        IndexedPriorityQueue::new()
+    }
+}
+
+impl<K: Clone + Eq + Hash, P: Copy + Ord> IndexedPriorityQueue<K, P> {
+    pub fn new() -> IndexedPriorityQueue<K, P> {
+        IndexedPriorityQueue { heap: Vec::new(), positions: FxHashMap::default() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    pub fn priority(&self, key: &K) -> Option<P> {
+        self.positions.get(key).map(|&i| self.heap[i].1)
+    }
+
+    pub fn push(&mut self, key: K, priority: P) {
+        /* Add an item, or change its priority if it is already queued.
+
+        # Arguments
+
+        * `key` - the item to queue
+        * `priority` - the priority of the item, lowest is popped first
+
+        # Examples
+
+        ```
+        let mut queue = IndexedPriorityQueue::new();
+        queue.push("a", 5);
+        queue.push("a", 2);
+        assert_eq!(queue.len(), 1);
+        ```
+        */
+        match self.positions.get(&key) {
+            Some(&i) => {
+                let previous = self.heap[i].1;
+                self.heap[i].1 = priority;
+                if priority < previous {self.sift_up(i);} else {self.sift_down(i);}
+            },
+            None => {
+                self.heap.push((key.clone(), priority));
+                self.positions.insert(key, self.heap.len() - 1);
+                self.sift_up(self.heap.len() - 1);
+            }
+        }
+    }
+
+    pub fn push_decrease(&mut self, key: K, priority: P) -> bool {
+        /* Add an item, or lower its priority if already queued with a higher one.
+
+        # Returns
+
+        Whether the queue was changed.
+        */
+        match self.priority(&key) {
+            Some(current) if current <= priority => false,
+            _ => {
+                self.push(key, priority);
+                true
+            }
+        }
+    }
+
+    pub fn peek(&self) -> Option<(&K, P)> {
+        self.heap.first().map(|(k, p)| (k, *p))
+    }
+
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        /* Remove and return the item with the lowest priority. */
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (key, priority) = self.heap.pop()?;
+        self.positions.remove(&key);
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((key, priority))
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let i = *self.positions.get(key)?;
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let (_, priority) = self.heap.pop()?;
+        self.positions.remove(key);
+        if i < self.heap.len() {
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        Some(priority)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions.insert(self.heap[a].0.clone(), a);
+        self.positions.insert(self.heap[b].0.clone(), b);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut smallest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.heap[child].1 < self.heap[smallest].1 {
+                    smallest = child;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pop_order() {
+        let mut queue = IndexedPriorityQueue::new();
+        for (key, priority) in [("d", 4), ("a", 1), ("c", 3), ("e", 5), ("b", 2)] {
+            queue.push(key, priority);
+        }
+
+        let order: Vec<&str> = core::iter::from_fn(|| queue.pop().map(|(k, _)| k)).collect();
+        assert_eq!(order, vec!["a", "b", "c", "d", "e"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_update_priority() {
+        let mut queue = IndexedPriorityQueue::new();
+        queue.push("a", 10);
+        queue.push("b", 5);
+        queue.push("c", 7);
+
+        assert!(queue.push_decrease("a", 1));
+        assert!(!queue.push_decrease("b", 6));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek(), Some((&"a", 1)));
+
+        queue.push("a", 20);
+        assert_eq!(queue.remove(&"c"), Some(7));
+        assert_eq!(queue.pop(), Some(("b", 5)));
+        assert_eq!(queue.pop(), Some(("a", 20)));
+        assert_eq!(queue.pop(), None);
+    }
+}
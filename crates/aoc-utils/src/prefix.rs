@@ -0,0 +1,134 @@
+/*                        PREFIX SUMS
+
+Cumulative sums allowing the total of any contiguous range of values, or any
+rectangle of a grid, to be found in constant time. Useful for counting the
+empty rows and columns crossed when expanding space, or for region totals.
+
+*/
+
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+use super::grid::Grid;
+
+pub struct PrefixSum {
+    // cumulative[i] is the sum of the first i values
+    cumulative: Vec<i64>
+}
+
+impl PrefixSum {
+    pub fn new<I: IntoIterator<Item = i64>>(values: I) -> PrefixSum {
+        /* Build the cumulative sums of a sequence of values.
+
+        # Examples
+
+        ```
+        let empty_columns = PrefixSum::new((0..width).map(|c| if column_empty(c) {1} else {0}));
+        ```
+        */
+        let mut cumulative = vec![0];
+        for value in values {
+            cumulative.push(cumulative[cumulative.len() - 1] + value);
+        }
+        PrefixSum { cumulative }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cumulative.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn total(&self) -> i64 {
+        self.cumulative[self.len()]
+    }
+
+    pub fn sum(&self, range: Range<usize>) -> i64 {
+        /* Sum of the values with indices within the half-open range.
+
+        # Panics
+
+        If the range extends beyond the values.
+        */
+        if range.start >= range.end {
+            return 0;
+        }
+        self.cumulative[range.end] - self.cumulative[range.start]
+    }
+}
+
+pub struct SummedAreaTable {
+    width: usize,
+    // cumulative[r * (width + 1) + c] is the sum of all values above and left of (r, c)
+    cumulative: Vec<i64>
+}
+
+impl SummedAreaTable {
+    pub fn new(grid: &Grid<i64>) -> SummedAreaTable {
+        /* Build the summed-area table of a grid of values. */
+        let width = grid.width();
+        let mut cumulative = vec![0; (grid.height() + 1) * (width + 1)];
+
+        for row in 0..grid.height() {
+            let mut row_total = 0;
+            for column in 0..width {
+                row_total += grid.row(row)[column];
+                cumulative[(row + 1) * (width + 1) + column + 1] = cumulative[row * (width + 1) + column + 1] + row_total;
+            }
+        }
+
+        SummedAreaTable { width, cumulative }
+    }
+
+    fn at(&self, row: usize, column: usize) -> i64 {
+        self.cumulative[row * (self.width + 1) + column]
+    }
+
+    pub fn sum(&self, rows: Range<usize>, columns: Range<usize>) -> i64 {
+        /* Sum of the values within the rectangle covering the half-open row and column ranges.
+
+        # Examples
+
+        ```
+        let table = SummedAreaTable::new(&grid);
+        let top_left_total = table.sum(0..2, 0..2);
+        ```
+        */
+        if rows.start >= rows.end || columns.start >= columns.end {
+            return 0;
+        }
+        self.at(rows.end, columns.end) - self.at(rows.start, columns.end) - self.at(rows.end, columns.start)
+            + self.at(rows.start, columns.start)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_prefix_sum() {
+        let sums = PrefixSum::new([3, 1, 4, 1, 5]);
+
+        assert_eq!(sums.len(), 5);
+        assert_eq!(sums.total(), 14);
+        assert_eq!(sums.sum(1..4), 6);
+        assert_eq!(sums.sum(0..5), 14);
+        assert_eq!(sums.sum(3..3), 0);
+        assert!(PrefixSum::new([]).is_empty());
+    }
+
+    #[test]
+    fn test_summed_area_table() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]).unwrap();
+        let table = SummedAreaTable::new(&grid);
+
+        assert_eq!(table.sum(0..3, 0..3), 45);
+        assert_eq!(table.sum(1..3, 1..3), 28);
+        assert_eq!(table.sum(0..1, 0..3), 6);
+        assert_eq!(table.sum(2..3, 0..1), 7);
+        assert_eq!(table.sum(1..1, 0..3), 0);
+    }
+}
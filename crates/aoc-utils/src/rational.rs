@@ -0,0 +1,203 @@
+/*                        RATIONAL NUMBERS
+
+Exact fractions with i128 numerator and denominator, for linear solving and
+geometry where floating point rounding would give wrong answers. Values are
+always held in lowest terms with a positive denominator.
+
+*/
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::math::gcd;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numerator: 0, denominator: 1 };
+    pub const ONE: Rational = Rational { numerator: 1, denominator: 1 };
+
+    pub fn new(numerator: i128, denominator: i128) -> Rational {
+        /* Create the fraction numerator/denominator in lowest terms.
+
+        # Panics
+
+        If the denominator is zero.
+
+        # Examples
+
+        ```
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        ```
+        */
+        assert!(denominator != 0, "Rational with zero denominator");
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Rational { numerator: numerator / divisor, denominator: denominator / divisor }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn abs(&self) -> Rational {
+        Rational { numerator: self.numerator.abs(), denominator: self.denominator }
+    }
+
+    pub fn recip(&self) -> Rational {
+        /* The reciprocal 1/x, panicking if the value is zero. */
+        Rational::new(self.denominator, self.numerator)
+    }
+
+    pub fn floor(&self) -> i128 {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Rational {
+        Rational { numerator: value, denominator: 1 }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Rational {
+        Rational::from(value as i128)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        let common = gcd(self.denominator, other.denominator);
+        Rational::new(
+            self.numerator * (other.denominator / common) + other.numerator * (self.denominator / common),
+            self.denominator / common * other.denominator
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        // Cancel before multiplying to keep intermediate values small
+        let a = gcd(self.numerator, other.denominator).max(1);
+        let b = gcd(other.numerator, self.denominator).max(1);
+        Rational::new(
+            (self.numerator / a) * (other.numerator / b),
+            (self.denominator / b) * (other.denominator / a)
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Rational) -> Rational {
+        self * other.recip()
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational { numerator: -self.numerator, denominator: self.denominator }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        assert_eq!(Rational::new(0, -7), Rational::ZERO);
+        assert_eq!(Rational::new(-3, 2).denominator(), 2);
+        assert!(Rational::new(8, 4).is_integer());
+        assert_eq!(Rational::new(-7, 2).floor(), -4);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let half = Rational::new(1, 2);
+        let third = Rational::new(1, 3);
+
+        assert_eq!(half + third, Rational::new(5, 6));
+        assert_eq!(half - third, Rational::new(1, 6));
+        assert_eq!(half * third, Rational::new(1, 6));
+        assert_eq!(half / third, Rational::new(3, 2));
+        assert_eq!(-half, Rational::new(-1, 2));
+        assert_eq!(Rational::from(3i64) * third, Rational::ONE);
+    }
+
+    #[test]
+    fn test_ordering_and_display() {
+        let mut values = vec![Rational::new(1, 2), Rational::new(-1, 3), Rational::new(2, 5)];
+        values.sort();
+
+        assert_eq!(values, vec![Rational::new(-1, 3), Rational::new(2, 5), Rational::new(1, 2)]);
+        assert_eq!(Rational::new(-3, 6).to_string(), "-1/2");
+        assert_eq!(Rational::from(4i128).to_string(), "4");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_denominator() {
+        Rational::new(1, 0);
+    }
+}
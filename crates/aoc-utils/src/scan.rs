@@ -0,0 +1,142 @@
+/*                        MULTI-PATTERN SCANNING
+
+Find every occurrence of any of a set of tokens within a string along with their
+positions, e.g. the word forms of digits in day 1. Matching is performed in a
+single pass using an Aho-Corasick automaton.
+
+*/
+
+use alloc::{boxed::Box, string::{String, ToString}, vec::Vec};
+use aho_corasick::{AhoCorasick, MatchKind};
+
+// Returned when the automaton cannot be built, e.g. when the patterns exceed its size limits
+pub use aho_corasick::BuildError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScanMatch {
+    // Index of the matched pattern within the scanner's pattern list
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize
+}
+
+pub struct Scanner {
+    automaton: AhoCorasick,
+    patterns: Vec<String>,
+    overlapping: bool
+}
+
+impl Scanner {
+    fn build<I, P>(patterns: I, overlapping: bool) -> Result<Scanner, BuildError>
+    where I: IntoIterator<Item = P>, P: AsRef<str> {
+        let patterns: Vec<String> = patterns.into_iter().map(|p| p.as_ref().to_string()).collect();
+
+        let match_kind = if overlapping {MatchKind::Standard} else {MatchKind::LeftmostLongest};
+
+        let automaton = AhoCorasick::builder().match_kind(match_kind).build(&patterns)?;
+
+        Ok(Scanner { automaton, patterns, overlapping })
+    }
+
+    pub fn new<I, P>(patterns: I) -> Result<Scanner, BuildError>
+    where I: IntoIterator<Item = P>, P: AsRef<str> {
+        /* Create a scanner returning non-overlapping matches.
+
+        Matches are found from left to right, where several patterns match at the same
+        position the longest is chosen.
+
+        # Arguments
+
+        * `patterns` - the tokens to search for
+
+        # Examples
+
+        ```
+        let scanner = Scanner::new(["one", "two", "three"]).unwrap();
+        ```
+        */
+        Scanner::build(patterns, false)
+    }
+
+    pub fn new_overlapping<I, P>(patterns: I) -> Result<Scanner, BuildError>
+    where I: IntoIterator<Item = P>, P: AsRef<str> {
+        /* Create a scanner returning every match including those which overlap.
+
+        Needed where tokens may share characters, e.g. both 'one' and 'eight' are
+        present within 'oneight'.
+
+        # Arguments
+
+        * `patterns` - the tokens to search for
+
+        # Examples
+
+        ```
+        let scanner = Scanner::new_overlapping(["one", "eight"]).unwrap();
+        assert_eq!(scanner.find_iter("oneight").count(), 2);
+        ```
+        */
+        Scanner::build(patterns, true)
+    }
+
+    pub fn pattern(&self, index: usize) -> &str {
+        &self.patterns[index]
+    }
+
+    pub fn find_iter<'a>(&'a self, haystack: &'a str) -> Box<dyn Iterator<Item = ScanMatch> + 'a> {
+        /* Iterate through all matches within a string in order of position.
+
+        # Arguments
+
+        * `haystack` - the string to search
+
+        # Examples
+
+        ```
+        for m in scanner.find_iter("xtwone3four") {
+            println!("'{}' at {}", scanner.pattern(m.pattern), m.start);
+        }
+        ```
+        */
+        let to_scan_match = |m: aho_corasick::Match| ScanMatch {
+            pattern: m.pattern().as_usize(),
+            start: m.start(),
+            end: m.end()
+        };
+
+        if self.overlapping {
+            Box::new(self.automaton.find_overlapping_iter(haystack).map(to_scan_match))
+        } else {
+            Box::new(self.automaton.find_iter(haystack).map(to_scan_match))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_leftmost_longest() {
+        let scanner = Scanner::new(["one", "on", "eight", "two"]).unwrap();
+
+        let matches: Vec<(&str, usize)> = scanner
+            .find_iter("oneightwo")
+            .map(|m| (scanner.pattern(m.pattern), m.start))
+            .collect();
+
+        assert_eq!(matches, vec![("one", 0), ("two", 6)]);
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let scanner = Scanner::new_overlapping(["one", "eight", "two"]).unwrap();
+
+        let matches: Vec<(&str, usize, usize)> = scanner
+            .find_iter("oneightwo")
+            .map(|m| (scanner.pattern(m.pattern), m.start, m.end))
+            .collect();
+
+        assert_eq!(matches, vec![("one", 0, 3), ("eight", 2, 7), ("two", 6, 9)]);
+    }
+}
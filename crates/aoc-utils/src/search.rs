@@ -0,0 +1,264 @@
+/*                        SEARCH
+
+Generic graph searches over arbitrary states, where the graph is defined
+implicitly by a function returning the neighbours of a state, and binary
+search over the answer of monotone problems.
+
+*/
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::hash::Hash;
+
+use super::hash::FxHashMap;
+use super::pqueue::IndexedPriorityQueue;
+
+pub struct BfsResult<S> {
+    pub distances: FxHashMap<S, u32>,
+    // The state from which each state was first reached, start states have no entry
+    pub predecessors: FxHashMap<S, S>
+}
+
+impl<S: Clone + Eq + Hash> BfsResult<S> {
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        /* Reconstruct a shortest path from one of the start states to the target.
+
+        # Returns
+
+        The states along the path including both ends, None if the target was not reached.
+        */
+        if !self.distances.contains_key(target) {
+            return None;
+        }
+
+        let mut path = vec![target.clone()];
+
+        while let Some(previous) = self.predecessors.get(&path[path.len() - 1]) {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+pub fn bfs_with_predecessors<S, I, N, R>(start_states: I, mut neighbours: N) -> BfsResult<S>
+where S: Clone + Eq + Hash, I: IntoIterator<Item = S>, N: FnMut(&S) -> R, R: IntoIterator<Item = S> {
+    /* Breadth first search recording the distance to, and predecessor of, every reachable state.
+
+    # Arguments
+
+    * `start_states` - the states at distance zero
+    * `neighbours` - returns the states reachable in a single step from a state
+
+    # Examples
+
+    ```
+    let result = bfs_with_predecessors([0], |&n: &i32| if n < 10 {vec![n + 1, n + 3]} else {vec![]});
+    assert_eq!(result.distances[&9], 3);
+    ```
+    */
+    let mut distances = FxHashMap::<S, u32>::default();
+    let mut predecessors = FxHashMap::<S, S>::default();
+    let mut queue = VecDeque::<S>::new();
+
+    for state in start_states {
+        if !distances.contains_key(&state) {
+            distances.insert(state.clone(), 0);
+            queue.push_back(state);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state];
+
+        for next in neighbours(&state) {
+            if distances.contains_key(&next) {
+                continue;
+            }
+            distances.insert(next.clone(), distance + 1);
+            predecessors.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+
+    BfsResult { distances, predecessors }
+}
+
+pub fn bfs<S, I, N, R>(start_states: I, neighbours: N) -> FxHashMap<S, u32>
+where S: Clone + Eq + Hash, I: IntoIterator<Item = S>, N: FnMut(&S) -> R, R: IntoIterator<Item = S> {
+    /* Breadth first search returning the distance to every reachable state.
+
+    # Arguments
+
+    * `start_states` - the states at distance zero
+    * `neighbours` - returns the states reachable in a single step from a state
+
+    # Examples
+
+    ```
+    let distances = bfs([(0, 0)], |&pos| grid.neighbours(pos.0, pos.1, Connectivity::Four));
+    ```
+    */
+    bfs_with_predecessors(start_states, neighbours).distances
+}
+
+pub fn dijkstra<S, I, N, R>(start_states: I, mut neighbours: N) -> FxHashMap<S, u64>
+where S: Clone + Eq + Hash, I: IntoIterator<Item = S>, N: FnMut(&S) -> R, R: IntoIterator<Item = (S, u64)> {
+    /* Find the lowest total cost to reach every reachable state.
+
+    Queued states have their cost lowered in place so each state is only ever
+    held in the queue once.
+
+    # Arguments
+
+    * `start_states` - the states at cost zero
+    * `neighbours` - returns the states reachable in a single step from a state along with the cost of the step
+
+    # Examples
+
+    ```
+    let costs = dijkstra([(0, 0)], |&(r, c)| {
+        grid.neighbours(r, c, Connectivity::Four)
+            .into_iter()
+            .map(|(nr, nc)| ((nr, nc), *grid.get(nr, nc).unwrap() as u64))
+            .collect::<Vec<_>>()
+    });
+    ```
+    */
+    let mut costs = FxHashMap::<S, u64>::default();
+    let mut queue = IndexedPriorityQueue::<S, u64>::new();
+
+    for state in start_states {
+        queue.push(state, 0);
+    }
+
+    while let Some((state, cost)) = queue.pop() {
+        // Settled before expanding, so neither a self-loop nor an edge back can queue it again
+        costs.insert(state.clone(), cost);
+        for (next, step_cost) in neighbours(&state) {
+            if !costs.contains_key(&next) {
+                queue.push_decrease(next, cost + step_cost);
+            }
+        }
+    }
+
+    costs
+}
+
+pub fn binary_search_answer<P>(lo: i64, hi: i64, mut predicate: P) -> Option<i64>
+where P: FnMut(i64) -> bool {
+    /* Find the smallest value in lo..=hi for which a monotone predicate holds.
+
+    The predicate must be false for every value below some threshold and true
+    for every value from it onwards. Returns None if it is false throughout.
+
+    # Examples
+
+    ```
+    // Shortest button hold beating a record of 9mm in a 7ms race
+    let hold = binary_search_answer(0, 3, |h| h * (7 - h) > 9);
+    assert_eq!(hold, Some(2));
+    ```
+    */
+    if lo > hi || !predicate(hi) {
+        return None;
+    }
+
+    let (mut lo, mut hi) = (lo, hi);
+
+    while lo < hi {
+        // Floor of the average without overflowing for extreme bounds
+        let mid = (lo & hi) + ((lo ^ hi) >> 1);
+
+        if predicate(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    Some(lo)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::{Connectivity, Grid};
+
+    #[test]
+    fn test_bfs_grid() {
+        let grid = Grid::from_lines("...\n.#.\n...").unwrap();
+
+        let distances = bfs([(0, 0)], |&(r, c)| {
+            grid.neighbours(r, c, Connectivity::Four)
+                .into_iter()
+                .filter(|&(nr, nc)| grid.get(nr, nc) == Some(&'.'))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(distances.len(), 8);
+        assert_eq!(distances[&(2, 2)], 4);
+        assert!(!distances.contains_key(&(1, 1)));
+    }
+
+    #[test]
+    fn test_bfs_predecessors() {
+        let result = bfs_with_predecessors([0, 100], |&n: &i32| if n < 10 {vec![n + 1, n + 3]} else {vec![]});
+
+        assert_eq!(result.distances[&9], 3);
+        assert_eq!(result.distances[&100], 0);
+        assert_eq!(result.path_to(&9).unwrap().len(), 4);
+        assert_eq!(result.path_to(&100), Some(vec![100]));
+        assert_eq!(result.path_to(&-1), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let grid = Grid::from_rows(vec![vec![1, 9, 1], vec![1, 9, 1], vec![1, 1, 1]]).unwrap();
+
+        let costs = dijkstra([(0, 0)], |&(r, c)| {
+            grid.neighbours(r, c, Connectivity::Four)
+                .into_iter()
+                .map(|(nr, nc)| ((nr, nc), *grid.get(nr, nc).unwrap() as u64))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(costs[&(0, 0)], 0);
+        assert_eq!(costs[&(0, 2)], 6);
+        assert_eq!(costs[&(0, 1)], 9);
+        assert_eq!(costs.len(), 9);
+    }
+
+    #[test]
+    fn test_dijkstra_self_loop() {
+        let costs = dijkstra([0], |&n: &u32| if n == 0 {vec![(0, 1), (1, 5)]} else {vec![]});
+
+        assert_eq!(costs[&0], 0);
+        assert_eq!(costs[&1], 5);
+    }
+
+    #[test]
+    fn test_dijkstra_back_edge() {
+        // 2 is reached from 1 and leads straight back to it, and to the start
+        let costs = dijkstra([0], |&n: &u32| match n {
+            0 => vec![(1, 2), (2, 7)],
+            1 => vec![(2, 1), (1, 4)],
+            2 => vec![(1, 1), (0, 1)],
+            _ => vec![]
+        });
+
+        assert_eq!(costs[&0], 0);
+        assert_eq!(costs[&1], 2);
+        assert_eq!(costs[&2], 3);
+    }
+
+    #[test]
+    fn test_binary_search_answer() {
+        assert_eq!(binary_search_answer(0, 3, |h| h * (7 - h) > 9), Some(2));
+        assert_eq!(binary_search_answer(0, 100, |x| x * x >= 50), Some(8));
+        assert_eq!(binary_search_answer(0, 5, |_| false), None);
+        assert_eq!(binary_search_answer(5, 0, |_| true), None);
+        assert_eq!(binary_search_answer(i64::MIN, i64::MAX, |x| x >= -7), Some(-7));
+        assert_eq!(binary_search_answer(i64::MIN, i64::MAX, |_| true), Some(i64::MIN));
+    }
+}
@@ -0,0 +1,192 @@
+/*                        SPARSE GRID
+
+A grid backed by a hash map for unbounded or mostly empty spaces, such as the
+trench of a dig plan, where positions may be negative. The bounding box of
+all occupied cells is kept up to date.
+
+*/
+
+use alloc::vec::Vec;
+
+use super::grid::Grid;
+use super::hash::FxHashMap;
+
+// Inclusive ((min_row, min_column), (max_row, max_column)) bounds
+pub type Bounds = ((i64, i64), (i64, i64));
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: FxHashMap<(i64, i64), T>,
+    bounds: Option<Bounds>
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: FxHashMap::default(), bounds: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        /* The smallest rectangle containing every occupied cell, None if the grid is empty. */
+        self.bounds
+    }
+
+    pub fn get(&self, position: (i64, i64)) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn contains(&self, position: (i64, i64)) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    pub fn insert(&mut self, position: (i64, i64), value: T) -> Option<T> {
+        /* Store a value at a position, returning any value previously held there. */
+        let (row, column) = position;
+        self.bounds = Some(match self.bounds {
+            Some(((r0, c0), (r1, c1))) => ((r0.min(row), c0.min(column)), (r1.max(row), c1.max(column))),
+            None => (position, position)
+        });
+        self.cells.insert(position, value)
+    }
+
+    pub fn remove(&mut self, position: (i64, i64)) -> Option<T> {
+        /* Remove the value at a position, shrinking the bounds if it was on the edge. */
+        let removed = self.cells.remove(&position)?;
+
+        if let Some(((r0, c0), (r1, c1))) = self.bounds {
+            let (row, column) = position;
+            if row == r0 || row == r1 || column == c0 || column == c1 {
+                self.bounds = self.compute_bounds();
+            }
+        }
+        Some(removed)
+    }
+
+    fn compute_bounds(&self) -> Option<Bounds> {
+        let rows = self.cells.keys().map(|p| p.0);
+        let columns = self.cells.keys().map(|p| p.1);
+        Some((
+            (rows.clone().min()?, columns.clone().min()?),
+            (rows.max()?, columns.max()?)
+        ))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        /* Iterate through the occupied cells in row order. */
+        let mut positions: Vec<&(i64, i64)> = self.cells.keys().collect();
+        positions.sort();
+        positions.into_iter().map(move |p| (*p, &self.cells[p]))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    pub fn from_grid<F: Fn(&T) -> bool>(grid: &Grid<T>, occupied: F) -> SparseGrid<T> {
+        /* Create a sparse grid holding the cells of a dense grid for which `occupied` is true.
+
+        # Examples
+
+        ```
+        let trench = SparseGrid::from_grid(&Grid::from_lines(input).unwrap(), |&c| c == '#');
+        ```
+        */
+        let mut sparse = SparseGrid::new();
+        for ((row, column), value) in grid.positions() {
+            if occupied(value) {
+                sparse.insert((row as i64, column as i64), value.clone());
+            }
+        }
+        sparse
+    }
+
+    pub fn to_grid(&self, fill: T) -> (Grid<T>, (i64, i64)) {
+        /* Convert to a dense grid just covering the bounds, with unoccupied cells set to `fill`.
+
+        # Returns
+
+        The dense grid and the sparse position of its top-left cell, which must be
+        subtracted from sparse positions to give dense ones.
+        */
+        let ((r0, c0), (r1, c1)) = match self.bounds {
+            Some(b) => b,
+            None => return (Grid::new(0, 0, fill), (0, 0))
+        };
+
+        let mut grid = Grid::new((c1 - c0 + 1) as usize, (r1 - r0 + 1) as usize, fill);
+        for (&(row, column), value) in &self.cells {
+            if let Some(cell) = grid.get_mut((row - r0) as usize, (column - c0) as usize) {
+                *cell = value.clone();
+            }
+        }
+        (grid, (r0, c0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bounds_tracking() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert((0, 0), '#');
+        grid.insert((-2, 5), '#');
+        grid.insert((3, -1), '#');
+        assert_eq!(grid.bounds(), Some(((-2, -1), (3, 5))));
+
+        assert_eq!(grid.remove((-2, 5)), Some('#'));
+        assert_eq!(grid.bounds(), Some(((0, -1), (3, 0))));
+        assert_eq!(grid.remove((9, 9)), None);
+
+        grid.remove((0, 0));
+        grid.remove((3, -1));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn test_row_order_iteration() {
+        let mut grid = SparseGrid::new();
+        for position in [(1, 0), (0, 2), (-1, 3), (0, -4)] {
+            grid.insert(position, 1);
+        }
+
+        let positions: Vec<(i64, i64)> = grid.iter().map(|(p, _)| p).collect();
+        assert_eq!(positions, vec![(-1, 3), (0, -4), (0, 2), (1, 0)]);
+    }
+
+    #[test]
+    fn test_dense_conversion() {
+        let dense = Grid::from_lines("#..\n..#\n").unwrap();
+        let sparse = SparseGrid::from_grid(&dense, |&c| c == '#');
+
+        assert_eq!(sparse.len(), 2);
+        assert!(sparse.contains((1, 2)));
+
+        let (round_trip, offset) = sparse.to_grid('.');
+        assert_eq!(round_trip, dense);
+        assert_eq!(offset, (0, 0));
+
+        let mut shifted = SparseGrid::new();
+        shifted.insert((-1, -1), 'a');
+        shifted.insert((0, 1), 'b');
+        let (grid, offset) = shifted.to_grid('.');
+        assert_eq!(offset, (-1, -1));
+        assert_eq!(grid.row_string(0), "a..");
+        assert_eq!(grid.row_string(1), "..b");
+    }
+}
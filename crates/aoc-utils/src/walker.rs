@@ -0,0 +1,173 @@
+/*                        WALKER
+
+A walker with a position and heading which follows movement instructions,
+recording the cells it passes through and the vertices where it turns. Used
+for following dig plans and pipe loops.
+
+*/
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left
+}
+
+impl Direction {
+    pub fn from_char(c: char) -> Result<Direction, String> {
+        /* Read a direction from a single character instruction, e.g. 'U', '>' or 'N'. */
+        match c {
+            'U' | 'N' | '^' => Ok(Direction::Up),
+            'R' | 'E' | '>' => Ok(Direction::Right),
+            'D' | 'S' | 'v' => Ok(Direction::Down),
+            'L' | 'W' | '<' => Ok(Direction::Left),
+            _ => Err(format!("Unrecognised direction '{}'", c))
+        }
+    }
+
+    pub fn offset(&self) -> (i64, i64) {
+        /* The (row, column) change of a single step in this direction. */
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1)
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up
+        }
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        self.turn_right().turn_right().turn_right()
+    }
+
+    pub fn reverse(&self) -> Direction {
+        self.turn_right().turn_right()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Walker {
+    pub pos: (i64, i64),
+    pub dir: Direction,
+    record_cells: bool,
+    cells: Vec<(i64, i64)>,
+    vertices: Vec<(i64, i64)>
+}
+
+impl Walker {
+    pub fn new(pos: (i64, i64), dir: Direction) -> Walker {
+        /* Create a walker recording only the vertices at the end of each movement.
+
+        Suitable for long movements such as those of a dig plan with hexadecimal
+        distances, where recording every cell is not feasible.
+        */
+        Walker { pos, dir, record_cells: false, cells: Vec::new(), vertices: vec![pos] }
+    }
+
+    pub fn tracing(pos: (i64, i64), dir: Direction) -> Walker {
+        /* Create a walker which also records every cell it passes through. */
+        Walker { pos, dir, record_cells: true, cells: vec![pos], vertices: vec![pos] }
+    }
+
+    pub fn turn_left(&mut self) {
+        self.dir = self.dir.turn_left();
+    }
+
+    pub fn turn_right(&mut self) {
+        self.dir = self.dir.turn_right();
+    }
+
+    pub fn turn_around(&mut self) {
+        self.dir = self.dir.reverse();
+    }
+
+    pub fn forward(&mut self, steps: i64) {
+        /* Move the given number of steps in the current direction. */
+        let (dr, dc) = self.dir.offset();
+
+        if self.record_cells {
+            for i in 1..=steps {
+                self.cells.push((self.pos.0 + dr * i, self.pos.1 + dc * i));
+            }
+        }
+
+        self.pos = (self.pos.0 + dr * steps, self.pos.1 + dc * steps);
+        self.vertices.push(self.pos);
+    }
+
+    pub fn walk(&mut self, dir: Direction, steps: i64) {
+        /* Face the given direction and move forward, as for a dig plan instruction. */
+        self.dir = dir;
+        self.forward(steps);
+    }
+
+    pub fn cells(&self) -> &Vec<(i64, i64)> {
+        /* Every cell visited in order, including the start. Empty unless created with `tracing`. */
+        &self.cells
+    }
+
+    pub fn vertices(&self) -> &Vec<(i64, i64)> {
+        /* The start position followed by the position at the end of every movement. */
+        &self.vertices
+    }
+
+    pub fn distance_travelled(&self) -> i64 {
+        self.vertices
+            .windows(2)
+            .map(|w| (w[1].0 - w[0].0).abs() + (w[1].1 - w[0].1).abs())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Direction::from_char('R').unwrap(), Direction::Right);
+        assert_eq!(Direction::from_char('^').unwrap(), Direction::Up);
+        assert!(Direction::from_char('x').is_err());
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Down.reverse(), Direction::Up);
+    }
+
+    #[test]
+    fn test_trace_cells() {
+        let mut walker = Walker::tracing((0, 0), Direction::Right);
+        walker.forward(2);
+        walker.turn_right();
+        walker.forward(1);
+        walker.turn_around();
+
+        assert_eq!(walker.pos, (1, 2));
+        assert_eq!(walker.dir, Direction::Up);
+        assert_eq!(walker.cells(), &vec![(0, 0), (0, 1), (0, 2), (1, 2)]);
+        assert_eq!(walker.vertices(), &vec![(0, 0), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn test_dig_plan_loop() {
+        let mut walker = Walker::new((0, 0), Direction::Right);
+        for (c, steps) in [('R', 6), ('D', 5), ('L', 6), ('U', 5)] {
+            walker.walk(Direction::from_char(c).unwrap(), steps);
+        }
+
+        assert_eq!(walker.pos, (0, 0));
+        assert_eq!(walker.vertices().len(), 5);
+        assert!(walker.cells().is_empty());
+        assert_eq!(walker.distance_travelled(), 22);
+    }
+}
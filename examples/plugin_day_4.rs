@@ -0,0 +1,92 @@
+/*                        DAY 4 PLUGIN
+
+A solver plugin for day 4, see src/plugin.rs, written without the aoc23 crate
+as a plugin built elsewhere would be. Build it with
+
+cargo build --example plugin_day_4
+
+and load target/debug/examples/libplugin_day_4.so, or the .dylib or .dll of
+the platform, with `aoc23 run --plugin` or `aoc23 compare --plugin`.
+
+Rather than scoring each card as it is read, this counts the matches of every
+card once and keeps a running count of the copies won in part 2.
+
+*/
+
+use std::collections::HashSet;
+use std::ffi::{c_char, CString};
+
+const PLUGIN_ABI_VERSION: u32 = 1;
+
+fn card_matches(input: &str) -> Result<Vec<usize>, String> {
+    /* The number of winning numbers on each card, in card order. */
+    let mut matches = Vec::<usize>::new();
+    for (i, line) in input.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let numbers = match line.split_once(':').and_then(|(_, n)| n.split_once('|')) {
+            Some(n) => n,
+            None => return Err(format!("Line {}: not a scratchcard", i + 1))
+        };
+        let winning: HashSet<&str> = numbers.0.split_whitespace().collect();
+        matches.push(numbers.1.split_whitespace().filter(|n| winning.contains(n)).count());
+    }
+    Ok(matches)
+}
+
+fn solve(part: u32, input: &str) -> Result<u64, String> {
+    let matches = card_matches(input)?;
+    match part {
+        1 => Ok(matches.iter().map(|&m| if m == 0 {0} else {1u64 << (m - 1)}).sum()),
+        2 => {
+            let mut copies = vec![1u64; matches.len()];
+            for (i, &m) in matches.iter().enumerate() {
+                for j in i + 1..(i + 1 + m).min(copies.len()) {
+                    copies[j] += copies[i];
+                }
+            }
+            Ok(copies.iter().sum())
+        },
+        _ => Err(format!("Day 4 has no part {}", part))
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn aoc23_plugin_abi_version() -> u32 {
+    PLUGIN_ABI_VERSION
+}
+
+#[no_mangle]
+pub extern "C" fn aoc23_plugin_day() -> u32 {
+    4
+}
+
+#[no_mangle]
+pub extern "C" fn aoc23_plugin_name() -> *const c_char {
+    c"plugin_day_4".as_ptr()
+}
+
+/// # Safety
+///
+/// `input` must point to `len` bytes and `out` to somewhere a string pointer can be written.
+#[no_mangle]
+pub unsafe extern "C" fn aoc23_plugin_solve(part: u32, input: *const u8, len: usize, out: *mut *mut c_char) -> i32 {
+    let bytes = std::slice::from_raw_parts(input, len);
+    let (status, text) = match std::str::from_utf8(bytes) {
+        Ok(input) => match solve(part, input) {
+            Ok(answer) => (0, answer.to_string()),
+            Err(e) => (1, e)
+        },
+        Err(e) => (1, format!("The input is not UTF-8: {}", e))
+    };
+    *out = CString::new(text).unwrap_or_default().into_raw();
+    status
+}
+
+/// # Safety
+///
+/// `s` must be a string written by `aoc23_plugin_solve` which has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn aoc23_plugin_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -0,0 +1,150 @@
+/*                        ASYNC RUNNER
+
+Runs the days of an event on a tokio runtime, so that the inputs which are
+missing can be downloaded while the days which already have one are being
+solved, rather than waiting for each download in turn. Solving is CPU bound
+and downloading uses a blocking HTTP client, so both happen on the blocking
+thread pool of the runtime, with a task per day tying them together.
+
+The results are gathered in day order whichever finishes first, as with the
+parallel runner.
+
+*/
+
+use std::path::Path;
+use tokio::task::JoinHandle;
+
+use crate::config::Config;
+use crate::error::AocError;
+use crate::event::Event;
+use crate::fetch::ensure_input_async;
+use crate::runner::{run_day, DayResult, InputSet, PartResult};
+use crate::util::io::InputSource;
+
+pub async fn run_all_async(inputs: &InputSet) -> Vec<DayResult> {
+    /* As `run_all`, solving the days with an input concurrently.
+
+    # Examples
+
+    ```
+    let inputs = InputSet::from_dir("data").unwrap();
+    let results = tokio::runtime::Runtime::new().unwrap().block_on(run_all_async(&inputs));
+    ```
+    */
+    let mut tasks = Vec::<(u32, JoinHandle<DayResult>)>::new();
+    for solver in inputs.solvers() {
+        let day = solver.day();
+        let input = match inputs.get(day) {
+            Some(i) => i.to_string(),
+            None => continue
+        };
+        tasks.push((day, tokio::task::spawn_blocking(move || run_day(solver.as_ref(), &input))));
+    }
+
+    gather(tasks).await
+}
+
+pub async fn run_event(event: &Event, data_dir: impl AsRef<Path>, config: &Config) -> Vec<DayResult> {
+    /* Solve every day of an event with the inputs in a directory, downloading those which are missing.
+
+    A day whose input cannot be downloaded is reported with the reason in place of
+    its answers, without holding up the other days.
+
+    # Examples
+
+    ```
+    let config = Config::load().unwrap();
+    let event = aoc23::event(2023).unwrap();
+    let results = tokio::runtime::Runtime::new().unwrap().block_on(run_event(&event, config.data_dir(), &config));
+    ```
+    */
+    let mut tasks = Vec::<(u32, JoinHandle<DayResult>)>::new();
+    for solver in event.solvers() {
+        let (year, day) = (event.year, solver.day());
+        let input_file = data_dir.as_ref().join(format!("day_{}.dat", day));
+        let config = config.clone();
+
+        tasks.push((day, tokio::spawn(async move {
+            match ensure_input_async(year, day, input_file.clone(), config).await {
+                Ok(_) => (),
+                Err(e) => return failed(day, &e.to_string())
+            };
+
+            let solved = tokio::task::spawn_blocking(move || match InputSource::read(&input_file).and_then(InputSource::into_string) {
+                Ok(input) => run_day(solver.as_ref(), &input),
+                Err(e) => failed(day, &AocError::from(e).to_string())
+            });
+            match solved.await {
+                Ok(result) => result,
+                Err(e) => failed(day, &format!("Solver failed: {}", e))
+            }
+        })));
+    }
+
+    gather(tasks).await
+}
+
+fn failed(day: u32, error: &str) -> DayResult {
+    /* The result of a day which could not be run, with the same error for both parts. */
+    let parts = [1, 2].iter().map(|&part| PartResult { part, answer: None, error: Some(error.to_string()), diagnostics: Vec::new(), elapsed_ms: 0.0 }).collect();
+    DayResult { day, parts }
+}
+
+async fn gather(tasks: Vec<(u32, JoinHandle<DayResult>)>) -> Vec<DayResult> {
+    // The tasks are already running, so awaiting them in day order does not hold any of them up
+    let mut results = Vec::<DayResult>::new();
+    for (day, task) in tasks {
+        results.push(match task.await {
+            Ok(result) => result,
+            Err(e) => failed(day, &format!("Solver failed: {}", e))
+        });
+    }
+    results
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::cache::CacheMeta;
+    use crate::runner::run_all;
+    use std::path::PathBuf;
+
+    fn answers(results: &[DayResult]) -> Vec<(u32, Vec<Option<Answer>>)> {
+        results.iter().map(|r| (r.day, r.parts.iter().map(|p| p.answer.clone()).collect())).collect()
+    }
+
+    #[tokio::test]
+    async fn test_run_all_async() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let inputs = InputSet::from_dir(&test_dir).unwrap();
+
+        assert_eq!(answers(&run_all_async(&inputs).await), answers(&run_all(&inputs)));
+    }
+
+    #[tokio::test]
+    async fn test_run_event() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let root = std::env::temp_dir().join(format!("aoc23_async_{}", std::process::id()));
+        let data_dir = root.join("data");
+        std::fs::create_dir_all(&data_dir).unwrap();
+        for day in [2, 3, 4, 5] {
+            std::fs::copy(test_dir.join(format!("day_{}.dat", day)), data_dir.join(format!("day_{}.dat", day))).unwrap();
+        }
+
+        // Day 1 is missing from the data directory, and is found in the cache rather than downloaded
+        let config = Config { cache_dir: Some(root.join("cache")), ..Config::default() };
+        let day_1 = std::fs::read_to_string(test_dir.join("day_1_1.dat")).unwrap();
+        config.cache().put(2023, 1, "input", &day_1, &CacheMeta::new("https://adventofcode.com/2023/day/1/input", None)).unwrap();
+
+        let event = crate::event::event(2023).unwrap();
+        let results = run_event(&event, &data_dir, &config).await;
+        let day_1_written = data_dir.join("day_1.dat").exists();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(day_1_written);
+        assert_eq!(results.iter().map(|r| r.day).collect::<Vec<u32>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(results[0].parts[0].answer, Some(Answer::Int(142)));
+        assert_eq!(results[4].parts[1].answer, Some(Answer::Int(46)));
+    }
+}
@@ -0,0 +1,328 @@
+/*                        BENCHMARKING
+
+A quick timing harness for the registered solvers. Each part is solved a
+number of times and the minimum, median and maximum wall-clock times are
+reported in a table, giving an idea of which days are slow. With the
+`profiling` feature the heap allocations of a single run of each part are
+counted too, see src/profiling.rs. Days solved in more than one way can also
+have their implementations timed against each other on the same part, checking
+that they agree on the answer. For careful measurements with warm up and
+outlier analysis use a benchmarking crate such as criterion instead.
+
+*/
+
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::error::AocError;
+#[cfg(feature = "profiling")]
+use crate::profiling::{measure, AllocationStats};
+use crate::runner::InputSet;
+use crate::solver::Solver;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    pub day: u32,
+    pub part: u32,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    // The allocations made by the first of the runs
+    #[cfg(feature = "profiling")]
+    pub heap: AllocationStats
+}
+
+pub fn time_part(solver: &dyn Solver, part: u32, input: &str, runs: usize) -> Result<PartTiming, AocError> {
+    /* Solve one part of a puzzle repeatedly, recording the spread of wall-clock times.
+
+    # Arguments
+
+    * `solver` - the solver for the day
+    * `part` - the part to solve, 1 or 2
+    * `input` - the puzzle input
+    * `runs` - the number of times to solve the part, at least one
+
+    # Examples
+
+    ```
+    let timing = time_part(solver(5).unwrap().as_ref(), 2, &input, 10).unwrap();
+    ```
+    */
+    Ok(timed_runs(solver, part, input, runs)?.1)
+}
+
+pub(crate) fn timed_runs(solver: &dyn Solver, part: u32, input: &str, runs: usize) -> Result<(Answer, PartTiming), AocError> {
+    /* As `time_part`, also giving the answer found by the timed runs. */
+    if runs == 0 {
+        return Err(AocError::InvalidArgument("At least one run is needed for timing".to_string()));
+    }
+
+    let _span = tracing::info_span!("bench", day = solver.day(), part).entered();
+
+    // The allocations are counted on a run of their own so the counting is not timed
+    #[cfg(feature = "profiling")]
+    let heap = match measure(|| solver.solve(part, input)) {
+        (Ok(_), stats) => stats.unwrap_or_default(),
+        (Err(e), _) => return Err(e)
+    };
+
+    let mut times = Vec::<Duration>::with_capacity(runs);
+    let mut answer = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        answer = Some(solver.solve(part, input)?);
+        times.push(start.elapsed());
+    }
+    times.sort();
+
+    // An even number of runs has no middle value, so the two either side are averaged
+    let median = if runs.is_multiple_of(2) {(times[runs / 2 - 1] + times[runs / 2]) / 2} else {times[runs / 2]};
+
+    let timing = PartTiming {
+        day: solver.day(),
+        part,
+        runs,
+        min: times[0],
+        median,
+        max: times[runs - 1],
+        #[cfg(feature = "profiling")]
+        heap
+    };
+
+    // There is at least one run, so there is always an answer
+    match answer {
+        Some(a) => Ok((a, timing)),
+        None => Err(AocError::InvalidArgument("At least one run is needed for timing".to_string()))
+    }
+}
+
+pub fn bench_all(inputs: &InputSet, runs: usize) -> Result<Vec<PartTiming>, AocError> {
+    /* Time both parts of every day of the event which has an input in the set.
+
+    # Examples
+
+    ```
+    let timings = bench_all(&InputSet::from_dir("data").unwrap(), 10).unwrap();
+    println!("{}", table(&timings));
+    ```
+    */
+    let mut timings = Vec::<PartTiming>::new();
+
+    for solver in inputs.solvers() {
+        let input = match inputs.get(solver.day()) {
+            Some(i) => i,
+            None => continue
+        };
+
+        for part in [1, 2] {
+            timings.push(time_part(solver.as_ref(), part, input, runs)?);
+        }
+    }
+
+    Ok(timings)
+}
+
+// One implementation of a part, with its answer and timing, as found by `compare`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contender {
+    pub name: String,
+    pub answer: Answer,
+    pub timing: PartTiming
+}
+
+pub fn compare(implementations: &[(&str, &dyn Solver)], part: u32, input: &str, runs: usize) -> Result<Vec<Contender>, AocError> {
+    /* Solve a part with each of several implementations, timing each as `time_part`.
+
+    The answer of each implementation is taken from its timed runs, so that a slow
+    implementation is not solved again just to check it.
+
+    # Returns
+
+    The answer and timing of each implementation, or an error if any of them fails or
+    their answers do not all agree.
+
+    # Examples
+
+    ```
+    let implementations = alternatives(5);
+    let named: Vec<(&str, &dyn Solver)> = implementations.iter().map(|(n, s)| (*n, s.as_ref())).collect();
+    println!("{}", comparison_table(&compare(&named, 2, &input, 3).unwrap()));
+    ```
+    */
+    let mut contenders = Vec::<Contender>::new();
+
+    for &(name, solver) in implementations {
+        let (answer, timing) = timed_runs(solver, part, input, runs)?;
+        contenders.push(Contender { name: name.to_string(), answer, timing });
+    }
+
+    match contenders.iter().find(|c| c.answer != contenders[0].answer) {
+        Some(c) => Err(AocError::InvalidArgument(format!(
+            "The implementations disagree on part {}: '{}' gives {} but '{}' gives {}",
+            part, contenders[0].name, contenders[0].answer, c.name, c.answer
+        ))),
+        None => Ok(contenders)
+    }
+}
+
+pub fn comparison_table(contenders: &[Contender]) -> String {
+    /* Lay out the timings of the implementations of a part, with each median relative to the fastest. */
+    let fastest = contenders.iter().map(|c| c.timing.median).min().unwrap_or_default();
+
+    let mut rows = format!("{:<12}  {:>16}  {:>11}  {:>11}  {:>11}  {:>9}\n", "Solver", "Answer", "Min", "Median", "Max", "Relative");
+    for contender in contenders {
+        let relative = if fastest.is_zero() {1.0} else {contender.timing.median.as_secs_f64() / fastest.as_secs_f64()};
+        rows.push_str(&format!(
+            "{:<12}  {:>16}  {:>11}  {:>11}  {:>11}  {:>8.1}x\n",
+            contender.name, contender.answer.to_string(),
+            format_duration(contender.timing.min), format_duration(contender.timing.median), format_duration(contender.timing.max),
+            relative
+        ));
+    }
+
+    rows
+}
+
+pub(crate) fn format_duration(duration: Duration) -> String {
+    /* Format a duration in the largest unit in which it is at least one. */
+    let seconds = duration.as_secs_f64();
+    if seconds >= 1.0 {
+        format!("{:.3} s", seconds)
+    } else if seconds >= 1e-3 {
+        format!("{:.3} ms", seconds * 1e3)
+    } else {
+        format!("{:.3} µs", seconds * 1e6)
+    }
+}
+
+pub fn table(timings: &[PartTiming]) -> String {
+    /* Lay out timings as a table with a row for each day and part.
+
+    # Examples
+
+    ```
+    Day  Part  Runs          Min       Median          Max
+      5     2    10     1.201 ms     1.342 ms     2.004 ms
+    ```
+
+    With the `profiling` feature the number of allocations and bytes allocated are
+    added as the last two columns.
+    */
+    let mut rows = format!("{:>3}  {:>4}  {:>4}  {:>11}  {:>11}  {:>11}", "Day", "Part", "Runs", "Min", "Median", "Max");
+    #[cfg(feature = "profiling")]
+    rows.push_str(&format!("  {:>10}  {:>12}", "Allocs", "Bytes"));
+    rows.push('\n');
+
+    for timing in timings {
+        rows.push_str(&format!(
+            "{:>3}  {:>4}  {:>4}  {:>11}  {:>11}  {:>11}",
+            timing.day, timing.part, timing.runs,
+            format_duration(timing.min), format_duration(timing.median), format_duration(timing.max)
+        ));
+        #[cfg(feature = "profiling")]
+        rows.push_str(&format!("  {:>10}  {:>12}", timing.heap.allocations, timing.heap.bytes_allocated));
+        rows.push('\n');
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solver::solver;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_time_part() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let input = std::fs::read_to_string(test_dir.join("day_4.dat")).unwrap();
+        let day_4 = solver(4).unwrap();
+
+        let timing = time_part(day_4.as_ref(), 2, &input, 4).unwrap();
+        assert_eq!((timing.day, timing.part, timing.runs), (4, 2, 4));
+        assert!(timing.min <= timing.median && timing.median <= timing.max);
+        #[cfg(feature = "profiling")]
+        assert!(timing.heap.allocations > 0 && timing.heap.bytes_allocated > 0);
+
+        assert!(time_part(day_4.as_ref(), 1, &input, 0).is_err());
+        assert!(time_part(day_4.as_ref(), 3, &input, 1).is_err());
+    }
+
+    #[test]
+    fn test_bench_all() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+
+        // Day 1 has no day_1.dat test input so is skipped
+        let timings = bench_all(&InputSet::from_dir(&test_dir).unwrap(), 2).unwrap();
+        let days_and_parts: Vec<(u32, u32)> = timings.iter().map(|t| (t.day, t.part)).collect();
+        assert_eq!(days_and_parts, vec![(2, 1), (2, 2), (3, 1), (3, 2), (4, 1), (4, 2), (5, 1), (5, 2)]);
+
+        let rows = table(&timings);
+        assert_eq!(rows.lines().count(), 9);
+        assert!(rows.lines().next().unwrap().trim_start().starts_with("Day  Part  Runs"));
+
+        if cfg!(feature = "profiling") {
+            assert!(rows.lines().next().unwrap().ends_with("Allocs         Bytes"));
+        } else {
+            assert!(rows.lines().next().unwrap().ends_with("Max"));
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let input = std::fs::read_to_string(test_dir.join("day_5.dat")).unwrap();
+
+        // The GPU implementation needs an adapter, which not every machine running the tests has, see gpu::test
+        let implementations = crate::solver::alternatives(5);
+        let named: Vec<(&str, &dyn Solver)> = implementations.iter().filter(|(n, _)| *n != "gpu").map(|(n, s)| (*n, s.as_ref())).collect();
+        let contenders = compare(&named, 2, &input, 2).unwrap();
+        assert_eq!(contenders.iter().map(|c| c.name.as_str()).collect::<Vec<&str>>(), vec!["default", "bruteforce"]);
+        assert!(contenders.iter().all(|c| c.answer == 46));
+
+        let rows = comparison_table(&contenders);
+        assert_eq!(rows.lines().count(), 3);
+        assert!(rows.lines().nth(1).unwrap().starts_with("default"));
+
+        // Day 4 solves part 2 differently, so standing it in for day 5 makes the answers differ
+        let day_4 = solver(4).unwrap();
+        let disagreeing = compare(&[("default", named[0].1), ("day 4", day_4.as_ref())], 2, &input, 1);
+        assert!(disagreeing.unwrap_err().to_string().contains("disagree"));
+
+        // Each implementation is only solved for its timed runs, and the extra run counting allocations
+        let counted = CountingSolver { inner: solver(5).unwrap(), solves: AtomicUsize::new(0) };
+        compare(&[("counted", &counted)], 2, &input, 3).unwrap();
+        assert_eq!(counted.solves.load(Ordering::Relaxed), if cfg!(feature = "profiling") {4} else {3});
+    }
+
+    struct CountingSolver {
+        inner: Box<dyn Solver>,
+        solves: AtomicUsize
+    }
+
+    impl Solver for CountingSolver {
+        fn day(&self) -> u32 {
+            self.inner.day()
+        }
+
+        fn part1(&self, input: &str) -> Result<Answer, AocError> {
+            self.solves.fetch_add(1, Ordering::Relaxed);
+            self.inner.part1(input)
+        }
+
+        fn part2(&self, input: &str) -> Result<Answer, AocError> {
+            self.solves.fetch_add(1, Ordering::Relaxed);
+            self.inner.part2(input)
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(250)), "250.000 µs");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.500 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.500 s");
+    }
+}
@@ -0,0 +1,1676 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use aoc23::config::Config;
+use aoc23::day_2::{Color, Day2};
+use aoc23::day_3::Day3;
+use aoc23::day_4::{CardOptions, Day4, DuplicateNumbers, Scoring};
+use aoc23::day_5::Day5;
+use aoc23::logging::LogFormat;
+use aoc23::outcome::Outcome;
+use aoc23::progress::ProgressEvent;
+use aoc23::util::io::InputSource;
+use aoc23::Solver;
+
+const USAGE: &str = "Usage: aoc23 [--log-format text|json] [--offline] [--profile NAME] COMMAND
+    aoc23 run [--year Y] [--day N] [--part 1|2] [--input FILE] [--progress] [--mmap] [--explain]
+              [--record [--answers FILE]] [--plugin LIB]
+              [--cubes R,G,B] [--gear-symbol C] [--scoring doubling|count]
+              [--duplicates each|distinct] [--seed-ranges]
+    aoc23 all [--year Y] [--data DIR | --bundle FILE] [--parallel] [--threads N] [--fetch]
+              [--max-time MS] [--max-memory MB]
+    aoc23 bench [--year Y] [--runs N] [--data DIR]
+    aoc23 compare --day N [--part 1|2] [--input FILE] [--runs N]
+                  [--plugin LIB]...
+    aoc23 verify [--year Y] [--answers FILE] [--data DIR] [--record]
+    aoc23 crosscheck [--year Y] [--data DIR] [--day N]... [--runs N]
+                     [--reference NAME]...
+    aoc23 stats [--year Y]
+    aoc23 submit --day N [--part 1|2] [--year Y] [--answer A]
+    aoc23 report [--year Y] [--output FILE]
+    aoc23 selftest
+    aoc23 list [--year Y]
+    aoc23 new-day --day N
+    aoc23 tui [--data DIR]
+    aoc23 gui [--data DIR]
+    aoc23 repl [--day N] [--input FILE]
+    aoc23 watch [--year Y] [--dir DIR] [--interval MS]
+    aoc23 wait --day N [--year Y] [--data DIR] [--scaffold]
+    aoc23 encrypt [--year Y] [--data DIR] | --new-key
+
+The year defaults to 2023. Without --part both parts are run. The input defaults
+to day_N.dat in the data directory of the year (data for 2023), which is
+downloaded if missing using the session token in AOC_SESSION. Without --day the
+day is found from the name of the --input file, such as day_07.txt or
+2023-12-07.txt or those matching day_patterns in the configuration, or else
+from its content. With --progress the progress of the solver is shown on
+stderr. With --mmap, available when built with the mmap feature, the input is
+memory mapped rather than read into memory. With --explain each answer is
+preceded by a walkthrough of how it was found, for days 2 and 5 of 2023. When
+built with the archive feature, --input can name a file within a zip or tar
+archive, as in inputs.zip!/day_7.txt. When built with the gzip feature, inputs
+compressed with gzip are decompressed as they are read, by every command.
+
+The puzzles of 2023 take options changing their rules: --cubes sets the cubes
+in the bag for day 2, --gear-symbol the symbol marking gears for day 3,
+--scoring how day 4 part 1 scores matches and --duplicates whether repeated
+numbers match more than once, and --seed-ranges reads the seeds of day 5 as
+ranges in part 1 as well.
+
+Settings are read from aoc23/config.toml in the user's configuration directory,
+where data_dir replaces the default data directory of every command. Logs are
+written to stderr as text, or as one JSON object per line with --log-format
+json, which can also be set with AOC23_LOG_FORMAT or log_format. With --offline,
+or offline = true in the configuration, nothing is downloaded from
+adventofcode.com, so inputs must already be on disk or in the cache.
+--profile NAME uses the session, data directory and cache of the profile NAME
+given by a [profiles.NAME] table of the configuration, e.g. for another account.
+The answers and times of every all and run are kept in results.jsonl in the
+results_dir of the configuration, or in aoc23 in the user's data directory
+($XDG_DATA_HOME, or ~/.local/share). When built with the fetch feature, they
+are also posted to the url of a [webhook] table of the configuration once they
+finish, as JSON or as a Slack or Discord message, see src/notify.rs.
+
+all, bench, verify and crosscheck work on the days of the year given with
+--year, 2023 by default, reading the inputs from DIR, the data directory of the
+year unless given.
+
+all solves every day with an input in DIR and prints the answers and times as
+JSON. With --bundle the inputs are instead read from the sections of FILE, each
+starting with a header line such as '## day 3'. With --parallel, available when
+built with the parallel feature, the days are solved at the same time, on the
+threads set in the configuration if any. --threads N solves them on a pool of N
+threads, or one after the other when N is 0. With --fetch, available when built
+with the async feature, missing inputs are downloaded while the days which have
+one are solved. --max-time and --max-memory, or max_time_ms and max_memory_mb in
+the configuration, limit the wall time and process memory allowed for each day,
+which are then solved one after the other. A day over the limit is abandoned
+and the run stops there, printing the results so far and failing with the day
+which was abandoned and those which were not run.
+
+bench solves each part of every day with an input in DIR N times (default 10)
+and prints the minimum, median and maximum times.
+
+compare solves a part of a day of 2023 (default 2) with each of its
+implementations, such as the range based and brute force solutions of day 5,
+N times (default 3), failing if they disagree on the answer and otherwise
+printing the times of each. The input defaults to that of run. When built with
+the gpu feature, day 5 is also solved by mapping every seed on the GPU.
+
+When built with the plugins feature, --plugin LIB loads the solver of a day
+from the shared library LIB, see src/plugin.rs for what it must export. run
+solves the day with it rather than the built in solver, and compare times it
+alongside the built in implementations, once for each --plugin given.
+
+verify checks the answers for the inputs in DIR against those
+in FILE (default answers.toml), failing if any differ. With --record the
+answers of parts which have none in FILE are added to it, as are those found
+by run --record, creating FILE if need be.
+
+crosscheck solves both parts of every day with an input in DIR, or of each
+--day given, with the solver of the crate and with each reference
+implementation in the [references] table of the configuration, or those named
+with --reference, N times (default 3). Each reference is a command run with
+'{day}' in its arguments replaced by the day and the part added as its last
+argument, given the input on stdin and writing the answer as the last line of
+its output. The answers and median times of each are printed, and the command
+fails if any two answers differ.
+
+When built with the bundle feature, the inputs of 2023 chosen at build time are
+built into the binary and used for the days whose input is not on disk, by run
+as well as all, bench, verify and crosscheck.
+
+stats prints, for each day of the year in the runs which have been kept, how
+many runs included it, when it was first solved without a part failing, and
+the best time of each part.
+
+submit, available when built with the fetch feature, submits A as the answer
+to a part of a day (default 1) and prints the verdict of the site, failing if
+it was not accepted. Every answer submitted is kept with the verdict alongside
+the results, and an answer already rejected, or outside the bounds set by those
+found too high or too low, is refused without being sent. Without --answer the
+answers submitted for the part are listed. run notes what the answers
+submitted before say of each answer it finds.
+
+report writes FILE (default report.html), a web page of the runs of the year
+which have been kept, with the latest answer and best time of each part and a
+chart for each day of the time taken by each part over the runs.
+
+selftest checks every day of 2023 against the sample inputs built into the
+binary, failing if any part gives the wrong answer or any day has no sample.
+
+new-day creates crates/aoc-core/src/day_N.rs with placeholder solutions and
+data/test/day_N.dat, and declares the day in crates/aoc-core/src/lib.rs. The
+module registers its own solver. The test input is the example from the
+puzzle page of 2023 when it can be downloaded, and is left empty otherwise.
+It is run from the root of the repository.
+
+tui, available when built with the tui feature, shows a dashboard of every
+day with an input in DIR (default data), which can be re-run from there.
+
+gui, available when built with the gui feature, opens a window in which a day
+is chosen and its input, read from DIR (default data), pasted or opened, is
+solved in the background while its progress is shown. The inputs of days 3 and
+5 are drawn as well.
+
+repl starts an interactive session on the days of 2023, loading FILE if given,
+in which an input can be solved, parsed and reloaded after editing. Type 'help'
+for its commands.
+
+watch solves every file dropped into DIR (default inbox), finding its day as
+run does, and prints its answers and writes them to FILE.answers.json beside
+it. DIR is looked at every MS milliseconds (default 1000), and a file is solved
+once it has stopped changing, and again whenever it is changed.
+
+wait, available when built with the fetch feature, counts down to when the
+puzzle of day N unlocks, at midnight US Eastern time, then downloads its input
+to DIR, by default where run reads it. DIR must be given for a year with no
+solved puzzles. With --scaffold the day is then created as by new-day, for 2023
+only.
+
+encrypt, available when built with the encrypt feature, encrypts every input
+in DIR which is not already, with the key in AOC23_INPUT_KEY or input_key in
+the configuration, so that the data directory can be committed. Inputs are
+then decrypted as they are read, and downloaded inputs are written encrypted.
+With --new-key a new random key is printed instead.";
+
+// The rules of the 2023 puzzles which can be changed from the command line
+#[derive(Default)]
+struct DayOptions {
+    cubes: Option<[i32; 3]>,
+    gear_symbol: Option<String>,
+    scoring: Option<Scoring>,
+    duplicates: Option<DuplicateNumbers>,
+    seed_ranges: bool
+}
+
+impl DayOptions {
+    fn given(&self) -> Vec<(&'static str, u32)> {
+        /* The options which have been set, with the day each applies to. */
+        [
+            ("--cubes", 2, self.cubes.is_some()),
+            ("--gear-symbol", 3, self.gear_symbol.is_some()),
+            ("--scoring", 4, self.scoring.is_some()),
+            ("--duplicates", 4, self.duplicates.is_some()),
+            ("--seed-ranges", 5, self.seed_ranges)
+        ]
+        .into_iter()
+        .filter(|&(_, _, set)| set)
+        .map(|(flag, day, _)| (flag, day))
+        .collect()
+    }
+}
+
+struct RunArgs {
+    year: u32,
+    day: u32,
+    parts: Vec<u32>,
+    input: Option<PathBuf>,
+    progress: bool,
+    mmap: bool,
+    explain: bool,
+    record: Option<PathBuf>,
+    plugin: Option<PathBuf>,
+    options: DayOptions
+}
+
+fn parse_cubes(value: &str) -> Result<[i32; 3], String> {
+    let counts: Vec<i32> = match value.split(',').map(|n| n.trim().parse::<i32>()).collect() {
+        Ok(c) => c,
+        Err(e) => return Err(format!("Invalid cube counts '{}': {}", value, e))
+    };
+    match counts[..] {
+        [red, green, blue] => Ok([red, green, blue]),
+        _ => Err(format!("Expected three cube counts for red, green and blue, got '{}'", value))
+    }
+}
+
+fn parse_run_args(args: &[String], config: &Config) -> Result<RunArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut day = None;
+    let mut parts = vec![1, 2];
+    let mut input = None;
+    let mut progress = false;
+    let mut mmap = false;
+    let mut explain = false;
+    let mut record = false;
+    let mut answers = PathBuf::from("answers.toml");
+    let mut plugin = None;
+    let mut options = DayOptions::default();
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        match flag.as_str() {
+            "--progress" => {
+                progress = true;
+                continue;
+            },
+            "--mmap" => {
+                mmap = true;
+                continue;
+            },
+            "--explain" => {
+                explain = true;
+                continue;
+            },
+            "--seed-ranges" => {
+                options.seed_ranges = true;
+                continue;
+            },
+            "--record" => {
+                record = true;
+                continue;
+            },
+            _ => ()
+        };
+
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--year" => year = parse_year(value)?,
+            "--day" => day = match value.parse::<u32>() {
+                Ok(d) => Some(d),
+                Err(e) => return Err(format!("Invalid day '{}': {}", value, e))
+            },
+            "--part" => parts = match value.parse::<u32>() {
+                Ok(p) => vec![p],
+                Err(e) => return Err(format!("Invalid part '{}': {}", value, e))
+            },
+            "--input" => input = Some(PathBuf::from(value)),
+            "--answers" => answers = PathBuf::from(value),
+            "--plugin" => plugin = Some(PathBuf::from(value)),
+            "--cubes" => options.cubes = Some(parse_cubes(value)?),
+            "--gear-symbol" => options.gear_symbol = Some(value.clone()),
+            "--scoring" => options.scoring = match value.as_str() {
+                "doubling" => Some(Scoring::Doubling),
+                "count" => Some(Scoring::Count),
+                _ => return Err(format!("Unknown scoring '{}', expected 'doubling' or 'count'", value))
+            },
+            "--duplicates" => options.duplicates = match value.as_str() {
+                "each" => Some(DuplicateNumbers::EachOccurrence),
+                "distinct" => Some(DuplicateNumbers::Distinct),
+                _ => return Err(format!("Unknown duplicates '{}', expected 'each' or 'distinct'", value))
+            },
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    let day = match (day, &input) {
+        (Some(d), _) => d,
+        (None, Some(i)) => detect_day(i, config)?,
+        (None, None) => return Err("The day to run must be given with --day, or found from the input given with --input".to_string())
+    };
+
+    // Answers found under other rules are not the answers to the puzzle
+    if record && !options.given().is_empty() {
+        return Err("--record keeps the answers to the puzzle as set, so cannot be given with options changing its rules".to_string());
+    }
+    let record = if record {Some(answers)} else {None};
+
+    // The options and explanations are those of the built in solvers
+    if plugin.is_some() && (explain || !options.given().is_empty()) {
+        return Err("--plugin runs the solver of the plugin, so cannot be given with --explain or the options of a day".to_string());
+    }
+
+    Ok(RunArgs { year, day, parts, input, progress, mmap, explain, record, plugin, options })
+}
+
+fn detect_day(input: &Path, config: &Config) -> Result<u32, String> {
+    /* The day of an input from its file name, matched against the configured patterns, or else its content. */
+    // The content is only read when the name gives no day, as run reads the input again, perhaps memory mapped
+    let day = match config.day_detector()?.from_file_name(input) {
+        Some(d) => Some(d),
+        None => match read_input(input, false)?.as_str() {
+            Ok(content) => aoc23::detect::sniff_day(content),
+            Err(e) => return Err(aoc23::AocError::from(e).to_string())
+        }
+    };
+
+    match day {
+        Some(d) => {
+            tracing::info!("Running day {} for '{}'", d, input.display());
+            Ok(d)
+        },
+        None => Err(format!("Could not tell the day of '{}' from its name or content, give it with --day", input.display()))
+    }
+}
+
+fn parse_year(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(y) => Ok(y),
+        Err(e) => Err(format!("Invalid year '{}': {}", value, e))
+    }
+}
+
+fn find_event(year: u32) -> Result<aoc23::Event, String> {
+    match aoc23::event(year) {
+        Some(e) => Ok(e),
+        None => Err(format!("No puzzles have been solved for {}", year))
+    }
+}
+
+fn event_data_dir(event: &aoc23::Event, data_dir: &Option<PathBuf>, config: &Config) -> PathBuf {
+    // --data wins over the configured directory, which wins over that of the event
+    match (data_dir, &config.data_dir) {
+        (Some(d), _) | (None, Some(d)) => d.clone(),
+        (None, None) => PathBuf::from(event.data_dir)
+    }
+}
+
+struct BenchArgs {
+    year: u32,
+    runs: usize,
+    data_dir: Option<PathBuf>
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut runs = 10;
+    let mut data_dir = None;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--runs" => runs = match value.parse::<usize>() {
+                Ok(r) => r,
+                Err(e) => return Err(format!("Invalid number of runs '{}': {}", value, e))
+            },
+            "--year" => year = parse_year(value)?,
+            "--data" => data_dir = Some(PathBuf::from(value)),
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    Ok(BenchArgs { year, runs, data_dir })
+}
+
+struct CompareArgs {
+    day: u32,
+    part: u32,
+    input: Option<PathBuf>,
+    runs: usize,
+    plugins: Vec<PathBuf>
+}
+
+fn parse_compare_args(args: &[String]) -> Result<CompareArgs, String> {
+    let mut day = None;
+    let mut part = 2;
+    let mut input = None;
+    let mut runs = 3;
+    let mut plugins = Vec::<PathBuf>::new();
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--day" => day = match value.parse::<u32>() {
+                Ok(d) => Some(d),
+                Err(e) => return Err(format!("Invalid day '{}': {}", value, e))
+            },
+            "--part" => part = match value.parse::<u32>() {
+                Ok(p) => p,
+                Err(e) => return Err(format!("Invalid part '{}': {}", value, e))
+            },
+            "--input" => input = Some(PathBuf::from(value)),
+            "--runs" => runs = match value.parse::<usize>() {
+                Ok(r) => r,
+                Err(e) => return Err(format!("Invalid number of runs '{}': {}", value, e))
+            },
+            "--plugin" => plugins.push(PathBuf::from(value)),
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    match day {
+        Some(day) => Ok(CompareArgs { day, part, input, runs, plugins }),
+        None => Err("The day to compare must be given with --day".to_string())
+    }
+}
+
+struct AllArgs {
+    year: u32,
+    data_dir: Option<PathBuf>,
+    bundle: Option<PathBuf>,
+    parallel: bool,
+    threads: Option<usize>,
+    fetch: bool,
+    max_time_ms: Option<u64>,
+    max_memory_mb: Option<u64>
+}
+
+fn parse_all_args(args: &[String]) -> Result<AllArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut data_dir = None;
+    let mut bundle = None;
+    let mut parallel = false;
+    let mut threads = None;
+    let mut fetch = false;
+    let mut max_time_ms = None;
+    let mut max_memory_mb = None;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        match flag.as_str() {
+            "--parallel" => parallel = true,
+            "--fetch" => fetch = true,
+            "--year" => year = match iter.next() {
+                Some(v) => parse_year(v)?,
+                None => return Err(format!("Missing value for '{}'", flag))
+            },
+            "--data" => data_dir = match iter.next() {
+                Some(v) => Some(PathBuf::from(v)),
+                None => return Err(format!("Missing value for '{}'", flag))
+            },
+            "--bundle" => bundle = match iter.next() {
+                Some(v) => Some(PathBuf::from(v)),
+                None => return Err(format!("Missing value for '{}'", flag))
+            },
+            "--threads" => threads = match iter.next().map(|v| (v, v.parse::<usize>())) {
+                Some((_, Ok(n))) => Some(n),
+                Some((v, Err(e))) => return Err(format!("Invalid number of threads '{}': {}", v, e)),
+                None => return Err(format!("Missing value for '{}'", flag))
+            },
+            "--max-time" => max_time_ms = match iter.next().map(|v| (v, v.parse::<u64>())) {
+                Some((_, Ok(ms))) => Some(ms),
+                Some((v, Err(e))) => return Err(format!("Invalid time limit '{}': {}", v, e)),
+                None => return Err(format!("Missing value for '{}'", flag))
+            },
+            "--max-memory" => max_memory_mb = match iter.next().map(|v| (v, v.parse::<u64>())) {
+                Some((_, Ok(mb))) => Some(mb),
+                Some((v, Err(e))) => return Err(format!("Invalid memory limit '{}': {}", v, e)),
+                None => return Err(format!("Missing value for '{}'", flag))
+            },
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    if bundle.is_some() && (data_dir.is_some() || fetch) {
+        return Err("--bundle reads every input from one file, so cannot be given with --data or --fetch".to_string());
+    }
+
+    Ok(AllArgs { year, data_dir, bundle, parallel, threads, fetch, max_time_ms, max_memory_mb })
+}
+
+#[cfg(feature = "async")]
+fn fetch_and_run(event: &aoc23::Event, data_dir: &Path, config: &Config) -> Result<Vec<aoc23::DayResult>, String> {
+    match tokio::runtime::Runtime::new() {
+        Ok(runtime) => Ok(runtime.block_on(aoc23::async_runner::run_event(event, data_dir, config))),
+        Err(e) => Err(format!("Failed to start the async runtime: {}", e))
+    }
+}
+
+#[cfg(not(feature = "async"))]
+fn fetch_and_run(_event: &aoc23::Event, _data_dir: &Path, _config: &Config) -> Result<Vec<aoc23::DayResult>, String> {
+    Err("Downloading inputs while solving needs the async feature".to_string())
+}
+
+fn all(args: &AllArgs, config: &Config) -> Result<(), String> {
+    let event = find_event(args.year)?;
+    let data_dir = event_data_dir(&event, &args.data_dir, config);
+
+    // Limits on the command line win over those in the configuration
+    let limits = Config {
+        max_time_ms: args.max_time_ms.or(config.max_time_ms),
+        max_memory_mb: args.max_memory_mb.or(config.max_memory_mb),
+        ..Config::default()
+    };
+    let budget = limits.budget();
+    let budgeted = budget != aoc23::budget::Budget::default();
+
+    if budgeted && (args.fetch || args.parallel || args.threads.is_some_and(|n| n > 0)) {
+        return Err("Days solved within a time or memory limit are run one at a time, without --fetch, --parallel or --threads".to_string());
+    }
+
+    let start = Instant::now();
+    let mut abandoned = None;
+    let results = if args.fetch {
+        // Offline, inputs missing from both the data directory and the cache are reported as errors
+        fetch_and_run(&event, &data_dir, config)?
+    } else {
+        let inputs = match &args.bundle {
+            Some(b) => aoc23::InputSet::from_bundle_for_year(args.year, b)?,
+            None => event_inputs(&event, &data_dir)?
+        };
+
+        // An explicit thread count wins, --parallel alone uses the configured count or rayon's default
+        let threads = match (args.threads, args.parallel) {
+            (Some(n), _) => Some(n),
+            (None, true) => config.threads,
+            (None, false) => Some(0)
+        };
+
+        if budgeted {
+            let (results, over_budget) = aoc23::runner::run_all_with_budget(&inputs, &budget);
+            abandoned = over_budget;
+            results
+        } else {
+            solve_all(&inputs, threads)?
+        }
+    };
+
+    print_results(&results)?;
+    finish(config, aoc23::notify::Completion { command: "all".to_string(), year: event.year, elapsed: start.elapsed(), results });
+
+    // The abandoned solver is still running, so the run ends here with an error rather than going on to other work
+    match abandoned {
+        Some(a) => Err(a.to_string()),
+        None => Ok(())
+    }
+}
+
+fn finish(config: &Config, completion: aoc23::notify::Completion) {
+    /* Keep a finished run in the results store and post it to the webhook of the configuration, only warning if either fails. */
+    let record = aoc23::store::RunRecord::now(&completion.command, completion.year, config.profile.as_deref(), completion.results.clone());
+    let store = config.results_store();
+    if let Err(e) = store.append(&record) {
+        tracing::warn!("Failed to keep the results in '{}': {}", store.root().display(), e);
+    }
+
+    if let Some(webhook) = &config.webhook {
+        if let Err(e) = aoc23::notify::send(webhook, &completion) {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn solve_all(inputs: &aoc23::InputSet, threads: Option<usize>) -> Result<Vec<aoc23::DayResult>, String> {
+    match threads {
+        Some(n) => Ok(aoc23::runner::run_all_with_threads(inputs, n)?),
+        None => Ok(aoc23::runner::run_all_parallel(inputs))
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn solve_all(inputs: &aoc23::InputSet, threads: Option<usize>) -> Result<Vec<aoc23::DayResult>, String> {
+    match threads {
+        Some(0) => Ok(aoc23::run_all(inputs)),
+        _ => Err("Solving in parallel needs the parallel feature".to_string())
+    }
+}
+
+fn print_results(results: &[aoc23::DayResult]) -> Result<(), String> {
+    match serde_json::to_string_pretty(results) {
+        Ok(json) => println!("{}", json),
+        Err(e) => return Err(format!("Failed to write results: {}", e))
+    };
+    Ok(())
+}
+
+struct VerifyArgs {
+    year: u32,
+    answers: PathBuf,
+    data_dir: Option<PathBuf>,
+    record: bool
+}
+
+fn parse_verify_args(args: &[String]) -> Result<VerifyArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut answers = PathBuf::from("answers.toml");
+    let mut data_dir = None;
+    let mut record = false;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        if flag == "--record" {
+            record = true;
+            continue;
+        }
+
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--year" => year = parse_year(value)?,
+            "--answers" => answers = PathBuf::from(value),
+            "--data" => data_dir = Some(PathBuf::from(value)),
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    Ok(VerifyArgs { year, answers, data_dir, record })
+}
+
+fn verify(args: &VerifyArgs, config: &Config) -> Result<(), String> {
+    let event = find_event(args.year)?;
+    let mut key = load_answers(&args.answers, args.record)?;
+    let results = aoc23::run_all(&event_inputs(&event, &event_data_dir(&event, &args.data_dir, config))?);
+    let checks = aoc23::verify::check(&results, &key);
+    print!("{}", aoc23::verify::report(&checks));
+
+    if args.record {
+        let recorded = key.record_results(&results);
+        save_answers(&key, &args.answers, &recorded)?;
+    }
+
+    let failed = checks.iter().filter(|c| !c.passed()).count();
+    if failed > 0 {
+        return Err(format!("{} of {} parts did not match the expected answers", failed, checks.len()));
+    }
+    Ok(())
+}
+
+struct CrossCheckArgs {
+    year: u32,
+    data_dir: Option<PathBuf>,
+    days: Vec<u32>,
+    runs: usize,
+    references: Vec<String>
+}
+
+fn parse_crosscheck_args(args: &[String]) -> Result<CrossCheckArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut data_dir = None;
+    let mut days = Vec::<u32>::new();
+    let mut runs = 3;
+    let mut references = Vec::<String>::new();
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--year" => year = parse_year(value)?,
+            "--data" => data_dir = Some(PathBuf::from(value)),
+            "--day" => match value.parse::<u32>() {
+                Ok(d) => days.push(d),
+                Err(e) => return Err(format!("Invalid day '{}': {}", value, e))
+            },
+            "--runs" => runs = match value.parse::<usize>() {
+                Ok(r) => r,
+                Err(e) => return Err(format!("Invalid number of runs '{}': {}", value, e))
+            },
+            "--reference" => references.push(value.clone()),
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    Ok(CrossCheckArgs { year, data_dir, days, runs, references })
+}
+
+fn crosscheck(args: &CrossCheckArgs, config: &Config) -> Result<(), String> {
+    let event = find_event(args.year)?;
+
+    // Every configured reference unless some are named
+    let references: Vec<(String, Vec<String>)> = if args.references.is_empty() {
+        config.references.iter().map(|(name, command)| (name.clone(), command.clone())).collect()
+    } else {
+        let mut chosen = Vec::<(String, Vec<String>)>::new();
+        for name in &args.references {
+            match config.references.get(name) {
+                Some(command) => chosen.push((name.clone(), command.clone())),
+                None => return Err(format!("No reference '{}' in the [references] table of the configuration", name))
+            };
+        }
+        chosen
+    };
+    if references.is_empty() {
+        return Err("No reference implementations to check against, add them to a [references] table of the configuration".to_string());
+    }
+
+    let inputs = event_inputs(&event, &event_data_dir(&event, &args.data_dir, config))?;
+    let checks = aoc23::reference::cross_check(&inputs, &references, &args.days, args.runs)?;
+    print!("{}", aoc23::reference::report(&checks));
+
+    let differing = checks.iter().filter(|c| !c.agrees()).count();
+    if differing > 0 {
+        return Err(format!("The implementations disagree on {} of {} parts", differing, checks.len()));
+    }
+    Ok(())
+}
+
+fn load_answers(answers: &Path, record: bool) -> Result<aoc23::verify::AnswerKey, String> {
+    /* The expected answers, none if the file does not exist yet and answers are being recorded. */
+    if record && !answers.exists() {
+        return Ok(aoc23::verify::AnswerKey::default());
+    }
+    Ok(aoc23::verify::AnswerKey::from_file(answers)?)
+}
+
+fn save_answers(key: &aoc23::verify::AnswerKey, answers: &Path, recorded: &[(u32, u32)]) -> Result<(), String> {
+    // The file is only rewritten when there is something new, keeping any comments otherwise
+    if recorded.is_empty() {
+        return Ok(());
+    }
+    key.save(answers)?;
+    for (day, part) in recorded {
+        println!("Recorded day {} part {} in {}", day, part, answers.display());
+    }
+    Ok(())
+}
+
+fn bench(args: &BenchArgs, config: &Config) -> Result<(), String> {
+    let event = find_event(args.year)?;
+    let inputs = event_inputs(&event, &event_data_dir(&event, &args.data_dir, config))?;
+    let timings = aoc23::bench::bench_all(&inputs, args.runs)?;
+    print!("{}", aoc23::bench::table(&timings));
+    Ok(())
+}
+
+fn compare(args: &CompareArgs, config: &Config) -> Result<(), String> {
+    let event = find_event(aoc23::event::DEFAULT_YEAR)?;
+    let implementations = aoc23::solver::alternatives(args.day);
+    let plugins = load_plugins(&args.plugins, args.day)?;
+
+    let mut named: Vec<(&str, &dyn Solver)> = implementations.iter().map(|(name, s)| (*name, s.as_ref())).collect();
+    named.extend(plugins.iter().map(|(name, s)| (name.as_str(), s.as_ref())));
+    match named.len() {
+        0 => return Err(format!("Day {} of {} has not been solved", args.day, event.year)),
+        1 => return Err(format!("Day {} of {} has only one implementation to compare", args.day, event.year)),
+        _ => ()
+    };
+
+    let input = match &args.input {
+        Some(i) => i.clone(),
+        None => event_data_dir(&event, &None, config).join(format!("day_{}.dat", args.day))
+    };
+
+    #[cfg(feature = "fetch")]
+    if args.input.is_none() {
+        aoc23::fetch::ensure_input(event.year, args.day, &input, config)?;
+    }
+
+    let source = read_input(&input, false)?;
+    let puzzle_input = match source.as_str() {
+        Ok(contents) => contents,
+        Err(e) => return Err(aoc23::AocError::from(e).to_string())
+    };
+
+    let contenders = aoc23::bench::compare(&named, args.part, puzzle_input, args.runs)?;
+    print!("{}", aoc23::bench::comparison_table(&contenders));
+    Ok(())
+}
+
+fn selftest(args: &[String]) -> Result<(), String> {
+    if !args.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    let checks = aoc23::selftest::selftest();
+    print!("{}", aoc23::verify::report(&checks));
+
+    let failed = checks.iter().filter(|c| !c.passed()).count();
+    if failed > 0 {
+        return Err(format!("{} of {} parts failed on their sample input", failed, checks.len()));
+    }
+
+    // A new day is only checked once its example is added, so until then the self test fails
+    let untested = aoc23::selftest::untested_days();
+    if !untested.is_empty() {
+        let days: Vec<String> = untested.iter().map(|d| d.to_string()).collect();
+        return Err(format!("No sample answers for both parts of day {}, add them to EXAMPLES in src/selftest.rs", days.join(", ")));
+    }
+    Ok(())
+}
+
+fn repl(args: &[String], config: &Config) -> Result<(), String> {
+    use std::io::{BufRead, Write};
+    use aoc23::repl::Reply;
+
+    let mut session = aoc23::repl::Session::new(config.day_detector()?);
+    // The options are run as the commands they stand for, the day first so that it is kept on loading
+    let mut commands = Vec::<String>::new();
+    for pair in args.chunks(2) {
+        match pair {
+            [flag, value] if flag == "--day" => commands.insert(0, format!("day {}", value)),
+            [flag, value] if flag == "--input" => commands.push(format!("load {}", value)),
+            _ => return Err(USAGE.to_string())
+        };
+    }
+    for command in &commands {
+        match session.execute(command)? {
+            Reply::Output(o) => println!("{}", o),
+            Reply::Quit => return Ok(())
+        };
+    }
+
+    let stdin = std::io::stdin();
+    let mut line = String::new();
+    loop {
+        print!("aoc23> ");
+        if let Err(e) = std::io::stdout().flush() {
+            return Err(format!("Failed to write the prompt: {}", e));
+        }
+
+        line.clear();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) => return Ok(()),
+            Ok(_) => (),
+            Err(e) => return Err(format!("Failed to read a command: {}", e))
+        };
+
+        match session.execute(&line) {
+            Ok(Reply::Output(o)) if o.is_empty() => (),
+            Ok(Reply::Output(o)) => println!("{}", o),
+            Ok(Reply::Quit) => return Ok(()),
+            Err(e) => eprintln!("{}", e)
+        };
+    }
+}
+
+fn parse_list_args(args: &[String]) -> Result<Option<u32>, String> {
+    match args {
+        [] => Ok(None),
+        [flag, value] if flag == "--year" => Ok(Some(parse_year(value)?)),
+        _ => Err(USAGE.to_string())
+    }
+}
+
+fn list(year: Option<u32>) {
+    for event in aoc23::events().iter().filter(|e| year.is_none() || year == Some(e.year)) {
+        for solver in event.solvers() {
+            println!("{} day {}", event.year, solver.day());
+        }
+    }
+}
+
+fn parse_new_day_args(args: &[String]) -> Result<u32, String> {
+    match args {
+        [flag, value] if flag == "--day" => match value.parse::<u32>() {
+            Ok(d) => Ok(d),
+            Err(e) => Err(format!("Invalid day '{}': {}", value, e))
+        },
+        _ => Err(USAGE.to_string())
+    }
+}
+
+#[cfg(feature = "fetch")]
+fn fetch_example(day: u32, test_file: &Path, config: &Config) -> Result<bool, String> {
+    Ok(aoc23::fetch::ensure_example(aoc23::event::DEFAULT_YEAR, day, test_file, config)?)
+}
+
+#[cfg(not(feature = "fetch"))]
+fn fetch_example(_day: u32, _test_file: &Path, _config: &Config) -> Result<bool, String> {
+    Err("Downloading the example needs the fetch feature".to_string())
+}
+
+fn new_day(day: u32, config: &Config) -> Result<(), String> {
+    for file in aoc23::scaffold::new_day(".", day)? {
+        println!("Wrote {}", file.display());
+    }
+
+    // The day is still usable without its example, which can be pasted in by hand
+    let test_file = PathBuf::from("data/test").join(format!("day_{}.dat", day));
+    match fetch_example(day, &test_file, config) {
+        Ok(true) => println!("Wrote the example from the puzzle page to {}", test_file.display()),
+        Ok(false) => (),
+        Err(e) => eprintln!("Could not fill in {}: {}", test_file.display(), e)
+    };
+    Ok(())
+}
+
+#[cfg(feature = "encrypt")]
+struct EncryptArgs {
+    year: u32,
+    data_dir: Option<PathBuf>,
+    new_key: bool
+}
+
+#[cfg(feature = "encrypt")]
+fn parse_encrypt_args(args: &[String]) -> Result<EncryptArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut data_dir = None;
+    let mut new_key = false;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        if flag == "--new-key" {
+            new_key = true;
+            continue;
+        }
+
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--year" => year = parse_year(value)?,
+            "--data" => data_dir = Some(PathBuf::from(value)),
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    if new_key && (data_dir.is_some() || year != aoc23::event::DEFAULT_YEAR) {
+        return Err("--new-key only prints a key, so cannot be given with --year or --data".to_string());
+    }
+    Ok(EncryptArgs { year, data_dir, new_key })
+}
+
+#[cfg(feature = "encrypt")]
+fn encrypt(args: &[String], config: &Config) -> Result<(), String> {
+    use aoc23::util::crypt::{encrypt_file, InputKey};
+
+    let args = parse_encrypt_args(args)?;
+    if args.new_key {
+        println!("{}", InputKey::generate().to_hex());
+        return Ok(());
+    }
+
+    let key = match config.input_key()? {
+        Some(k) => k,
+        None => return Err(format!("No key to encrypt with, set {} or input_key in the configuration", aoc23::config::INPUT_KEY_ENV))
+    };
+
+    let event = find_event(args.year)?;
+    let data_dir = event_data_dir(&event, &args.data_dir, config);
+    for solver in event.solvers() {
+        let input = data_dir.join(format!("day_{}.dat", solver.day()));
+        if !input.exists() {
+            continue;
+        }
+        match encrypt_file(&input, &key) {
+            Ok(true) => println!("Encrypted {}", input.display()),
+            Ok(false) => (),
+            Err(e) => return Err(aoc23::AocError::io(&input, e).to_string())
+        };
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "encrypt"))]
+fn encrypt(_args: &[String], _config: &Config) -> Result<(), String> {
+    Err("Encrypting the inputs needs the encrypt feature".to_string())
+}
+
+struct WatchArgs {
+    year: u32,
+    dir: PathBuf,
+    interval: std::time::Duration
+}
+
+fn parse_watch_args(args: &[String]) -> Result<WatchArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut dir = PathBuf::from("inbox");
+    let mut interval = std::time::Duration::from_millis(1000);
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--year" => year = parse_year(value)?,
+            "--dir" => dir = PathBuf::from(value),
+            "--interval" => interval = match value.parse::<u64>() {
+                Ok(ms) if ms > 0 => std::time::Duration::from_millis(ms),
+                _ => return Err(format!("Invalid interval '{}', expected a number of milliseconds", value))
+            },
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    Ok(WatchArgs { year, dir, interval })
+}
+
+fn parse_stats_args(args: &[String]) -> Result<u32, String> {
+    match args {
+        [] => Ok(aoc23::event::DEFAULT_YEAR),
+        [flag, value] if flag == "--year" => parse_year(value),
+        _ => Err(USAGE.to_string())
+    }
+}
+
+fn stats(year: u32, config: &Config) -> Result<(), String> {
+    let store = config.results_store();
+    let runs = store.runs(year, config.profile.as_deref())?;
+    let (first, last) = match (runs.first(), runs.last()) {
+        (Some(f), Some(l)) => (f.timestamp, l.timestamp),
+        _ => return Err(format!("No runs of {} have been kept in '{}', run all or run first", year, store.results_path().display()))
+    };
+
+    let stats = aoc23::stats::day_stats(&runs);
+    let solved = stats.iter().filter(|s| s.first_solved.is_some()).count();
+    println!(
+        "{} runs of {} from {} to {} UTC, {} of {} days solved\n",
+        runs.len(), year, aoc23::store::format_timestamp(first), aoc23::store::format_timestamp(last), solved, stats.len()
+    );
+    print!("{}", aoc23::stats::table(&stats));
+    Ok(())
+}
+
+struct SubmitArgs {
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: Option<String>
+}
+
+fn parse_submit_args(args: &[String]) -> Result<SubmitArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut day = None;
+    let mut part = 1;
+    let mut answer = None;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--year" => year = parse_year(value)?,
+            "--day" => day = match value.parse::<u32>() {
+                Ok(d) => Some(d),
+                Err(e) => return Err(format!("Invalid day '{}': {}", value, e))
+            },
+            "--part" => part = match value.parse::<u32>() {
+                Ok(p @ 1..=2) => p,
+                _ => return Err(format!("Invalid part '{}', expected 1 or 2", value))
+            },
+            "--answer" => answer = Some(value.clone()),
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    match day {
+        Some(day) => Ok(SubmitArgs { year, day, part, answer }),
+        None => Err("The day to submit must be given with --day".to_string())
+    }
+}
+
+fn submit(args: &SubmitArgs, config: &Config) -> Result<(), String> {
+    let store = config.results_store();
+    let history = |store: &aoc23::store::ResultsStore| -> Result<aoc23::submit::History, String> {
+        Ok(aoc23::submit::History::new(&store.submissions()?, args.year, args.day, args.part, config.profile.as_deref()))
+    };
+
+    // Without an answer the answers submitted before are listed
+    let answer = match &args.answer {
+        Some(a) => aoc23::Answer::from_text(a),
+        None => {
+            let history = history(&store)?;
+            if history.submissions().is_empty() {
+                println!("No answers have been submitted for {} day {} part {}", args.year, args.day, args.part);
+            }
+            for submission in history.submissions() {
+                println!("{}  {}  {}", aoc23::store::format_timestamp(submission.timestamp), submission.answer, submission.verdict);
+            }
+            if history.correct().is_none() && !history.bounds().is_empty() {
+                println!("The answer is {}", history.bounds());
+            }
+            return Ok(());
+        }
+    };
+
+    let verdict = aoc23::submit::submit(args.year, args.day, args.part, &answer, config)?;
+    println!("Day {} part {}: {} is {}", args.day, args.part, answer, verdict);
+    if verdict == aoc23::submit::Verdict::Correct {
+        return Ok(());
+    }
+
+    let bounds = history(&store)?.bounds();
+    if !bounds.is_empty() {
+        println!("Day {} part {}: the answer is {}", args.day, args.part, bounds);
+    }
+    Err(format!("{} was not accepted for {} day {} part {}", answer, args.year, args.day, args.part))
+}
+
+struct ReportArgs {
+    year: u32,
+    output: PathBuf
+}
+
+fn parse_report_args(args: &[String]) -> Result<ReportArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut output = PathBuf::from("report.html");
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--year" => year = parse_year(value)?,
+            "--output" => output = PathBuf::from(value),
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    Ok(ReportArgs { year, output })
+}
+
+fn report(args: &ReportArgs, config: &Config) -> Result<(), String> {
+    let store = config.results_store();
+    let runs = store.runs(args.year, config.profile.as_deref())?;
+    if runs.is_empty() {
+        return Err(format!("No runs of {} have been kept in '{}', run all or run first", args.year, store.results_path().display()));
+    }
+
+    match std::fs::write(&args.output, aoc23::report::html_report(args.year, &runs)) {
+        Ok(_) => {
+            println!("Wrote the report of {} runs to '{}'", runs.len(), args.output.display());
+            Ok(())
+        },
+        Err(e) => Err(aoc23::AocError::io(&args.output, e).to_string())
+    }
+}
+
+fn watch(args: &WatchArgs, config: &Config) -> Result<(), String> {
+    let event = find_event(args.year)?;
+    if let Err(e) = std::fs::create_dir_all(&args.dir) {
+        return Err(aoc23::AocError::io(&args.dir, e).to_string());
+    }
+
+    let mut inbox = aoc23::watch::Inbox::new(&args.dir, event.year, config.day_detector()?);
+    tracing::info!("Watching '{}' for inputs of {}", args.dir.display(), event.year);
+
+    loop {
+        for delivery in inbox.poll()? {
+            let name = delivery.input.display();
+            if let Some(error) = &delivery.error {
+                println!("{}: {}", name, error);
+            }
+            for part in &delivery.parts {
+                match (&part.answer, &part.error) {
+                    (Some(answer), _) => println!("{}: day {} part {}: {}", name, delivery.day.unwrap_or_default(), part.part, answer),
+                    (None, Some(error)) => println!("{}: day {} part {} failed: {}", name, delivery.day.unwrap_or_default(), part.part, error),
+                    (None, None) => ()
+                };
+            }
+        }
+        std::thread::sleep(args.interval);
+    }
+}
+
+#[cfg(feature = "fetch")]
+struct WaitArgs {
+    year: u32,
+    day: u32,
+    data_dir: Option<PathBuf>,
+    scaffold: bool
+}
+
+#[cfg(feature = "fetch")]
+fn parse_wait_args(args: &[String]) -> Result<WaitArgs, String> {
+    let mut year = aoc23::event::DEFAULT_YEAR;
+    let mut day = None;
+    let mut data_dir = None;
+    let mut scaffold = false;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        if flag == "--scaffold" {
+            scaffold = true;
+            continue;
+        }
+
+        let value = match iter.next() {
+            Some(v) => v,
+            None => return Err(format!("Missing value for '{}'", flag))
+        };
+        match flag.as_str() {
+            "--year" => year = parse_year(value)?,
+            "--day" => day = match value.parse::<u32>() {
+                Ok(d) => Some(d),
+                Err(e) => return Err(format!("Invalid day '{}': {}", value, e))
+            },
+            "--data" => data_dir = Some(PathBuf::from(value)),
+            _ => return Err(format!("Unknown option '{}'", flag))
+        };
+    }
+
+    // new-day scaffolds the days of this crate, which are those of 2023
+    if scaffold && year != aoc23::event::DEFAULT_YEAR {
+        return Err(format!("--scaffold adds a day of {} to this crate, so cannot be given for {}", aoc23::event::DEFAULT_YEAR, year));
+    }
+
+    match day {
+        Some(day) => Ok(WaitArgs { year, day, data_dir, scaffold }),
+        None => Err("The day to wait for must be given with --day".to_string())
+    }
+}
+
+#[cfg(feature = "fetch")]
+fn wait(args: &[String], config: &Config) -> Result<(), String> {
+    use std::io::Write;
+    use std::time::{Duration, SystemTime};
+
+    let args = parse_wait_args(args)?;
+
+    // The puzzle can take a moment to appear once unlocked, so the download is tried a few times
+    const ATTEMPTS: u32 = 6;
+    const RETRY_AFTER: Duration = Duration::from_secs(5);
+
+    // The puzzles of a new event are waited for before any of them are solved, so it need not be registered yet
+    let data_dir = match (aoc23::event(args.year), &args.data_dir) {
+        (Some(event), _) => event_data_dir(&event, &args.data_dir, config),
+        (None, Some(d)) => d.clone(),
+        (None, None) => return Err(format!("No puzzles have been solved for {}, so the directory for its inputs must be given with --data", args.year))
+    };
+    let input = data_dir.join(format!("day_{}.dat", args.day));
+
+    // Anything stopping the download is reported before waiting rather than at the unlock
+    if aoc23::unlock::until_unlock(args.year, args.day, SystemTime::now())?.is_some() {
+        if aoc23::fetch::is_offline() {
+            return Err("wait downloads the input once the puzzle unlocks, so cannot be used offline".to_string());
+        }
+        aoc23::fetch::session_token(config)?;
+    }
+
+    while let Some(remaining) = aoc23::unlock::until_unlock(args.year, args.day, SystemTime::now())? {
+        eprint!("\rDay {} of {} unlocks in {} ", args.day, args.year, aoc23::unlock::countdown(remaining));
+        let _ = std::io::stderr().flush();
+        // Sleeping to the next whole second keeps the countdown ticking on the second
+        std::thread::sleep(match remaining.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            n => Duration::from_nanos(n as u64)
+        });
+    }
+    eprintln!("\rDay {} of {} is unlocked", args.day, args.year);
+
+    let mut attempt = 1;
+    loop {
+        match aoc23::fetch::ensure_input(args.year, args.day, &input, config) {
+            Ok(true) => println!("Wrote the input for day {} to {}", args.day, input.display()),
+            Ok(false) => println!("The input for day {} is already at {}", args.day, input.display()),
+            Err(e @ aoc23::AocError::Download { .. }) if attempt < ATTEMPTS => {
+                tracing::warn!(attempt, "{}, trying again in {} s", e, RETRY_AFTER.as_secs());
+                std::thread::sleep(RETRY_AFTER);
+                attempt += 1;
+                continue;
+            },
+            Err(e) => return Err(e.to_string())
+        };
+        break;
+    }
+
+    if args.scaffold {
+        new_day(args.day, config)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "fetch"))]
+fn wait(_args: &[String], _config: &Config) -> Result<(), String> {
+    Err("Waiting for a puzzle to unlock needs the fetch feature to download it".to_string())
+}
+
+fn parse_data_dir_args(args: &[String], config: &Config) -> Result<PathBuf, String> {
+    match args {
+        [] => Ok(config.data_dir()),
+        [flag, value] if flag == "--data" => Ok(PathBuf::from(value)),
+        _ => Err(USAGE.to_string())
+    }
+}
+
+#[cfg(feature = "tui")]
+fn tui(data_dir: PathBuf, config: &Config) -> Result<(), String> {
+    let logs = aoc23::tui::LogBuffer::new();
+    aoc23::logging::init_with_writer(logs.clone(), config)?;
+    aoc23::tui::run(aoc23::InputSet::from_dir(&data_dir)?, logs)
+}
+
+#[cfg(not(feature = "tui"))]
+fn tui(_data_dir: PathBuf, _config: &Config) -> Result<(), String> {
+    Err("The dashboard needs the tui feature".to_string())
+}
+
+#[cfg(feature = "gui")]
+fn gui(data_dir: PathBuf) -> Result<(), String> {
+    aoc23::gui::run(aoc23::InputSet::from_dir(&data_dir)?, &data_dir)
+}
+
+#[cfg(not(feature = "gui"))]
+fn gui(_data_dir: PathBuf) -> Result<(), String> {
+    Err("The desktop viewer needs the gui feature".to_string())
+}
+
+#[cfg(feature = "mmap")]
+fn read_input(input: &Path, mmap: bool) -> Result<InputSource, String> {
+    let source = if mmap {InputSource::mmap(input)} else {InputSource::read(input)};
+    match source {
+        Ok(s) => Ok(s),
+        Err(e) => Err(aoc23::AocError::from(e).to_string())
+    }
+}
+
+#[cfg(not(feature = "mmap"))]
+fn read_input(input: &Path, mmap: bool) -> Result<InputSource, String> {
+    if mmap {
+        return Err("Memory-mapping the input needs the mmap feature".to_string());
+    }
+    match InputSource::read(input) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(aoc23::AocError::from(e).to_string())
+    }
+}
+
+#[cfg(feature = "bundle")]
+fn event_inputs(event: &aoc23::Event, data_dir: &Path) -> Result<aoc23::InputSet, String> {
+    // The inputs built into the binary stand in for those missing from the data directory
+    let mut inputs = event.inputs(data_dir)?;
+    for day in aoc23::embedded::fill_missing(&mut inputs) {
+        tracing::info!("Using the input built in for day {}", day);
+    }
+    Ok(inputs)
+}
+
+#[cfg(not(feature = "bundle"))]
+fn event_inputs(event: &aoc23::Event, data_dir: &Path) -> Result<aoc23::InputSet, String> {
+    Ok(event.inputs(data_dir)?)
+}
+
+#[cfg(feature = "bundle")]
+fn built_in_input(args: &RunArgs, input: &Path) -> Option<&'static str> {
+    // The input built into the binary stands in for a missing default input rather than downloading it
+    match args.input {
+        None if !input.exists() => aoc23::embedded::input(args.year, args.day),
+        _ => None
+    }
+}
+
+#[cfg(not(feature = "bundle"))]
+fn built_in_input(_args: &RunArgs, _input: &Path) -> Option<&'static str> {
+    None
+}
+
+fn show_progress(event: &ProgressEvent) {
+    // Redraw the same line, moving on once the stage is finished
+    eprint!("\rDay {} {}: {}/{} ({:.0}%)", event.day, event.stage, event.done, event.total, 100.0 * event.fraction());
+    if event.is_finished() {
+        eprintln!();
+    }
+}
+
+// A solver loaded from a plugin, with the name it gives itself
+type NamedSolver = (String, Box<dyn Solver>);
+
+#[cfg(feature = "plugins")]
+fn load_plugins(paths: &[PathBuf], day: u32) -> Result<Vec<NamedSolver>, String> {
+    /* Load the solvers of plugin libraries, each of which must solve the day given. */
+    let mut loaded = Vec::<NamedSolver>::new();
+    for path in paths {
+        let plugin = aoc23::plugin::load(path)?;
+        if plugin.day() != day {
+            return Err(format!("The plugin '{}' solves day {} rather than day {}", path.display(), plugin.day(), day));
+        }
+        loaded.push((plugin.name().to_string(), Box::new(plugin)));
+    }
+    Ok(loaded)
+}
+
+#[cfg(not(feature = "plugins"))]
+fn load_plugins(paths: &[PathBuf], _day: u32) -> Result<Vec<NamedSolver>, String> {
+    if !paths.is_empty() {
+        return Err("Loading solvers from plugins needs the plugins feature".to_string());
+    }
+    Ok(Vec::new())
+}
+
+fn day_solver(event: &aoc23::Event, day: u32, options: &DayOptions, config: &Config) -> Result<Box<dyn Solver>, String> {
+    let solver = match event.solver(day) {
+        Some(s) => s,
+        None => return Err(format!("Day {} of {} has not been solved", day, event.year))
+    };
+
+    for (flag, flag_day) in options.given() {
+        if (event.year, day) != (2023, flag_day) {
+            return Err(format!("{} only applies to day {} of 2023", flag, flag_day));
+        }
+    }
+
+    // Days without options given keep the solver registered for the event
+    let configured: Box<dyn Solver> = match (day, &options.cubes, &options.gear_symbol) {
+        (2, Some([red, green, blue]), _) => Box::new(Day2 { cubes: BTreeMap::from([(Color::Red, *red), (Color::Green, *green), (Color::Blue, *blue)]) }),
+        (3, _, Some(symbol)) => Box::new(Day3::new(symbol)?),
+        (4, _, _) if options.scoring.is_some() || options.duplicates.is_some() => Box::new(Day4 {
+            options: CardOptions { scoring: options.scoring.unwrap_or_default(), duplicates: options.duplicates.unwrap_or_default() }
+        }),
+        // The memo capacity is a setting rather than an option, so applies whenever it is configured
+        (5, _, _) if event.year == 2023 && (options.seed_ranges || config.memo_capacity.is_some()) => Box::new(Day5 {
+            seed_ranges: options.seed_ranges.then_some(true),
+            memo_capacity: config.memo_capacity
+        }),
+        _ => solver
+    };
+    Ok(configured)
+}
+
+fn explanation(args: &RunArgs, part: u32, puzzle_input: &str) -> Result<String, String> {
+    // Day 2 explains both parts at once, so it is only given before the first
+    match (args.year, args.day) {
+        (2023, 2) if part == args.parts[0] => {
+            let cubes = match args.options.cubes {
+                Some([red, green, blue]) => BTreeMap::from([(Color::Red, red), (Color::Green, green), (Color::Blue, blue)]),
+                None => Day2::default().cubes
+            };
+            Ok(aoc23::day_2::explain_games_from_reader(puzzle_input.as_bytes(), &cubes)?)
+        },
+        (2023, 2) => Ok(String::new()),
+        (2023, 5) => Ok(aoc23::day_5::Almanac::parse(puzzle_input)?.explain(args.options.seed_ranges || part == 2)?),
+        (year, day) => Err(format!("There is no explanation for day {} of {}, only for days 2 and 5 of 2023", day, year))
+    }
+}
+
+fn run(args: &RunArgs, config: &Config) -> Result<(), String> {
+    let event = find_event(args.year)?;
+    let solver = match load_plugins(args.plugin.as_slice(), args.day)?.pop() {
+        Some((_, plugin)) => plugin,
+        None => day_solver(&event, args.day, &args.options, config)?
+    };
+
+    let input = match &args.input {
+        Some(i) => i.clone(),
+        None => event_data_dir(&event, &None, config).join(format!("day_{}.dat", args.day))
+    };
+
+    let source = match built_in_input(args, &input) {
+        Some(text) => InputSource::from_string(text.to_string()),
+        None => {
+            // Only the default input location is filled in, a missing --input file is an error
+            #[cfg(feature = "fetch")]
+            if args.input.is_none() {
+                aoc23::fetch::ensure_input(args.year, args.day, &input, config)?;
+            }
+            read_input(&input, args.mmap)?
+        }
+    };
+    let puzzle_input = match source.as_str() {
+        Ok(contents) => contents,
+        Err(e) => return Err(aoc23::AocError::from(e).to_string())
+    };
+
+    // The answers file is read up front, so that a bad one is reported before solving
+    let key = match &args.record {
+        Some(answers) => Some(load_answers(answers, true)?),
+        None => None
+    };
+    let mut solved = Vec::<(u32, aoc23::Answer)>::new();
+
+    // Each answer is shown against those submitted before, which cannot stop the run
+    let submissions = match config.results_store().submissions() {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::warn!("Failed to read the answers submitted before: {}", e);
+            Vec::new()
+        }
+    };
+
+    // Kept for the results store and webhook, which are told of a failed part as well as of the answers
+    let run_start = Instant::now();
+    let mut parts = Vec::<aoc23::PartResult>::new();
+    let finished = |parts: Vec<aoc23::PartResult>| aoc23::notify::Completion {
+        command: "run".to_string(), year: args.year, elapsed: run_start.elapsed(), results: vec![aoc23::DayResult { day: args.day, parts }]
+    };
+
+    for &part in &args.parts {
+        if args.explain {
+            print!("{}", explanation(args, part, puzzle_input)?);
+        }
+
+        let start = Instant::now();
+        // Diagnostics are only kept without --progress, solvers reporting progress log them instead
+        let (outcome, heap) = aoc23::profiling::measure(|| if args.progress {
+            solver.solve_with_progress(part, puzzle_input, &mut show_progress).map(Outcome::new)
+        } else {
+            solver.solve_with_diagnostics(part, puzzle_input)
+        });
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+        let outcome = match outcome {
+            Ok(o) => o,
+            Err(e) => {
+                parts.push(aoc23::PartResult { part, answer: None, error: Some(e.to_string()), diagnostics: Vec::new(), elapsed_ms });
+                finish(config, finished(parts));
+                return Err(e.to_string());
+            }
+        };
+
+        tracing::debug!(year = args.year, day = args.day, part, elapsed_ms, "Solved");
+        aoc23::profiling::report(&format!("day {} part {}", args.day, part), &heap);
+
+        println!("Day {} part {}: {}", args.day, part, outcome.answer);
+        for diagnostic in &outcome.diagnostics {
+            println!("Day {} part {} warning: {}", args.day, part, diagnostic);
+        }
+        let history = aoc23::submit::History::new(&submissions, args.year, args.day, part, config.profile.as_deref());
+        if let Some(note) = history.note(&outcome.answer) {
+            println!("Day {} part {} note: {}", args.day, part, note);
+        }
+        // Only collected with the profiling feature, and printed as the report is hidden by the default log level
+        if let Some(stats) = heap {
+            println!("Day {} part {} heap: {}", args.day, part, stats);
+        }
+        parts.push(aoc23::PartResult {
+            part,
+            answer: Some(outcome.answer.clone()),
+            error: None,
+            diagnostics: outcome.diagnostics.iter().map(|d| d.to_string()).collect(),
+            elapsed_ms
+        });
+        solved.push((part, outcome.answer));
+    }
+    finish(config, finished(parts));
+
+    if let (Some(answers), Some(mut key)) = (&args.record, key) {
+        let recorded: Vec<(u32, u32)> = solved
+            .iter()
+            .filter(|(part, answer)| key.record(args.day, *part, answer))
+            .map(|&(part, _)| (args.day, part))
+            .collect();
+        save_answers(&key, answers, &recorded)?;
+    }
+
+    Ok(())
+}
+
+// Options taken by every command, which may be given before or after the command
+#[derive(Default)]
+struct GlobalArgs {
+    log_format: Option<LogFormat>,
+    offline: bool,
+    profile: Option<String>
+}
+
+fn take_global_args(args: Vec<String>) -> Result<(Vec<String>, GlobalArgs), String> {
+    /* Split the options shared by every command from those of the command itself. */
+    let mut globals = GlobalArgs::default();
+    let mut remaining = Vec::<String>::new();
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--log-format" => globals.log_format = match iter.next() {
+                Some(v) => Some(v.parse()?),
+                None => return Err(format!("Missing value for '{}'", arg))
+            },
+            "--offline" => globals.offline = true,
+            "--profile" => globals.profile = match iter.next() {
+                Some(v) => Some(v),
+                None => return Err(format!("Missing value for '{}'", arg))
+            },
+            _ => remaining.push(arg)
+        };
+    }
+
+    Ok((remaining, globals))
+}
+
+fn main() {
+    let (args, globals) = match take_global_args(std::env::args().skip(1).collect()) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let loaded = Config::load().and_then(|c| match &globals.profile {
+        Some(p) => c.with_profile(p),
+        None => Ok(c)
+    });
+    let mut config = match loaded {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Inputs encrypted on disk are decrypted with this key by every command, and downloads encrypted
+    #[cfg(feature = "encrypt")]
+    match config.input_key() {
+        Ok(key) => aoc23::util::crypt::set_input_key(key),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Commands check the configuration, while the downloader refuses any request made regardless
+    config.offline |= globals.offline;
+    #[cfg(feature = "fetch")]
+    aoc23::fetch::set_offline(config.offline);
+
+    // The dashboard shows the logs itself rather than writing them over the screen
+    if args.first().map(|a| a.as_str()) != Some("tui") {
+        match aoc23::logging::init_for_cli(&config, globals.log_format) {
+            Ok(l) => l,
+            Err(e) => eprintln!("{}", e)
+        };
+    }
+
+    let result = match args.first().map(|a| a.as_str()) {
+        Some("run") => parse_run_args(&args[1..], &config).and_then(|a| run(&a, &config)),
+        Some("all") => parse_all_args(&args[1..]).and_then(|a| all(&a, &config)),
+        Some("bench") => parse_bench_args(&args[1..]).and_then(|a| bench(&a, &config)),
+        Some("compare") => parse_compare_args(&args[1..]).and_then(|a| compare(&a, &config)),
+        Some("verify") => parse_verify_args(&args[1..]).and_then(|a| verify(&a, &config)),
+        Some("crosscheck") => parse_crosscheck_args(&args[1..]).and_then(|a| crosscheck(&a, &config)),
+        Some("selftest") => selftest(&args[1..]),
+        Some("repl") => repl(&args[1..], &config),
+        Some("list") => parse_list_args(&args[1..]).map(list),
+        Some("new-day") => parse_new_day_args(&args[1..]).and_then(|d| new_day(d, &config)),
+        Some("encrypt") => encrypt(&args[1..], &config),
+        Some("stats") => parse_stats_args(&args[1..]).and_then(|y| stats(y, &config)),
+        Some("submit") => parse_submit_args(&args[1..]).and_then(|a| submit(&a, &config)),
+        Some("report") => parse_report_args(&args[1..]).and_then(|a| report(&a, &config)),
+        Some("watch") => parse_watch_args(&args[1..]).and_then(|a| watch(&a, &config)),
+        Some("wait") => wait(&args[1..], &config),
+        Some("tui") => parse_data_dir_args(&args[1..], &config).and_then(|d| tui(d, &config)),
+        Some("gui") => parse_data_dir_args(&args[1..], &config).and_then(gui),
+        _ => Err(USAGE.to_string())
+    };
+
+    match result {
+        Ok(_) => (),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+}
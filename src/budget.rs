@@ -0,0 +1,137 @@
+/*                        RESOURCE BUDGETS
+
+Runs a solver on a worker thread while a watchdog checks its wall time and the
+memory use of the process against a budget, giving up with a report of what
+was exceeded. Rust threads cannot be killed, so an over-budget solver is left
+running detached and the caller should exit rather than continue with other
+work, as `aoc23 all` does after `runner::run_all_with_budget` stops.
+
+Memory is measured as the resident set size of the whole process, read from
+/proc, so is only available on Linux and includes anything else the process
+holds. Memory budgets are ignored on other platforms.
+
+*/
+
+use std::sync::mpsc::{channel, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Budget {
+    pub wall_time: Option<Duration>,
+    pub memory_bytes: Option<u64>
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BudgetReport {
+    pub elapsed: Duration,
+    // Highest resident set size seen while the solver ran, if it could be measured
+    pub peak_memory_bytes: Option<u64>
+}
+
+pub fn resident_memory_bytes() -> Option<u64> {
+    /* The current resident set size of this process, if it can be determined. */
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kilobytes = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kilobytes * 1024)
+}
+
+pub fn run_with_budget<T, F>(budget: &Budget, solve: F) -> Result<(T, BudgetReport), String>
+where T: Send + 'static, F: FnOnce() -> T + Send + 'static {
+    /* Run a solver, failing if it exceeds the wall time or memory budget.
+
+    # Arguments
+
+    * `budget` - the limits to enforce, a limit of None is not checked
+    * `solve` - the solver to run
+
+    # Returns
+
+    The solver output along with the time taken and peak memory seen, or a
+    description of the limit which was exceeded.
+
+    # Examples
+
+    ```
+    let budget = Budget { wall_time: Some(Duration::from_secs(15)), memory_bytes: None };
+    let (locations, report) = run_with_budget(&budget, move || almanac.location_ranges(true)).unwrap();
+    ```
+    */
+    let (sender, receiver) = channel();
+    let start = Instant::now();
+    let mut peak_memory_bytes = resident_memory_bytes();
+
+    match thread::Builder::new().name("budgeted-solver".to_string()).spawn(move || {
+        let _ = sender.send(solve());
+    }) {
+        Ok(_) => (),
+        Err(e) => return Err(format!("Failed to start solver thread: {}", e))
+    };
+
+    loop {
+        let elapsed = start.elapsed();
+
+        if let Some(memory) = resident_memory_bytes() {
+            peak_memory_bytes = Some(peak_memory_bytes.map_or(memory, |p| p.max(memory)));
+        }
+
+        match receiver.try_recv() {
+            Ok(output) => return Ok((output, BudgetReport { elapsed, peak_memory_bytes })),
+            Err(TryRecvError::Disconnected) => return Err("Solver thread panicked".to_string()),
+            Err(TryRecvError::Empty) => ()
+        };
+
+        if let Some(limit) = budget.wall_time {
+            if elapsed > limit {
+                return Err(format!(
+                    "Wall time budget of {:?} exceeded, solver abandoned after {:?}",
+                    limit, elapsed
+                ));
+            }
+        }
+
+        if let (Some(limit), Some(used)) = (budget.memory_bytes, peak_memory_bytes) {
+            if used > limit {
+                return Err(format!(
+                    "Memory budget of {} bytes exceeded, process using {} bytes after {:?}",
+                    limit, used, elapsed
+                ));
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_within_budget() {
+        let budget = Budget { wall_time: Some(Duration::from_secs(10)), memory_bytes: None };
+        let (output, report) = run_with_budget(&budget, || (1..=10).sum::<i32>()).unwrap();
+
+        assert_eq!(output, 55);
+        assert!(report.elapsed < Duration::from_secs(10));
+        assert_eq!(run_with_budget(&Budget::default(), || 1).unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_exceeded_budgets() {
+        let budget = Budget { wall_time: Some(Duration::from_millis(20)), memory_bytes: None };
+        let result = run_with_budget(&budget, || thread::sleep(Duration::from_secs(2)));
+        assert!(result.unwrap_err().contains("Wall time budget"));
+
+        assert!(run_with_budget(&Budget::default(), || panic!("solver failed")).is_err());
+
+        if cfg!(target_os = "linux") {
+            let budget = Budget { wall_time: None, memory_bytes: Some(1) };
+            let result = run_with_budget(&budget, || thread::sleep(Duration::from_secs(2)));
+            assert!(result.unwrap_err().contains("Memory budget"));
+        }
+    }
+}
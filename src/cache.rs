@@ -0,0 +1,204 @@
+/*                        DOWNLOAD CACHE
+
+Keeps everything downloaded from adventofcode.com on disk, so that running
+again, or deleting a file from the data directory, does not request the same
+resource twice. Entries are kept per event and day, each with a small TOML
+file alongside recording where it came from, the ETag sent by the server and
+when it was last fetched, e.g.
+
+~/.cache/aoc23/2023/day_5/input
+~/.cache/aoc23/2023/day_5/input.meta.toml
+
+The cache lives in $XDG_CACHE_HOME/aoc23 (or ~/.cache/aoc23) unless another
+directory is configured with `cache_dir`.
+
+*/
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::AocError;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheMeta {
+    pub url: String,
+    pub etag: Option<String>,
+    // Seconds since the Unix epoch
+    pub last_fetched: u64
+}
+
+impl CacheMeta {
+    pub fn new(url: &str, etag: Option<String>) -> CacheMeta {
+        /* Metadata for a resource fetched just now. */
+        CacheMeta { url: url.to_string(), etag, last_fetched: now() }
+    }
+
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now().saturating_sub(self.last_fetched))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub body: String,
+    pub meta: CacheMeta
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cache {
+    root: PathBuf
+}
+
+impl Cache {
+    pub fn new(root: impl AsRef<Path>) -> Cache {
+        Cache { root: root.as_ref().to_path_buf() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn body_path(&self, year: u32, day: u32, resource: &str) -> PathBuf {
+        self.root.join(year.to_string()).join(format!("day_{}", day)).join(resource)
+    }
+
+    fn meta_path(&self, year: u32, day: u32, resource: &str) -> PathBuf {
+        self.body_path(year, day, resource).with_extension("meta.toml")
+    }
+
+    pub fn get(&self, year: u32, day: u32, resource: &str) -> Option<CacheEntry> {
+        /* The cached copy of a resource, e.g. "input", if there is a complete one. */
+        let body = std::fs::read_to_string(self.body_path(year, day, resource)).ok()?;
+
+        // An entry without readable metadata is treated as missing and fetched again
+        let meta = match toml::from_str(&std::fs::read_to_string(self.meta_path(year, day, resource)).ok()?) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::warn!(year, day, resource, "Ignoring cache entry with invalid metadata: {}", e);
+                return None;
+            }
+        };
+
+        Some(CacheEntry { body, meta })
+    }
+
+    pub fn put(&self, year: u32, day: u32, resource: &str, body: &str, meta: &CacheMeta) -> Result<(), AocError> {
+        /* Store a resource and its metadata, replacing any previous copy. */
+        let body_path = self.body_path(year, day, resource);
+        if let Some(dir) = body_path.parent() {
+            match std::fs::create_dir_all(dir) {
+                Ok(_) => (),
+                Err(e) => return Err(AocError::io(dir, e))
+            };
+        }
+
+        let meta_toml = match toml::to_string(meta) {
+            Ok(m) => m,
+            Err(e) => return Err(AocError::parse(format!("Failed to write cache metadata: {}", e)))
+        };
+
+        // Any previous metadata is removed first and the new written last, each file being
+        // renamed into place whole, so an interrupted put leaves no entry rather than a bad one
+        let meta_path = self.meta_path(year, day, resource);
+        match std::fs::remove_file(&meta_path) {
+            Ok(_) => (),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+            Err(e) => return Err(AocError::io(&meta_path, e))
+        };
+        write_whole(&body_path, body)?;
+        write_whole(&meta_path, &meta_toml)
+    }
+
+    pub fn touch(&self, year: u32, day: u32, resource: &str) -> Result<(), AocError> {
+        /* Mark a cached resource as fetched now, after the server confirmed it is unchanged. */
+        match self.get(year, day, resource) {
+            Some(entry) => self.put(year, day, resource, &entry.body, &CacheMeta { last_fetched: now(), ..entry.meta }),
+            None => Err(AocError::MissingData(format!("Nothing cached for {} day {} {}", year, day, resource)))
+        }
+    }
+}
+
+pub fn default_dir() -> Option<PathBuf> {
+    /* The aoc23 directory within the user's cache directory. */
+    let cache_dir = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(d) if !d.is_empty() => PathBuf::from(d),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".cache")
+    };
+    Some(cache_dir.join("aoc23"))
+}
+
+fn write_whole(path: &Path, contents: &str) -> Result<(), AocError> {
+    /* Write a file alongside the destination then rename it over, so the destination is never partly written. */
+    let mut partial_name = path.as_os_str().to_os_string();
+    partial_name.push(".partial");
+    let partial_path = PathBuf::from(partial_name);
+
+    match std::fs::write(&partial_path, contents) {
+        Ok(_) => (),
+        Err(e) => return Err(AocError::io(&partial_path, e))
+    };
+    match std::fs::rename(&partial_path, path) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(AocError::io(path, e))
+    }
+}
+
+fn now() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs(),
+        Err(_) => 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let cache = Cache::new(std::env::temp_dir().join(format!("aoc23_cache_{}", std::process::id())));
+        let missing = cache.get(2023, 5, "input");
+
+        let meta = CacheMeta { url: "https://adventofcode.com/2023/day/5/input".to_string(), etag: Some("\"abc\"".to_string()), last_fetched: 10 };
+        cache.put(2023, 5, "input", "seeds: 1 2\n", &meta).unwrap();
+        let stored = cache.get(2023, 5, "input");
+        cache.touch(2023, 5, "input").unwrap();
+        let touched = cache.get(2023, 5, "input").unwrap();
+        let other_day = cache.get(2023, 4, "input");
+
+        std::fs::remove_dir_all(cache.root()).unwrap();
+
+        assert!(missing.is_none());
+        assert_eq!(stored, Some(CacheEntry { body: "seeds: 1 2\n".to_string(), meta: meta.clone() }));
+        assert_eq!(touched.meta.etag, meta.etag);
+        assert!(touched.meta.last_fetched > 10);
+        assert!(touched.meta.age() < Duration::from_secs(60));
+        assert!(other_day.is_none());
+    }
+
+    #[test]
+    fn test_cache_replace() {
+        let cache = Cache::new(std::env::temp_dir().join(format!("aoc23_cache_replace_{}", std::process::id())));
+        let old_meta = CacheMeta::new("https://adventofcode.com/2023/day/5", Some("\"old\"".to_string()));
+        let new_meta = CacheMeta::new("https://adventofcode.com/2023/day/5", Some("\"new\"".to_string()));
+
+        cache.put(2023, 5, "page", "old page", &old_meta).unwrap();
+        cache.put(2023, 5, "page", "new page", &new_meta).unwrap();
+        let replaced = cache.get(2023, 5, "page");
+
+        // A body left without its metadata, as by a put interrupted after removing the old, is not an entry
+        std::fs::remove_file(cache.meta_path(2023, 5, "page")).unwrap();
+        let unfinished = cache.get(2023, 5, "page");
+
+        let mut files: Vec<String> = std::fs::read_dir(cache.root().join("2023/day_5")).unwrap()
+            .map(|f| f.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        std::fs::remove_dir_all(cache.root()).unwrap();
+
+        assert_eq!(replaced, Some(CacheEntry { body: "new page".to_string(), meta: new_meta }));
+        assert!(unfinished.is_none());
+        assert_eq!(files, vec!["page"]);
+    }
+}
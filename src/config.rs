@@ -0,0 +1,309 @@
+/*                        CONFIGURATION
+
+User settings for the command line runner and input download, read from
+`aoc23/config.toml` in the user's configuration directory ($XDG_CONFIG_HOME,
+or ~/.config). Every setting is optional and the file need not exist:
+
+session = "53616c7465645f5f..."   # the 'session' cookie, see src/fetch.rs
+data_dir = "/home/me/aoc/inputs"   # used in place of 'data' for the inputs
+log_level = "info"                 # logging when RUST_LOG is not set
+log_format = "json"                # 'text' or 'json' when AOC23_LOG_FORMAT is not set
+threads = 4                        # threads used by `all --parallel`, 0 to solve serially
+cache_dir = "/tmp/aoc23"           # where downloads are cached, see src/cache.rs
+max_time_ms = 15000                # wall time allowed for each day by `all`, see src/budget.rs
+max_memory_mb = 512                # memory allowed for the process while `all` solves a day
+memo_capacity = 1024               # entries kept by memoizing solvers when using `run`
+day_patterns = ['(?P<day>\d+)\.in'] # file names giving the day when `run` has no --day
+offline = true                     # never contact adventofcode.com, as with --offline
+input_key = "3f0c9e..."            # 64 hex digits encrypting the inputs, see aoc_utils::crypt
+results_dir = "/home/me/aoc/runs"  # where the results of every run are kept, see src/store.rs
+
+[profiles.work]                    # chosen with --profile work, e.g. for a second account
+session = "..."                    # replaces the session above
+data_dir = "/home/me/aoc/work"     # replaces the data directory above
+cache_dir = "/tmp/aoc23-work"      # by default the 'work' directory within the cache
+
+[references]                       # implementations checked by `crosscheck`, see src/reference.rs
+python = ["python3", "reference/day_{day}.py"]
+
+[webhook]                          # told when `all` or `run` finishes, see src/notify.rs
+url = "https://hooks.slack.com/services/..."
+format = "slack"                   # 'json', 'slack' or 'discord'
+min_ms = 60000                     # only for runs taking at least this long
+
+Environment variables take precedence, i.e. AOC_SESSION over the session and
+RUST_LOG over the log level and AOC23_INPUT_KEY over the input key, while command line options take precedence over
+both. The session of a profile chosen on the command line is therefore used
+even when AOC_SESSION is set, while a profile without a session of its own
+falls back to AOC_SESSION and then the top level session.
+
+*/
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::budget::Budget;
+use crate::cache::{self, Cache};
+use crate::detect::DayDetector;
+use crate::error::AocError;
+use crate::logging::LogFormat;
+use crate::notify::Webhook;
+use crate::store::{self, ResultsStore};
+#[cfg(feature = "encrypt")]
+use crate::util::crypt::InputKey;
+
+// Where the inputs are kept when no data directory is configured
+pub const DEFAULT_DATA_DIR: &str = "data";
+
+#[cfg(feature = "encrypt")]
+pub const INPUT_KEY_ENV: &str = "AOC23_INPUT_KEY";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub session: Option<String>,
+    pub data_dir: Option<PathBuf>,
+    pub log_level: Option<String>,
+    pub log_format: Option<LogFormat>,
+    pub threads: Option<usize>,
+    pub cache_dir: Option<PathBuf>,
+    pub max_time_ms: Option<u64>,
+    pub max_memory_mb: Option<u64>,
+    pub memo_capacity: Option<usize>,
+    pub day_patterns: Option<Vec<String>>,
+    pub offline: bool,
+    pub input_key: Option<String>,
+    pub results_dir: Option<PathBuf>,
+    pub profiles: BTreeMap<String, Profile>,
+    // The command of each reference implementation by name, see src/reference.rs
+    pub references: BTreeMap<String, Vec<String>>,
+    pub webhook: Option<Webhook>,
+    // The profile in use, chosen on the command line rather than in the file
+    #[serde(skip)]
+    pub profile: Option<String>
+}
+
+// The settings of one account, for when inputs are downloaded for more than one
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    pub session: Option<String>,
+    pub data_dir: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>
+}
+
+impl Config {
+    pub fn parse(content: &str) -> Result<Config, AocError> {
+        /* Read the settings from the contents of a configuration file.
+
+        # Examples
+
+        ```
+        let config = Config::parse("data_dir = \"inputs\"\nthreads = 4\n").unwrap();
+        ```
+        */
+        match toml::from_str(content) {
+            Ok(c) => Ok(c),
+            Err(e) => Err(AocError::parse(format!("Invalid configuration: {}", e)))
+        }
+    }
+
+    pub fn from_file(file_name: impl AsRef<Path>) -> Result<Config, AocError> {
+        match std::fs::read_to_string(file_name.as_ref()) {
+            Ok(contents) => Config::parse(&contents),
+            Err(e) => Err(AocError::io(file_name.as_ref(), e))
+        }
+    }
+
+    pub fn load() -> Result<Config, AocError> {
+        /* Read the user's configuration file, using the defaults if there is none. */
+        match config_file() {
+            Some(f) if f.exists() => Config::from_file(&f),
+            _ => Ok(Config::default())
+        }
+    }
+
+    pub fn data_dir(&self) -> PathBuf {
+        /* The directory holding the puzzle inputs, `data` unless configured. */
+        match &self.data_dir {
+            Some(d) => d.clone(),
+            None => PathBuf::from(DEFAULT_DATA_DIR)
+        }
+    }
+
+    pub fn with_profile(&self, name: &str) -> Result<Config, AocError> {
+        /* The settings with those of the named profile in place of the top level ones.
+
+        Downloads differ per account, so a profile without a cache directory of its own
+        uses a directory named after it within the usual cache. The session is left as
+        the top level one, the profile's own being found with `profile_session`, as only
+        the latter is used ahead of AOC_SESSION.
+
+        # Examples
+
+        ```
+        let config = Config::load().unwrap().with_profile("work").unwrap();
+        ```
+        */
+        let known: Vec<String> = self.profiles.keys().cloned().collect();
+        let profile = match (self.profiles.get(name), known.is_empty()) {
+            (Some(p), _) => p,
+            (None, true) => return Err(AocError::InvalidArgument(format!("No profile '{}', the configuration has no [profiles] tables", name))),
+            (None, false) => return Err(AocError::InvalidArgument(format!("No profile '{}' in the configuration, expected one of: {}", name, known.join(", "))))
+        };
+
+        Ok(Config {
+            data_dir: profile.data_dir.clone().or_else(|| self.data_dir.clone()),
+            cache_dir: Some(match &profile.cache_dir {
+                Some(d) => d.clone(),
+                None => self.cache().root().join(name)
+            }),
+            profile: Some(name.to_string()),
+            ..self.clone()
+        })
+    }
+
+    pub fn profile_session(&self) -> Option<&str> {
+        /* The session given by the profile in use, if there is one and it has a session. */
+        self.profiles.get(self.profile.as_deref()?)?.session.as_deref()
+    }
+
+    pub fn budget(&self) -> Budget {
+        /* The wall time and memory allowed for each day, unlimited unless configured. */
+        Budget {
+            wall_time: self.max_time_ms.map(Duration::from_millis),
+            memory_bytes: self.max_memory_mb.map(|mb| mb * 1024 * 1024)
+        }
+    }
+
+    pub fn day_detector(&self) -> Result<DayDetector, AocError> {
+        /* The detector for the day of an input, using the configured file name patterns if any. */
+        match &self.day_patterns {
+            Some(p) => DayDetector::new(p),
+            None => Ok(DayDetector::default())
+        }
+    }
+
+    #[cfg(feature = "encrypt")]
+    pub fn input_key(&self) -> Result<Option<InputKey>, AocError> {
+        /* The key encrypting the inputs, from AOC23_INPUT_KEY or else the configuration, if any. */
+        let hex = match std::env::var(INPUT_KEY_ENV) {
+            Ok(k) if !k.trim().is_empty() => k,
+            _ => match &self.input_key {
+                Some(k) => k.clone(),
+                None => return Ok(None)
+            }
+        };
+
+        match InputKey::from_hex(&hex) {
+            Some(k) => Ok(Some(k)),
+            None => Err(AocError::InvalidArgument("The input key is not 64 hexadecimal digits".to_string()))
+        }
+    }
+
+    pub fn cache(&self) -> Cache {
+        /* The download cache, in the user's cache directory unless configured. */
+        match (self.cache_dir.clone(), cache::default_dir()) {
+            (Some(d), _) | (None, Some(d)) => Cache::new(d),
+            // Without a home directory the cache is kept alongside the inputs
+            (None, None) => Cache::new(self.data_dir().join(".cache"))
+        }
+    }
+
+    pub fn results_store(&self) -> ResultsStore {
+        /* The store of past runs, in the user's data directory unless configured. */
+        match (self.results_dir.clone(), store::default_dir()) {
+            (Some(d), _) | (None, Some(d)) => ResultsStore::new(d),
+            // Without a home directory the runs are kept alongside the inputs
+            (None, None) => ResultsStore::new(self.data_dir().join(".results"))
+        }
+    }
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    /* The directory of the aoc23 settings within the user's configuration directory. */
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(d) if !d.is_empty() => PathBuf::from(d),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config")
+    };
+    Some(config_dir.join("aoc23"))
+}
+
+pub fn config_file() -> Option<PathBuf> {
+    Some(config_dir()?.join("config.toml"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::notify::WebhookFormat;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse("session = \"abc123\"\ndata_dir = \"inputs\"\nlog_level = \"debug\"\nthreads = 4\ncache_dir = \"/tmp/aoc23\"\n").unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc123"));
+        assert_eq!(config.data_dir(), PathBuf::from("inputs"));
+        assert_eq!(config.log_level.as_deref(), Some("debug"));
+        assert_eq!(Config::parse("log_format = \"json\"\n").unwrap().log_format, Some(LogFormat::Json));
+        assert!(Config::parse("log_format = \"xml\"\n").is_err());
+        assert_eq!(config.threads, Some(4));
+        assert_eq!(config.cache().root(), Path::new("/tmp/aoc23"));
+
+        let budget = Config::parse("max_time_ms = 1500\nmax_memory_mb = 2\n").unwrap().budget();
+        assert_eq!(budget, Budget { wall_time: Some(Duration::from_millis(1500)), memory_bytes: Some(2 * 1024 * 1024) });
+        assert_eq!(Config::default().budget(), Budget::default());
+        assert_eq!(Config::parse("memo_capacity = 16\n").unwrap().memo_capacity, Some(16));
+        assert!(Config::parse("offline = true\n").unwrap().offline);
+        assert!(!Config::default().offline);
+        assert_eq!(Config::parse("input_key = \"00ff\"\n").unwrap().input_key.as_deref(), Some("00ff"));
+        assert_eq!(Config::parse("results_dir = \"/tmp/runs\"\n").unwrap().results_store().root(), Path::new("/tmp/runs"));
+
+        let patterns = Config::parse("day_patterns = ['^(?P<day>\\d+)\\.in$']\n").unwrap();
+        assert_eq!(patterns.day_detector().unwrap().from_file_name("inputs/7.in"), Some(7));
+        assert_eq!(patterns.day_detector().unwrap().from_file_name("day_7.dat"), None);
+        assert_eq!(Config::default().day_detector().unwrap().from_file_name("day_7.dat"), Some(7));
+        assert!(Config::parse("day_patterns = ['day(\\d+)']\n").unwrap().day_detector().is_err());
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+
+        let webhook = Config::parse("[webhook]\nurl = \"https://example.com/hook\"\nformat = \"discord\"\n").unwrap().webhook.unwrap();
+        assert_eq!((webhook.format, webhook.min_ms), (WebhookFormat::Discord, 0));
+        assert!(Config::parse("[webhook]\nformat = \"slack\"\n").is_err());
+
+        let accounts = Config::parse(
+            "session = \"home\"\ncache_dir = \"/tmp/aoc23\"\n[profiles.work]\nsession = \"work\"\ndata_dir = \"work\"\n[profiles.empty]\n"
+        ).unwrap();
+        let work = accounts.with_profile("work").unwrap();
+        assert_eq!((work.profile_session(), work.data_dir()), (Some("work"), PathBuf::from("work")));
+        assert_eq!(work.session.as_deref(), Some("home"));
+        assert_eq!(work.cache().root(), Path::new("/tmp/aoc23/work"));
+        assert_eq!(work.profile.as_deref(), Some("work"));
+        assert_eq!(accounts.with_profile("empty").unwrap().session.as_deref(), Some("home"));
+        assert_eq!(accounts.with_profile("empty").unwrap().profile_session(), None);
+        assert_eq!(accounts.profile_session(), None);
+        assert!(accounts.with_profile("play").unwrap_err().to_string().contains("empty, work"));
+        assert!(Config::parse("[profiles.work]\nthreads = 2\n").is_err());
+        assert!(Config::default().with_profile("work").unwrap_err().to_string().contains("no [profiles]"));
+        assert_eq!(Config::default().data_dir(), PathBuf::from("data"));
+
+        assert!(Config::parse("threads = \"four\"\n").is_err());
+        assert!(Config::parse("sesion = \"abc123\"\n").is_err());
+        assert!(Config::from_file("/no/such/config.toml").is_err());
+    }
+
+    #[cfg(feature = "encrypt")]
+    #[test]
+    fn test_input_key() {
+        // AOC23_INPUT_KEY takes precedence when set, so only the configured key is checked without it
+        if std::env::var(INPUT_KEY_ENV).is_ok() {
+            return;
+        }
+        let key = InputKey::generate();
+        let config = Config { input_key: Some(key.to_hex()), ..Config::default() };
+        assert_eq!(config.input_key().unwrap(), Some(key));
+        assert_eq!(Config::default().input_key().unwrap(), None);
+        assert!(Config { input_key: Some("00ff".to_string()), ..Config::default() }.input_key().is_err());
+    }
+}
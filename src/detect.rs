@@ -0,0 +1,282 @@
+/*                        DAY DETECTION
+
+Infers which day an input belongs to, first from its file name and otherwise
+by recognising the layout of the content. File names are matched against a
+list of regular expressions each with a named `day` capture group, by default
+accepting names such as 'day_7.dat', 'day07.txt' and '2023-12-07.txt'. Other
+patterns can be set with `day_patterns` in the configuration, see src/config.rs.
+
+A bundle file holds the inputs of several days, each section starting with a
+header line such as '## day 3'. Bundles can be split into sections, or into one
+file per day which the file based solvers can read directly. A whole bundle can
+also be solved at once, see `InputSet::from_bundle` in src/runner.rs.
+
+*/
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+use crate::util::io::lines;
+
+pub const DEFAULT_DAY_PATTERNS: [&str; 2] = [
+    r"(?i)day[_\-\s]?0*(?P<day>\d{1,2})\b",
+    r"^\d{4}-12-(?P<day>\d{2})\b"
+];
+
+pub struct DayDetector {
+    patterns: Vec<Regex>
+}
+
+impl DayDetector {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<DayDetector, AocError> {
+        /* Create a detector from file name patterns, each containing a `day` capture group.
+
+        # Examples
+
+        ```
+        let detector = DayDetector::new(&[r"input(?P<day>\d+)"]).unwrap();
+        assert_eq!(detector.from_file_name("input7.txt"), Some(7));
+        ```
+        */
+        let mut compiled = Vec::<Regex>::new();
+
+        for pattern in patterns.iter().map(|p| p.as_ref()) {
+            let re = match Regex::new(pattern) {
+                Ok(r) => r,
+                Err(e) => return Err(AocError::InvalidArgument(format!("Invalid day pattern '{}': {}", pattern, e)))
+            };
+            if !re.capture_names().any(|n| n == Some("day")) {
+                return Err(AocError::InvalidArgument(format!("Day pattern '{}' has no 'day' capture group", pattern)));
+            }
+            compiled.push(re);
+        }
+
+        Ok(DayDetector { patterns: compiled })
+    }
+
+    pub fn from_file_name<P: AsRef<Path>>(&self, path: P) -> Option<u32> {
+        /* Day given by the first pattern matching the file name, ignoring the directory. */
+        let file_name = path.as_ref().file_name()?.to_str()?;
+
+        self.patterns
+            .iter()
+            .filter_map(|re| re.captures(file_name))
+            .filter_map(|c| c.name("day")?.as_str().parse::<u32>().ok())
+            .find(|day| (1..=25).contains(day))
+    }
+
+    pub fn detect<P: AsRef<Path>>(&self, path: P, content: &str) -> Option<u32> {
+        /* Day from the file name if possible, otherwise from the content. */
+        match self.from_file_name(&path) {
+            Some(d) => Some(d),
+            None => sniff_day(content)
+        }
+    }
+}
+
+impl Default for DayDetector {
+    fn default() -> Self {
+        match DayDetector::new(&DEFAULT_DAY_PATTERNS) {
+            Ok(d) => d,
+            Err(e) => panic!("{}", e)
+        }
+    }
+}
+
+pub fn sniff_day(content: &str) -> Option<u32> {
+    /* Guess the day of an input from its layout, for days where this is unambiguous. */
+    let lines: Vec<&str> = content.lines().map(|l| l.trim_end()).filter(|l| !l.is_empty()).collect();
+
+    let first = lines.first()?;
+
+    if first.starts_with("seeds:") {
+        return Some(5);
+    }
+    if lines.iter().all(|l| l.starts_with("Game ") && l.contains(':')) {
+        return Some(2);
+    }
+    if lines.iter().all(|l| l.starts_with("Card ") && l.contains(':') && l.contains('|')) {
+        return Some(4);
+    }
+
+    // A rectangular schematic of digits, dots and symbols
+    let width = first.len();
+    if lines.iter().all(|l| l.len() == width && !l.chars().any(|c| c.is_alphabetic() || c.is_whitespace()))
+        && lines.iter().any(|l| l.contains('.')) {
+        return Some(3);
+    }
+
+    // Calibration lines of lower case letters and digits
+    if lines.iter().all(|l| l.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())) {
+        return Some(1);
+    }
+
+    None
+}
+
+// The input of a single day within a bundle, `line` is the line number of its header
+#[derive(Clone, Debug, PartialEq)]
+pub struct DaySection {
+    pub day: u32,
+    pub line: usize,
+    pub content: String
+}
+
+pub fn split_bundle(bundle_file: impl AsRef<Path>) -> Result<Vec<DaySection>, AocError> {
+    /* Split a bundle of inputs into the section for each day.
+
+    Sections begin with a header line of the form '## day N' and run until the next header,
+    trailing blank lines are removed. Content before the first header, repeated days and days
+    outside 1 to 25 are errors.
+
+    # Examples
+
+    ```
+    for section in split_bundle("/path/to/inputs.txt").unwrap() {
+        tracing::info!("Day {} has {} lines", section.day, section.content.lines().count());
+    }
+    ```
+    */
+    let header_re = match Regex::new(r"(?i)^##\s*day\s*0*(\d+)\s*$") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let mut sections = Vec::<DaySection>::new();
+    let mut seen = HashSet::<u32>::new();
+
+    for (i, line) in lines(&bundle_file)?.enumerate() {
+        let file_line = line?;
+
+        match header_re.captures(&file_line).and_then(|c| c.get(1)) {
+            Some(d) => {
+                let day = match d.as_str().parse::<u32>() {
+                    Ok(n) if (1..=25).contains(&n) => n,
+                    _ => return Err(AocError::parse_at(i + 1, format!("Invalid day '{}' in section header", d.as_str())))
+                };
+                if !seen.insert(day) {
+                    return Err(AocError::parse_at(i + 1, format!("Day {} appears more than once", day)));
+                }
+                sections.push(DaySection { day, line: i + 1, content: String::new() });
+            },
+            None => match sections.last_mut() {
+                Some(s) => {
+                    s.content.push_str(&file_line);
+                    s.content.push('\n');
+                },
+                None => if !file_line.trim().is_empty() {
+                    return Err(AocError::parse_at(i + 1, "Content comes before the first '## day N' header"));
+                }
+            }
+        };
+    }
+
+    for section in &mut sections {
+        let trimmed = section.content.trim_end().len();
+        section.content.truncate(trimmed);
+        section.content.push('\n');
+    }
+
+    Ok(sections)
+}
+
+pub fn unbundle(bundle_file: impl AsRef<Path>, output_dir: impl AsRef<Path>) -> Result<Vec<(u32, PathBuf)>, AocError> {
+    /* Write each day of a bundle to its own file, named as the inputs under 'data/'.
+
+    # Returns
+
+    The day and path of every file written.
+
+    # Examples
+
+    ```
+    for (day, path) in unbundle("/path/to/inputs.txt", "/tmp/inputs").unwrap() {
+        tracing::info!("Day {} input written to '{}'", day, path.display());
+    }
+    ```
+    */
+    let mut written = Vec::<(u32, PathBuf)>::new();
+
+    for section in split_bundle(&bundle_file)? {
+        let path = output_dir.as_ref().join(format!("day_{}.dat", section.day));
+        match std::fs::write(&path, &section.content) {
+            Ok(_) => written.push((section.day, path)),
+            Err(e) => return Err(AocError::io(&path, e))
+        };
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_detect_from_file_name() {
+        let detector = DayDetector::default();
+
+        assert_eq!(detector.from_file_name("data/day_5.dat"), Some(5));
+        assert_eq!(detector.from_file_name("inputs/day07.txt"), Some(7));
+        assert_eq!(detector.from_file_name("Day-12.in"), Some(12));
+        assert_eq!(detector.from_file_name("2023-12-07.txt"), Some(7));
+        assert_eq!(detector.from_file_name("day_5/input.txt"), None);
+        assert_eq!(detector.from_file_name("day_31.dat"), None);
+        assert_eq!(detector.from_file_name("input.txt"), None);
+
+        let custom = DayDetector::new(&[r"^(?P<day>\d+)\.in$"]).unwrap();
+        assert_eq!(custom.from_file_name("18.in"), Some(18));
+        assert!(matches!(DayDetector::new(&[r"day(\d+)"]), Err(AocError::InvalidArgument(_))));
+        assert_eq!(DayDetector::new(&[String::from(r"p(?P<day>\d+)")]).unwrap().from_file_name("p3"), Some(3));
+        assert!(DayDetector::new(&[r"day(?P<day>"]).is_err());
+    }
+
+    #[test]
+    fn test_sniff_content() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+
+        for (file, day) in [("day_1_2.dat", 1), ("day_2.dat", 2), ("day_3.dat", 3), ("day_4.dat", 4), ("day_5.dat", 5)] {
+            let content = std::fs::read_to_string(test_dir.join(file)).unwrap();
+            assert_eq!(sniff_day(&content), Some(day), "{}", file);
+            assert_eq!(DayDetector::default().detect("input.txt", &content), Some(day));
+        }
+
+        assert_eq!(sniff_day(""), None);
+        assert_eq!(sniff_day("Some other puzzle\n"), None);
+    }
+
+    #[test]
+    fn test_bundles() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let day_2 = std::fs::read_to_string(test_dir.join("day_2.dat")).unwrap();
+        let day_5 = std::fs::read_to_string(test_dir.join("day_5.dat")).unwrap();
+
+        let output_dir = std::env::temp_dir().join(format!("aoc23_bundle_{}", std::process::id()));
+        std::fs::create_dir_all(&output_dir).unwrap();
+        let bundle_file = output_dir.join("bundle.txt");
+        std::fs::write(&bundle_file, format!("\n## Day 2\n{}\n\n## day 05\n{}", day_2, day_5)).unwrap();
+
+        let sections = split_bundle(&bundle_file).unwrap();
+        assert_eq!(sections.iter().map(|s| (s.day, s.line)).collect::<Vec<_>>(), vec![(2, 2), (5, 9)]);
+        assert_eq!(sniff_day(&sections[1].content), Some(5));
+
+        let written = unbundle(&bundle_file, &output_dir).unwrap();
+        let total = crate::day_2::get_total_game_power(&written[0].1).unwrap();
+        assert_eq!(total, crate::day_2::get_total_game_power(test_dir.join("day_2.dat")).unwrap());
+        assert_eq!(
+            crate::day_5::Almanac::from_file(&written[1].1).unwrap().min_location(&[crate::day_5::SeedRange::new(79, 92)]).unwrap(),
+            Some(crate::day_5::Location(46))
+        );
+
+        std::fs::write(&bundle_file, "## day 1\nab1\n## day 1\n2cd\n").unwrap();
+        assert!(split_bundle(&bundle_file).unwrap_err().to_string().contains("more than once"));
+        std::fs::write(&bundle_file, "stray\n## day 1\nab1\n").unwrap();
+        assert!(matches!(split_bundle(&bundle_file), Err(AocError::Parse { line: Some(1), .. })));
+        assert!(matches!(split_bundle(output_dir.join("missing.txt")), Err(AocError::Io { .. })));
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+    }
+}
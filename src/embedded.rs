@@ -0,0 +1,75 @@
+/*                        EMBEDDED INPUTS
+
+Puzzle inputs of 2023 built into the binary with the bundle feature, so that
+a release build can solve every day on a machine without a data directory,
+e.g.
+
+AOC23_BUNDLE_DAYS=1,2,5 cargo build --release --features bundle
+
+The inputs are chosen when building, see build.rs, and are only used for the
+days whose input is missing from the data directory, see `fill_missing`, so
+that a file on disk always wins over the one built in.
+
+*/
+
+use crate::event::DEFAULT_YEAR;
+use crate::runner::InputSet;
+
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+pub fn input(year: u32, day: u32) -> Option<&'static str> {
+    /* The input built in for a day, if any, only those of 2023 being built in. */
+    if year != DEFAULT_YEAR {
+        return None;
+    }
+    INPUTS.iter().find(|(d, _)| *d == day).map(|&(_, input)| input)
+}
+
+pub fn fill_missing(inputs: &mut InputSet) -> Vec<u32> {
+    /* Add the input built in for every solved day the set has no input for, returning those days. */
+    let missing: Vec<u32> = inputs
+        .solvers()
+        .iter()
+        .map(|s| s.day())
+        .filter(|&day| inputs.get(day).is_none())
+        .collect();
+
+    let mut filled = Vec::<u32>::new();
+    for day in missing {
+        if let Some(embedded) = input(inputs.year(), day) {
+            inputs.insert(day, embedded.to_string());
+            filled.push(day);
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input() {
+        for &(day, embedded) in INPUTS {
+            assert_eq!(input(DEFAULT_YEAR, day), Some(embedded));
+            assert_eq!(input(2022, day), None);
+        }
+        assert_eq!(input(DEFAULT_YEAR, 26), None);
+    }
+
+    #[test]
+    fn test_fill_missing() {
+        let mut inputs = InputSet::new();
+        inputs.insert(1, "on disk".to_string());
+        let filled = fill_missing(&mut inputs);
+
+        assert!(!filled.contains(&1));
+        assert_eq!(inputs.get(1), Some("on disk"));
+        for day in filled {
+            assert_eq!(inputs.get(day), input(DEFAULT_YEAR, day));
+        }
+
+        let mut other_year = InputSet::for_year(2022);
+        assert!(fill_missing(&mut other_year).is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+/*                        EVENTS
+
+Each year of Advent of Code is a separate event with its own twenty five
+puzzles. Solvers are registered under the event they belong to, so that the
+runner, input download and command line can address a puzzle by year and day.
+The days of this crate are registered under 2023, and another year can be
+added with its own registry function and data directory.
+
+*/
+
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+use crate::runner::InputSet;
+use crate::solver::{self, Solver};
+
+pub const DEFAULT_YEAR: u32 = 2023;
+
+pub struct Event {
+    pub year: u32,
+    // Directory holding the puzzle inputs, named day_N.dat
+    pub data_dir: &'static str,
+    registry: fn() -> Vec<Box<dyn Solver>>
+}
+
+impl Event {
+    pub const fn new(year: u32, data_dir: &'static str, registry: fn() -> Vec<Box<dyn Solver>>) -> Event {
+        Event { year, data_dir, registry }
+    }
+
+    pub fn solvers(&self) -> Vec<Box<dyn Solver>> {
+        /* A solver for every implemented day of the event, in day order. */
+        (self.registry)()
+    }
+
+    pub fn solver(&self, day: u32) -> Option<Box<dyn Solver>> {
+        self.solvers().into_iter().find(|s| s.day() == day)
+    }
+
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        /* Where the puzzle input for a day of the event is kept. */
+        PathBuf::from(self.data_dir).join(format!("day_{}.dat", day))
+    }
+
+    pub fn inputs(&self, data_dir: impl AsRef<Path>) -> Result<InputSet, AocError> {
+        /* Read the inputs for the days of the event from a directory, see `InputSet::from_dir_for_year`. */
+        InputSet::from_dir_for_year(self.year, data_dir)
+    }
+}
+
+pub fn events() -> Vec<Event> {
+    /* Every registered event, in year order.
+
+    # Examples
+
+    ```
+    for event in aoc23::events() {
+        tracing::info!("{} has {} days solved", event.year, event.solvers().len());
+    }
+    ```
+    */
+    vec![
+        Event::new(2023, "data", solver::solvers)
+    ]
+}
+
+pub fn event(year: u32) -> Option<Event> {
+    /* The event for a single year, if it has been registered. */
+    events().into_iter().find(|e| e.year == year)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registered_events() {
+        assert_eq!(events().iter().map(|e| e.year).collect::<Vec<u32>>(), vec![DEFAULT_YEAR]);
+
+        let event_2023 = event(2023).unwrap();
+        assert_eq!(event_2023.solvers().iter().map(|s| s.day()).collect::<Vec<u32>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(event_2023.solver(5).unwrap().day(), 5);
+        assert!(event_2023.solver(25).is_none());
+        assert_eq!(event_2023.input_path(5), PathBuf::from("data/day_5.dat"));
+
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let inputs = event_2023.inputs(&test_dir).unwrap();
+        assert_eq!(inputs.year(), 2023);
+        assert_eq!(inputs.days().collect::<Vec<u32>>(), vec![2, 3, 4, 5]);
+        assert!(InputSet::from_dir_for_year(2015, &test_dir).unwrap().solvers().is_empty());
+
+        assert!(event(2015).is_none());
+    }
+}
@@ -0,0 +1,579 @@
+/*                        INPUT DOWNLOAD
+
+Downloads the official puzzle input for a day of an event, i.e. the puzzles of
+one year, from adventofcode.com. Inputs differ per account, so requests are
+authenticated with the 'session' cookie of a logged in browser, read from the
+AOC_SESSION environment variable, else the `session` setting of the
+configuration file (see src/config.rs), or else from the file 'aoc23/session'
+in the user's configuration directory ($XDG_CONFIG_HOME, or ~/.config).
+
+Inputs are written to the data directory of the event as day_N.dat, where the
+runner reads them from, and are only requested if the file does not already
+exist, to keep load on the site to a minimum. Every download is also kept in
+the cache (see src/cache.rs), which is checked first, so the same input or
+page is not requested twice.
+
+With the `encrypt` feature and an input key set, inputs are written to the data
+directory encrypted, so that it can be committed, see aoc_utils::crypt. The
+cache, which is kept outside the repository, holds them as downloaded.
+
+The example given on the puzzle page, its first `<pre><code>` block, can also
+be saved as the test input of a new day, see `ensure_example`. Answers are
+posted back to the site with `post_answer`, which src/submit.rs wraps with a
+check against the answers submitted before.
+
+Requests are also spaced out, at least MIN_REQUEST_INTERVAL apart, by recording
+the time of the last one in the cache directory, so that several runs sharing
+the cache, such as a loop in a shell script, are throttled together.
+
+In offline mode, set with `--offline` or the `offline` setting, every request
+fails straight away without touching the network, while inputs already in the
+cache can still be used.
+
+*/
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::cache::{Cache, CacheMeta};
+use crate::config::{config_dir, Config};
+use crate::error::AocError;
+use crate::submit::Verdict;
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = "github.com/artemis-beta/Advent-of-Code-2023 input fetcher";
+
+// The first year with an event
+const FIRST_YEAR: u32 = 2015;
+
+// Names of the downloads within the cache for each day
+const INPUT: &str = "input";
+const PAGE: &str = "page";
+
+// How long a cached puzzle page is used before checking it has not changed
+const PAGE_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+// The least time between two requests to the site, from this run or any other sharing the cache
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+// Name of the file within the cache holding the time of the last request, in milliseconds since the epoch
+const LAST_REQUEST: &str = "last_request";
+
+// Held while waiting for a turn, so downloads on several threads, as with `all --fetch`, queue up
+static REQUEST_TURN: Mutex<()> = Mutex::new(());
+
+// Whether requests to the site are refused, shared by every download of the process
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+// Held by the tests which set the offline mode or depend on it, as the tests of the crate run in parallel
+#[cfg(test)]
+pub(crate) static OFFLINE_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn set_offline(offline: bool) {
+    /* Refuse, or allow again, every request to adventofcode.com made by this process. */
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+pub fn input_url(year: u32, day: u32) -> String {
+    format!("https://adventofcode.com/{}/day/{}/input", year, day)
+}
+
+pub fn session_file() -> Option<PathBuf> {
+    /* Location of the session token file within the user's configuration directory. */
+    Some(config_dir()?.join("session"))
+}
+
+fn parse_session(token: &str) -> Option<String> {
+    // Accept the token as copied from the browser, with or without the cookie name
+    let token = token.trim();
+    let token = match token.strip_prefix("session=") {
+        Some(t) => t,
+        None => token
+    };
+    if token.is_empty() {None} else {Some(token.to_string())}
+}
+
+pub fn session_token(config: &Config) -> Result<String, AocError> {
+    /* Read the session token from AOC_SESSION, falling back to the configuration and then the session file.
+
+    The session of a profile chosen on the command line is used ahead of AOC_SESSION,
+    while the top level session, which a profile without its own inherits, is not.
+    */
+    let token = config.profile_session()
+        .and_then(parse_session)
+        .or_else(|| std::env::var(SESSION_ENV).ok().as_deref().and_then(parse_session))
+        .or_else(|| config.session.as_deref().and_then(parse_session));
+    if let Some(t) = token {
+        return Ok(t);
+    }
+
+    let file = match session_file() {
+        Some(f) => f,
+        None => return Err(AocError::MissingData(format!("No session token, set {}", SESSION_ENV)))
+    };
+
+    match std::fs::read_to_string(&file) {
+        Ok(content) => match parse_session(&content) {
+            Some(t) => Ok(t),
+            None => Err(AocError::MissingData(format!("Session file '{}' is empty", file.display())))
+        },
+        Err(_) => Err(AocError::MissingData(format!("No session token, set {} or write it to '{}'", SESSION_ENV, file.display())))
+    }
+}
+
+pub fn puzzle_url(year: u32, day: u32) -> String {
+    format!("https://adventofcode.com/{}/day/{}", year, day)
+}
+
+// The result of a download, which may be conditional on the copy already held
+enum Download {
+    Body { body: String, etag: Option<String> },
+    NotModified
+}
+
+fn wait_for_turn(stamp_file: &Path, interval: Duration) -> Duration {
+    /* Sleep until the interval has passed since the request recorded in the stamp file, then record one now.
+
+    # Returns
+
+    The time spent waiting.
+    */
+    // A thread which panicked while waiting leaves nothing inconsistent behind, so its lock is still used
+    let _turn = REQUEST_TURN.lock().unwrap_or_else(|e| e.into_inner());
+
+    let millis = |t: SystemTime| t.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+    let last = std::fs::read_to_string(stamp_file).ok().and_then(|s| s.trim().parse::<u64>().ok());
+
+    // A stamp from the future, e.g. after the clock was put back, waits one interval at most
+    let waited = match last {
+        Some(l) => interval.saturating_sub(Duration::from_millis(millis(SystemTime::now()).saturating_sub(l))),
+        None => Duration::ZERO
+    };
+    if !waited.is_zero() {
+        tracing::debug!(waited_ms = waited.as_millis() as u64, "Waiting before the next request");
+        std::thread::sleep(waited);
+    }
+
+    // Failing to record the request only means the next one may come sooner
+    let recorded = match stamp_file.parent() {
+        Some(dir) => std::fs::create_dir_all(dir),
+        None => Ok(())
+    }.and_then(|_| std::fs::write(stamp_file, millis(SystemTime::now()).to_string()));
+    match recorded {
+        Ok(_) => (),
+        Err(e) => tracing::warn!("Failed to record the request time in '{}': {}", stamp_file.display(), e)
+    };
+
+    waited
+}
+
+fn check_request(year: u32, day: u32, url: &str) -> Result<(), AocError> {
+    /* Refuse a request in offline mode or for a day which cannot exist, before waiting for a turn. */
+    if is_offline() {
+        return Err(AocError::InvalidArgument(format!("Cannot download '{}' in offline mode", url)));
+    }
+    if year < FIRST_YEAR {
+        return Err(AocError::InvalidArgument(format!("There is no {} event to fetch from", year)));
+    }
+    if !(1..=25).contains(&day) {
+        return Err(AocError::InvalidArgument(format!("There is no day {} to fetch", day)));
+    }
+    Ok(())
+}
+
+fn download(year: u32, day: u32, url: &str, session: &str, etag: Option<&str>, cache: &Cache) -> Result<Download, AocError> {
+    /* Request a resource of the site, only sending it back if it does not match the given ETag. */
+    check_request(year, day, url)?;
+
+    let _span = tracing::info_span!("fetch", year, day, url).entered();
+
+    wait_for_turn(&cache.root().join(LAST_REQUEST), MIN_REQUEST_INTERVAL);
+
+    let mut request = ureq::get(url)
+        .header("Cookie", &format!("session={}", session))
+        .header("User-Agent", USER_AGENT);
+    if let Some(e) = etag {
+        request = request.header("If-None-Match", e);
+    }
+
+    let failed = |reason: String| AocError::Download { url: url.to_string(), reason };
+    match request.call() {
+        Ok(r) if r.status() == 304 => Ok(Download::NotModified),
+        Ok(mut r) => {
+            let etag = r.headers().get("ETag").and_then(|e| e.to_str().ok()).map(|e| e.to_string());
+            match r.body_mut().read_to_string() {
+                Ok(body) => Ok(Download::Body { body, etag }),
+                Err(e) => Err(failed(format!("could not read the response: {}", e)))
+            }
+        },
+        Err(ureq::Error::StatusCode(code)) if code == 400 || code == 401 => {
+            Err(failed(format!("the request was refused ({}), the session token may have expired", code)))
+        },
+        Err(ureq::Error::StatusCode(404)) => Err(failed(format!("{} day {} is not available yet", year, day))),
+        Err(e) => Err(failed(e.to_string()))
+    }
+}
+
+fn store(cache: &Cache, year: u32, day: u32, resource: &str, body: &str, meta: &CacheMeta) {
+    // Failing to cache only means fetching again next time, so the download is still used
+    match cache.put(year, day, resource, body, meta) {
+        Ok(_) => (),
+        Err(e) => tracing::warn!(year, day, resource, "Failed to cache download: {}", e)
+    };
+}
+
+pub fn fetch_input(year: u32, day: u32, session: &str, cache: &Cache) -> Result<String, AocError> {
+    /* The puzzle input for a day of the given year, downloaded unless it has been cached.
+
+    An input never changes once published, so a cached copy is always used.
+
+    # Examples
+
+    ```
+    let config = Config::load().unwrap();
+    let input = fetch_input(2023, 5, &session_token(&config).unwrap(), &config.cache()).unwrap();
+    ```
+    */
+    if let Some(entry) = cache.get(year, day, INPUT) {
+        tracing::debug!(year, day, "Using the cached input from {}", entry.meta.url);
+        return Ok(entry.body);
+    }
+
+    let url = input_url(year, day);
+    match download(year, day, &url, session, None, cache)? {
+        Download::Body { body, etag } => {
+            store(cache, year, day, INPUT, &body, &CacheMeta::new(&url, etag));
+            Ok(body)
+        },
+        Download::NotModified => Err(AocError::Download { url, reason: "unexpected 'not modified' response".to_string() })
+    }
+}
+
+pub fn fetch_page(year: u32, day: u32, session: &str, cache: &Cache) -> Result<String, AocError> {
+    /* The HTML of the puzzle page for a day of the given year.
+
+    The page gains part 2 once part 1 is solved, so a cached copy older than an hour
+    is checked with the server, and only downloaded again if its ETag has changed.
+    */
+    let cached = cache.get(year, day, PAGE);
+    let etag = match &cached {
+        Some(entry) if entry.meta.age() < PAGE_MAX_AGE => return Ok(entry.body.clone()),
+        Some(entry) => entry.meta.etag.clone(),
+        None => None
+    };
+
+    let url = puzzle_url(year, day);
+    match (download(year, day, &url, session, etag.as_deref(), cache)?, cached) {
+        (Download::Body { body, etag }, _) => {
+            store(cache, year, day, PAGE, &body, &CacheMeta::new(&url, etag));
+            Ok(body)
+        },
+        (Download::NotModified, Some(entry)) => {
+            match cache.touch(year, day, PAGE) {
+                Ok(_) => (),
+                Err(e) => tracing::warn!(year, day, "Failed to update cached page: {}", e)
+            };
+            Ok(entry.body)
+        },
+        (Download::NotModified, None) => Err(AocError::Download { url, reason: "unexpected 'not modified' response".to_string() })
+    }
+}
+
+pub fn answer_url(year: u32, day: u32) -> String {
+    format!("https://adventofcode.com/{}/day/{}/answer", year, day)
+}
+
+pub fn post_answer(year: u32, day: u32, part: u32, answer: &str, session: &str, cache: &Cache) -> Result<Verdict, AocError> {
+    /* Submit the answer to a part of a puzzle, returning what the site made of it.
+
+    This always asks the site, see `submit::submit` for checking the answer
+    against those submitted before.
+    */
+    let url = answer_url(year, day);
+    check_request(year, day, &url)?;
+    if !(1..=2).contains(&part) {
+        return Err(AocError::InvalidArgument(format!("There is no part {} to submit", part)));
+    }
+
+    let _span = tracing::info_span!("submit", year, day, part).entered();
+
+    wait_for_turn(&cache.root().join(LAST_REQUEST), MIN_REQUEST_INTERVAL);
+
+    let request = ureq::post(&url)
+        .header("Cookie", &format!("session={}", session))
+        .header("User-Agent", USER_AGENT);
+
+    let failed = |reason: String| AocError::Download { url: url.clone(), reason };
+    match request.send_form([("level", part.to_string()), ("answer", answer.to_string())]) {
+        Ok(mut r) => match r.body_mut().read_to_string() {
+            Ok(page) => Ok(Verdict::from_response(&page)),
+            Err(e) => Err(failed(format!("could not read the response: {}", e)))
+        },
+        Err(ureq::Error::StatusCode(code)) if code == 400 || code == 401 => {
+            Err(failed(format!("the answer was refused ({}), the session token may have expired", code)))
+        },
+        Err(e) => Err(failed(e.to_string()))
+    }
+}
+
+pub fn ensure_input(year: u32, day: u32, path: impl AsRef<Path>, config: &Config) -> Result<bool, AocError> {
+    /* Write the input for a day of the given year to a path unless the file already exists.
+
+    The input is taken from the download cache if it is there, so a session token is
+    only needed the first time. In offline mode an input which is not in the cache is
+    an error rather than a download.
+
+    # Returns
+
+    Whether the input was written.
+    */
+    let path = path.as_ref();
+    if path.exists() {
+        return Ok(false);
+    }
+
+    let cache = config.cache();
+    let input = match cache.get(year, day, INPUT) {
+        Some(entry) => entry.body,
+        None if is_offline() => return Err(AocError::MissingData(format!(
+            "The input for {} day {} is not at '{}' or in the cache, and cannot be downloaded in offline mode", year, day, path.display()
+        ))),
+        None => fetch_input(year, day, &session_token(config)?, &cache)?
+    };
+
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => match std::fs::create_dir_all(dir) {
+            Ok(_) => (),
+            Err(e) => return Err(AocError::io(dir, e))
+        },
+        _ => ()
+    };
+
+    match std::fs::write(path, stored(input)) {
+        Ok(_) => {
+            tracing::info!("Wrote the input for {} day {} to '{}'", year, day, path.display());
+            Ok(true)
+        },
+        Err(e) => Err(AocError::io(path, e))
+    }
+}
+
+#[cfg(feature = "encrypt")]
+fn stored(input: String) -> Vec<u8> {
+    /* The input as written to the data directory, encrypted if a key has been set for the process. */
+    match crate::util::crypt::input_key() {
+        Some(key) => crate::util::crypt::encrypt(&key, input.as_bytes()),
+        None => input.into_bytes()
+    }
+}
+
+#[cfg(not(feature = "encrypt"))]
+fn stored(input: String) -> Vec<u8> {
+    input.into_bytes()
+}
+
+pub fn example_input(page: &str) -> Option<String> {
+    /* The text of the first `<pre><code>` block of a puzzle page, the example input of part 1.
+
+    Markup within the block, such as the `<em>` used for highlighting, is removed and
+    HTML entities are replaced with the characters they stand for.
+    */
+    let start = page.find("<pre><code>")? + "<pre><code>".len();
+    let end = start + page[start..].find("</code></pre>")?;
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match (c, in_tag) {
+            ('<', _) => in_tag = true,
+            ('>', true) => in_tag = false,
+            (_, false) => text.push(c),
+            (_, true) => ()
+        };
+    }
+
+    // '&amp;' goes last so that an escaped entity such as '&amp;lt;' is not unescaped twice
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    if text.trim().is_empty() {None} else {Some(text)}
+}
+
+pub fn ensure_example(year: u32, day: u32, path: impl AsRef<Path>, config: &Config) -> Result<bool, AocError> {
+    /* Write the example input from the puzzle page of a day to a path, unless the file already has content.
+
+    # Returns
+
+    Whether the example was written.
+
+    # Examples
+
+    ```
+    ensure_example(2023, 6, "data/test/day_6.dat", &Config::load().unwrap()).unwrap();
+    ```
+    */
+    let path = path.as_ref();
+    match std::fs::metadata(path) {
+        Ok(m) if m.len() > 0 => return Ok(false),
+        _ => ()
+    };
+
+    let page = fetch_page(year, day, &session_token(config)?, &config.cache())?;
+    let example = match example_input(&page) {
+        Some(e) => e,
+        None => return Err(AocError::MissingData(format!("No example input found on the page of {} day {}", year, day)))
+    };
+
+    match std::fs::write(path, example) {
+        Ok(_) => {
+            tracing::info!("Wrote the example for {} day {} to '{}'", year, day, path.display());
+            Ok(true)
+        },
+        Err(e) => Err(AocError::io(path, e))
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn fetch_input_async(year: u32, day: u32, session: String, cache: Cache) -> Result<String, AocError> {
+    /* As `fetch_input`, downloading on the blocking thread pool so other tasks carry on meanwhile. */
+    match tokio::task::spawn_blocking(move || fetch_input(year, day, &session, &cache)).await {
+        Ok(result) => result,
+        Err(e) => Err(AocError::Download { url: input_url(year, day), reason: e.to_string() })
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn ensure_input_async(year: u32, day: u32, path: PathBuf, config: Config) -> Result<bool, AocError> {
+    /* As `ensure_input`, downloading on the blocking thread pool so other tasks carry on meanwhile. */
+    match tokio::task::spawn_blocking(move || ensure_input(year, day, &path, &config)).await {
+        Ok(result) => result,
+        Err(e) => Err(AocError::Download { url: input_url(year, day), reason: e.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_session_parsing() {
+        assert_eq!(parse_session("  abc123\n"), Some("abc123".to_string()));
+        assert_eq!(parse_session("session=abc123"), Some("abc123".to_string()));
+        assert_eq!(parse_session("\n"), None);
+        assert_eq!(input_url(2023, 5), "https://adventofcode.com/2023/day/5/input");
+
+        // A profile's session wins whether or not AOC_SESSION is set, an inherited one only without it
+        let config = Config::parse("session = \"main\"\n[profiles.work]\nsession = \"session=work\"\n[profiles.home]\n").unwrap();
+        assert_eq!(session_token(&config.with_profile("work").unwrap()).unwrap(), "work");
+        let from_env = std::env::var(SESSION_ENV).ok().as_deref().and_then(parse_session);
+        assert_eq!(session_token(&config.with_profile("home").unwrap()).unwrap(), from_env.unwrap_or("main".to_string()));
+    }
+
+    #[test]
+    fn test_existing_input_not_fetched() {
+        let _lock = OFFLINE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut test_file = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("data/test/day_5.dat");
+
+        let cache = Cache::new("/no/such/cache");
+        assert!(!ensure_input(2023, 5, &test_file, &Config::default()).unwrap());
+        assert!(matches!(fetch_input(2023, 26, "token", &cache), Err(AocError::InvalidArgument(_))));
+        assert!(matches!(fetch_input(2014, 1, "token", &cache), Err(AocError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_cached_input_not_fetched() {
+        let _lock = OFFLINE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let root = std::env::temp_dir().join(format!("aoc23_fetch_{}", std::process::id()));
+        let config = Config { cache_dir: Some(root.join("cache")), ..Config::default() };
+
+        let meta = CacheMeta::new(&input_url(2023, 3), None);
+        config.cache().put(2023, 3, INPUT, "467..114..\n", &meta).unwrap();
+
+        // There is no session token, so these would fail if a download were attempted
+        let input = fetch_input(2023, 3, "", &config.cache());
+        let written = ensure_input(2023, 3, root.join("data/day_3.dat"), &config);
+        let contents = std::fs::read_to_string(root.join("data/day_3.dat"));
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(input.unwrap(), "467..114..\n");
+        assert!(written.unwrap());
+        assert_eq!(contents.unwrap(), "467..114..\n");
+    }
+
+    #[test]
+    fn test_example_input() {
+        let page = "<article><p>For example:</p>\n<pre><code>467..114..\n...*......\n</code></pre>\n\
+                    <p>Then:</p><pre><code>ignored\n</code></pre></article>";
+        assert_eq!(example_input(page).unwrap(), "467..114..\n...*......\n");
+
+        let highlighted = "<pre><code>Card 1: <em>41</em> 48 | 83 &lt;&amp;lt;&gt;\n</code></pre>";
+        assert_eq!(example_input(highlighted).unwrap(), "Card 1: 41 48 | 83 <&lt;>\n");
+
+        assert_eq!(example_input("<p>No example today</p>"), None);
+        assert_eq!(example_input("<pre><code>\n</code></pre>"), None);
+
+        // A test file which already has content is left alone without fetching the page
+        let mut test_file = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("data/test/day_5.dat");
+        assert!(!ensure_example(2023, 5, &test_file, &Config::default()).unwrap());
+    }
+
+    #[test]
+    fn test_request_interval() {
+        let stamp_file = std::env::temp_dir().join(format!("aoc23_interval_{}", std::process::id())).join(LAST_REQUEST);
+
+        // The first request goes straight away, the next waits for the rest of the interval
+        assert_eq!(wait_for_turn(&stamp_file, Duration::from_millis(200)), Duration::ZERO);
+        let waited = wait_for_turn(&stamp_file, Duration::from_millis(200));
+        assert!(waited > Duration::from_millis(100) && waited <= Duration::from_millis(200), "waited {:?}", waited);
+
+        std::fs::write(&stamp_file, "not a time").unwrap();
+        assert_eq!(wait_for_turn(&stamp_file, Duration::from_millis(200)), Duration::ZERO);
+        assert!(std::fs::read_to_string(&stamp_file).unwrap().parse::<u64>().is_ok());
+
+        std::fs::remove_dir_all(stamp_file.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_offline() {
+        // The mode is shared by the whole process, so the other tests depending on it wait for this one
+        let _lock = OFFLINE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let cache = Cache::new("/no/such/cache");
+
+        let root = std::env::temp_dir().join(format!("aoc23_offline_{}", std::process::id()));
+        let config = Config { cache_dir: Some(root.join("cache")), ..Config::default() };
+        config.cache().put(2023, 3, INPUT, "467..114..\n", &CacheMeta::new(&input_url(2023, 3), None)).unwrap();
+
+        set_offline(true);
+        let refused = fetch_page(2023, 5, "token", &cache);
+        let cached = ensure_input(2023, 3, root.join("data/day_3.dat"), &config);
+        let missing = ensure_input(2023, 4, root.join("data/day_4.dat"), &config);
+        set_offline(false);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        // Inputs in the cache are still written, only those needing a download fail
+        assert!(cached.unwrap());
+        match missing {
+            Err(AocError::MissingData(reason)) => assert!(reason.contains("day 4") && reason.contains("offline mode")),
+            other => panic!("Expected the missing input to be reported, got {:?}", other)
+        };
+
+        match refused {
+            Err(AocError::InvalidArgument(reason)) => assert!(reason.contains("offline mode")),
+            other => panic!("Expected the download to be refused, got {:?}", other)
+        };
+        assert!(!is_offline());
+    }
+}
@@ -0,0 +1,364 @@
+/*                        GPU BRUTE FORCE
+
+With the gpu feature, day 5 can also be solved by mapping every seed through
+the maps of the almanac one by one, as `day_5::solve_part2_bruteforce` does,
+but with a seed to each invocation of a compute shader (src/gpu.wgsl), so that
+the billions of seeds of the full part 2 ranges take seconds rather than
+minutes. The range based solver needs neither, this is a check on it and a
+benchmark, registered as the "gpu" implementation of day 5:
+
+aoc23 compare --day 5 --part 2
+
+The shader works in 32 bits, as WGSL has no 64 bit integers without an
+extension few adapters offer, so an almanac with any number beyond u32::MAX
+is refused. Every puzzle input fits. Seeds are dispatched in batches of at most
+SEEDS_PER_DISPATCH, the most one dimension of workgroups is sure to hold, each
+invocation lowering a single shared minimum with an atomic.
+
+*/
+
+use wgpu::util::DeviceExt;
+
+use crate::answer::Answer;
+use crate::day_5::{Almanac, CategoryMap, Location, SeedRange};
+use crate::error::AocError;
+use crate::progress::{NoProgress, ProgressEvent, ProgressSink};
+use crate::solver::{Registration, Solver};
+
+const SHADER: &str = include_str!("gpu.wgsl");
+
+// Must match the workgroup size of the shader
+const WORKGROUP_SIZE: u32 = 256;
+
+// Every adapter takes at least 65535 workgroups in each dimension
+pub const SEEDS_PER_DISPATCH: u32 = WORKGROUP_SIZE * 65535;
+
+// A line of a map as laid out for the shader, padded to 16 bytes
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuMapping {
+    destination: u32,
+    source: u32,
+    length: u32,
+    padding: u32
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    first_seed: u32,
+    count: u32,
+    maps: u32,
+    padding: u32
+}
+
+fn fit(value: i64, what: &str, map: &CategoryMap) -> Result<u32, AocError> {
+    match u32::try_from(value) {
+        Ok(v) => Ok(v),
+        Err(_) => Err(AocError::InvalidArgument(format!(
+            "The {} {} of the {}-to-{} map does not fit in the 32 bits the GPU works in", what, value, map.source, map.destination
+        )))
+    }
+}
+
+fn pack_chain(chain: &[&CategoryMap]) -> Result<(Vec<GpuMapping>, Vec<u32>), AocError> {
+    /* The lines of the maps one after another, and the index just past the last line of each map. */
+    let mut mappings = Vec::<GpuMapping>::new();
+    let mut map_ends = Vec::<u32>::new();
+
+    for map in chain {
+        for range in &map.ranges {
+            // The last value of either end must fit as well, as the shader adds the offset to it
+            fit(range.source_end().max(range.destination_end()), "end", map)?;
+            mappings.push(GpuMapping {
+                destination: fit(range.destination_start, "destination", map)?,
+                source: fit(range.source_start, "source", map)?,
+                length: fit(range.length, "length", map)?,
+                padding: 0
+            });
+        }
+        map_ends.push(mappings.len() as u32);
+    }
+
+    // A binding cannot be empty, and the extra line is never read
+    if mappings.is_empty() {
+        mappings.push(GpuMapping { destination: 0, source: 0, length: 0, padding: 0 });
+    }
+    if map_ends.is_empty() {
+        map_ends.push(0);
+    }
+    Ok((mappings, map_ends))
+}
+
+pub struct GpuMapper {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    params: wgpu::Buffer,
+    lowest: wgpu::Buffer,
+    readback: wgpu::Buffer,
+    maps: u32,
+    adapter: String
+}
+
+impl GpuMapper {
+    pub fn new(almanac: &Almanac) -> Result<GpuMapper, AocError> {
+        /* Load the seed to location maps of an almanac onto the first GPU found.
+
+        # Examples
+
+        ```
+        let almanac = Almanac::from_file("/path/to/file").unwrap();
+        let mapper = GpuMapper::new(&almanac).unwrap();
+        let lowest = mapper.min_location(&almanac.seed_ranges(true), LogProgress).unwrap();
+        ```
+        */
+        let chain = almanac.map_chain("seed", "location")?;
+        let (mappings, map_ends) = pack_chain(&chain)?;
+        pollster::block_on(GpuMapper::connect(&mappings, &map_ends))
+    }
+
+    async fn connect(mappings: &[GpuMapping], map_ends: &[u32]) -> Result<GpuMapper, AocError> {
+        let unavailable = |reason: String| AocError::InvalidArgument(format!("No GPU to map the seeds on: {}", reason));
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = match instance.request_adapter(&wgpu::RequestAdapterOptions::default()).await {
+            Ok(a) => a,
+            Err(e) => return Err(unavailable(e.to_string()))
+        };
+        let descriptor = wgpu::DeviceDescriptor { label: Some("day 5"), required_limits: adapter.limits(), ..Default::default() };
+        let (device, queue) = match adapter.request_device(&descriptor).await {
+            Ok(d) => d,
+            Err(e) => return Err(unavailable(e.to_string()))
+        };
+        let adapter = adapter.get_info().name;
+        tracing::debug!(adapter, "Mapping the seeds of day 5 on the GPU");
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("day 5 seeds"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into())
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("day 5 seeds"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None
+        });
+
+        let storage = |label: &str, contents: &[u8], usage: wgpu::BufferUsages| device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(label), contents, usage
+        });
+        let mappings_buffer = storage("mappings", bytemuck::cast_slice(mappings), wgpu::BufferUsages::STORAGE);
+        let map_ends_buffer = storage("map ends", bytemuck::cast_slice(map_ends), wgpu::BufferUsages::STORAGE);
+        let params = storage("params", bytemuck::bytes_of(&Params { first_seed: 0, count: 0, maps: 0, padding: 0 }), wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST);
+        let lowest = storage("lowest", bytemuck::bytes_of(&u32::MAX), wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST);
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("lowest readback"),
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("day 5 seeds"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: mappings_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: map_ends_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: params.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: lowest.as_entire_binding() }
+            ]
+        });
+
+        Ok(GpuMapper { device, queue, pipeline, bind_group, params, lowest, readback, maps: map_ends.len() as u32, adapter })
+    }
+
+    pub fn adapter(&self) -> &str {
+        /* The name of the GPU the seeds are mapped on. */
+        &self.adapter
+    }
+
+    pub fn min_location<P: ProgressSink>(&self, seed_ranges: &[SeedRange], mut progress: P) -> Result<Option<Location>, AocError> {
+        /* The lowest location of any seed in the ranges, mapping every seed on the GPU.
+
+        # Arguments
+
+        * `seed_ranges` - the seeds to map, whose numbers must fit in 32 bits
+        * `progress` - receives the number of seeds mapped so far after each dispatch
+        */
+        let mut batches = Vec::<(u32, u32)>::new();
+        for range in seed_ranges {
+            let (first, last) = range.bounds();
+            let (first, last) = match (u32::try_from(first), u32::try_from(last)) {
+                (Ok(f), Ok(l)) if f <= l => (f, l),
+                _ => return Err(AocError::InvalidArgument(format!("The seeds {}..={} do not fit in the 32 bits the GPU works in", first, last)))
+            };
+            let mut start = first;
+            loop {
+                let count = (last - start).min(SEEDS_PER_DISPATCH - 1) + 1;
+                batches.push((start, count));
+                match start.checked_add(count) {
+                    Some(next) if next <= last => start = next,
+                    _ => break
+                };
+            }
+        }
+        if batches.is_empty() {
+            return Ok(None);
+        }
+
+        let _span = tracing::info_span!("solve", day = 5, method = "gpu", adapter = self.adapter).entered();
+        let total: u64 = batches.iter().map(|&(_, count)| count as u64).sum();
+        let mut done = 0u64;
+        self.queue.write_buffer(&self.lowest, 0, bytemuck::bytes_of(&u32::MAX));
+
+        // Each batch is waited on before the next, so that its parameters are not overwritten while in use
+        for (first_seed, count) in batches {
+            self.queue.write_buffer(&self.params, 0, bytemuck::bytes_of(&Params { first_seed, count, maps: self.maps, padding: 0 }));
+            let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("day 5 seeds") });
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("day 5 seeds"), timestamp_writes: None });
+                pass.set_pipeline(&self.pipeline);
+                pass.set_bind_group(0, &self.bind_group, &[]);
+                pass.dispatch_workgroups(count.div_ceil(WORKGROUP_SIZE), 1, 1);
+            }
+            self.queue.submit(Some(encoder.finish()));
+            self.wait()?;
+
+            done += count as u64;
+            progress.publish(&ProgressEvent { day: 5, stage: "gpu seeds", done, total });
+        }
+
+        Ok(Some(Location(self.read_lowest()? as i64)))
+    }
+
+    fn wait(&self) -> Result<(), AocError> {
+        match self.device.poll(wgpu::PollType::wait_indefinitely()) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AocError::InvalidArgument(format!("The GPU failed while mapping seeds: {}", e)))
+        }
+    }
+
+    fn read_lowest(&self) -> Result<u32, AocError> {
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("day 5 readback") });
+        encoder.copy_buffer_to_buffer(&self.lowest, 0, &self.readback, 0, 4);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = self.readback.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |mapped| {
+            let _ = sender.send(mapped);
+        });
+        self.wait()?;
+        match receiver.recv() {
+            Ok(Ok(())) => (),
+            Ok(Err(e)) => return Err(AocError::InvalidArgument(format!("Failed to read the lowest location back from the GPU: {}", e))),
+            Err(e) => return Err(AocError::InvalidArgument(format!("Failed to read the lowest location back from the GPU: {}", e)))
+        };
+
+        let lowest = bytemuck::pod_read_unaligned::<u32>(&slice.get_mapped_range());
+        self.readback.unmap();
+        Ok(lowest)
+    }
+}
+
+// Day 5 solved by mapping every seed on the GPU, as a check and benchmark of `Day5`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Day5Gpu;
+
+impl Day5Gpu {
+    fn solve_almanac(&self, use_ranges: bool, input: &str, progress: &mut dyn ProgressSink) -> Result<Answer, AocError> {
+        let almanac = Almanac::parse(input)?;
+        match GpuMapper::new(&almanac)?.min_location(&almanac.seed_ranges(use_ranges), |e: &ProgressEvent| progress.publish(e))? {
+            Some(l) => Ok(Answer::Int(l.0)),
+            None => Err(AocError::MissingData("The almanac lists no seeds".to_string()))
+        }
+    }
+}
+
+impl Solver for Day5Gpu {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        self.solve_almanac(false, input, &mut NoProgress)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        self.solve_almanac(true, input, &mut NoProgress)
+    }
+
+    fn solve_with_progress(&self, part: u32, input: &str, progress: &mut dyn ProgressSink) -> Result<Answer, AocError> {
+        match part {
+            1 => self.solve_almanac(false, input, progress),
+            2 => self.solve_almanac(true, input, progress),
+            _ => Err(AocError::InvalidArgument(format!("Day 5 has no part {}", part)))
+        }
+    }
+}
+
+inventory::submit! {
+    Registration { name: "gpu", build: || Box::new(Day5Gpu) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day_5::RangeMapping;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_pack_chain() {
+        let map = |source: &str, ranges: Vec<RangeMapping>| CategoryMap { source: source.to_string(), destination: "next".to_string(), ranges };
+        let seed_to_soil = map("seed", vec![
+            RangeMapping { destination_start: 50, source_start: 98, length: 2 },
+            RangeMapping { destination_start: 52, source_start: 50, length: 48 }
+        ]);
+        let empty = map("soil", Vec::new());
+
+        let (mappings, map_ends) = pack_chain(&[&seed_to_soil, &empty]).unwrap();
+        assert_eq!(mappings[1], GpuMapping { destination: 52, source: 50, length: 48, padding: 0 });
+        assert_eq!(map_ends, vec![2, 2]);
+        assert_eq!(pack_chain(&[]).unwrap(), (vec![GpuMapping { destination: 0, source: 0, length: 0, padding: 0 }], vec![0]));
+
+        // The top of the 32 bit range is allowed, a line reaching past it is not
+        let top = map("seed", vec![RangeMapping { destination_start: 0, source_start: u32::MAX as i64, length: 1 }]);
+        assert!(pack_chain(&[&top]).is_ok());
+        let past = map("seed", vec![RangeMapping { destination_start: u32::MAX as i64, source_start: 0, length: 2 }]);
+        assert!(pack_chain(&[&past]).unwrap_err().to_string().contains("end 4294967296 of the seed-to-next map"));
+
+        // Listed with day 5's other solvers, although registered from this crate rather than aoc-core
+        let names: Vec<&str> = crate::solver::alternatives(5).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["default", "bruteforce", "gpu"]);
+    }
+
+    #[test]
+    fn test_gpu_min_location() {
+        let input = std::fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test/day_5.dat")).unwrap();
+        let almanac = Almanac::parse(&input).unwrap();
+
+        // Not every machine running the tests has a GPU, or a driver wgpu can use
+        let mapper = match GpuMapper::new(&almanac) {
+            Ok(m) => m,
+            Err(e) => {
+                eprintln!("Skipping the GPU test: {}", e);
+                return;
+            }
+        };
+
+        let mut events = Vec::<ProgressEvent>::new();
+        assert_eq!(mapper.min_location(&almanac.seed_ranges(true), |e: &ProgressEvent| events.push(e.clone())).unwrap(), Some(Location(46)));
+        assert_eq!(mapper.min_location(&almanac.seed_ranges(false), NoProgress).unwrap(), Some(Location(35)));
+        assert_eq!(mapper.min_location(&[], NoProgress).unwrap(), None);
+        assert!(mapper.min_location(&[SeedRange::new(-1, 3)], NoProgress).is_err());
+        assert_eq!(events.last().unwrap().done, 27);
+        assert!(events.last().unwrap().is_finished());
+
+        // A range larger than a dispatch is split, the seeds past the maps keeping their numbers
+        assert_eq!(mapper.min_location(&[SeedRange::new(4_000_000_000, 4_000_000_000 + SEEDS_PER_DISPATCH as i64)], NoProgress).unwrap(), Some(Location(4_000_000_000)));
+    }
+}
@@ -0,0 +1,46 @@
+// Maps one seed of day 5 per invocation through every map of the almanac, see src/gpu.rs
+
+struct Mapping {
+    destination: u32,
+    source: u32,
+    length: u32,
+    padding: u32
+}
+
+struct Params {
+    first_seed: u32,
+    count: u32,
+    maps: u32,
+    padding: u32
+}
+
+// The lines of every map, one map after another
+@group(0) @binding(0) var<storage, read> mappings: array<Mapping>;
+// The index in mappings just past the last line of each map
+@group(0) @binding(1) var<storage, read> map_ends: array<u32>;
+@group(0) @binding(2) var<uniform> params: Params;
+@group(0) @binding(3) var<storage, read_write> lowest: atomic<u32>;
+
+@compute @workgroup_size(256)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.count) {
+        return;
+    }
+
+    var value = params.first_seed + id.x;
+    var start = 0u;
+    for (var m = 0u; m < params.maps; m++) {
+        let end = map_ends[m];
+        for (var i = start; i < end; i++) {
+            let line = mappings[i];
+            // Written as a difference so that the end of a line at the top of the range cannot overflow
+            if (value >= line.source && value - line.source < line.length) {
+                value = line.destination + (value - line.source);
+                break;
+            }
+        }
+        start = end;
+    }
+
+    atomicMin(&lowest, value);
+}
@@ -0,0 +1,677 @@
+/*                        DESKTOP VIEWER
+
+A desktop window for solving one day at a time. The registered days are listed
+on the left, and the input of the selected day can be pasted into the editor,
+opened from a file or dropped onto the window. Inputs found in the data
+directory are loaded as the viewer starts.
+
+A part is solved on a background thread which publishes its progress to the
+window, so the viewer stays responsive while a slow implementation such as the
+day 5 brute force is working. Days solved in more than one way can be run with
+any of them, see `solver::alternatives`.
+
+Where it helps the input is drawn as well as solved, the schematic of day 3 as
+a grid with its part numbers, symbols and gears picked out, and the almanac of
+day 5 as the ranges of values the seeds reach in each category. Hovering over
+either gives the details of the cell or range beneath the pointer.
+
+*/
+
+use eframe::egui::{self, Align2, Color32, FontId, Pos2, Rect, Sense, Vec2};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+
+use crate::day_3::Schematic;
+use crate::day_5::{Almanac, CategoryRanges};
+use crate::error::AocError;
+use crate::progress::ProgressEvent;
+use crate::runner::{run_part_with_progress, InputSet, PartResult};
+use crate::solver::alternatives;
+use crate::util::hash::FxHashMap;
+use crate::util::io::InputSource;
+
+// The symbol marking a gear in the day 3 schematic
+const GEAR_SYMBOL: char = '*';
+
+// Sent by the thread solving a part
+enum Update {
+    Progress(ProgressEvent),
+    Finished(PartResult)
+}
+
+// A part being solved in the background
+struct Job {
+    day: u32,
+    part: u32,
+    receiver: Receiver<Update>,
+    progress: Option<ProgressEvent>
+}
+
+// How a cell of a day 3 schematic is drawn, cells not listed are empty
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    // A digit of a number neighbouring a symbol
+    Part,
+    // A digit of a number which is not a part
+    Number,
+    Symbol,
+    // A gear symbol with exactly two part numbers around it
+    Gear
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Visual {
+    Grid {
+        rows: Vec<Vec<char>>,
+        cells: FxHashMap<(usize, usize), Cell>
+    },
+    // The values reached in each category, in the order they are mapped
+    Ranges(CategoryRanges)
+}
+
+pub fn visualise(day: u32, part: u32, input: &str) -> Result<Option<Visual>, AocError> {
+    /* A drawing of the input of a day, for those days which have one.
+
+    # Arguments
+
+    * `day` - the day of the input, 3 and 5 are drawn
+    * `part` - the part being solved, deciding whether the seeds of day 5 are ranges
+
+    # Examples
+
+    ```
+    let grid = visualise(3, 1, "467..114..\n...*......").unwrap();
+    ```
+    */
+    match day {
+        3 => Ok(Some(schematic_grid(input)?)),
+        5 => Ok(Some(Visual::Ranges(Almanac::parse(input)?.category_ranges(part == 2)?))),
+        _ => Ok(None)
+    }
+}
+
+fn schematic_grid(input: &str) -> Result<Visual, AocError> {
+    /* The cells of a schematic, with its digits marked by whether they make up a part. */
+    let schematic = Schematic::parse(input)?;
+    let rows: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+
+    let mut cells = FxHashMap::<(usize, usize), Cell>::default();
+    for (i, row) in rows.iter().enumerate() {
+        for (j, c) in row.iter().enumerate() {
+            match c {
+                '0'..='9' => cells.insert((i, j), Cell::Number),
+                '.' => None,
+                _ => cells.insert((i, j), Cell::Symbol)
+            };
+        }
+    }
+
+    for ((row, column), length) in schematic.part_spans() {
+        for j in column..column + length {
+            cells.insert((row, j), Cell::Part);
+        }
+    }
+
+    for (symbol, parts) in schematic.parts_by_symbol() {
+        if symbol.symbol == GEAR_SYMBOL && parts.len() == 2 {
+            cells.insert(symbol.position, Cell::Gear);
+        }
+    }
+
+    Ok(Visual::Grid { rows, cells })
+}
+
+pub struct Viewer {
+    inputs: InputSet,
+    days: Vec<u32>,
+    selected: usize,
+    part: u32,
+    implementation: &'static str,
+    // The input of the selected day as edited, kept in the input set as it changes
+    editor: String,
+    input_path: String,
+    results: BTreeMap<(u32, u32), PartResult>,
+    job: Option<Job>,
+    visual: Result<Option<Visual>, String>,
+    message: Option<String>,
+    cell_size: f32
+}
+
+impl Viewer {
+    pub fn new(inputs: InputSet, data_dir: impl AsRef<Path>) -> Viewer {
+        /* A viewer over every registered day, with the first day selected and nothing solved yet.
+
+        Files opened by name are looked for in `data_dir` by default.
+        */
+        let days = inputs.solvers().iter().map(|s| s.day()).collect();
+        let mut viewer = Viewer {
+            inputs,
+            days,
+            selected: 0,
+            part: 1,
+            implementation: "default",
+            editor: String::new(),
+            input_path: String::new(),
+            results: BTreeMap::new(),
+            job: None,
+            visual: Ok(None),
+            message: None,
+            cell_size: 12.0
+        };
+        viewer.select(0, data_dir.as_ref());
+        viewer
+    }
+
+    pub fn selected_day(&self) -> Option<u32> {
+        self.days.get(self.selected).copied()
+    }
+
+    pub fn select(&mut self, index: usize, data_dir: &Path) {
+        /* Select a day, showing its input and defaulting to its input file in `data_dir`. */
+        let day = match self.days.get(index) {
+            Some(&d) => d,
+            None => return
+        };
+
+        self.selected = index;
+        self.implementation = "default";
+        self.editor = self.inputs.get(day).unwrap_or_default().to_string();
+        self.input_path = data_dir.join(format!("day_{}.dat", day)).display().to_string();
+        self.refresh_visual();
+    }
+
+    pub fn set_part(&mut self, part: u32) {
+        self.part = part;
+        self.refresh_visual();
+    }
+
+    pub fn set_input(&mut self, input: &str) {
+        /* Replace the input of the selected day, as when pasting over the editor. */
+        self.editor = input.to_string();
+        self.input_changed();
+    }
+
+    pub fn open(&mut self, input_file: impl AsRef<Path>) -> Result<(), AocError> {
+        /* Read the input of the selected day from a file, which may be compressed or archived, see `InputSource`. */
+        let input = InputSource::read(input_file.as_ref()).and_then(InputSource::into_string)?;
+        self.input_path = input_file.as_ref().display().to_string();
+        self.set_input(&input);
+        Ok(())
+    }
+
+    fn input_changed(&mut self) {
+        if let Some(day) = self.selected_day() {
+            self.inputs.insert(day, self.editor.clone());
+        }
+        self.refresh_visual();
+    }
+
+    fn refresh_visual(&mut self) {
+        self.visual = match self.selected_day() {
+            Some(day) => visualise(day, self.part, &self.editor).map_err(|e| e.to_string()),
+            None => Ok(None)
+        };
+    }
+
+    pub fn visual(&self) -> Result<Option<&Visual>, &str> {
+        match &self.visual {
+            Ok(v) => Ok(v.as_ref()),
+            Err(e) => Err(e)
+        }
+    }
+
+    pub fn result(&self, day: u32, part: u32) -> Option<&PartResult> {
+        self.results.get(&(day, part))
+    }
+
+    pub fn progress(&self) -> Option<&ProgressEvent> {
+        self.job.as_ref().and_then(|j| j.progress.as_ref())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.job.is_some()
+    }
+
+    pub fn start(&mut self) -> Result<(), String> {
+        /* Solve the selected part of the selected day on a background thread, see `poll`. */
+        if self.is_running() {
+            return Err("A part is already being solved".to_string());
+        }
+
+        let day = match self.selected_day() {
+            Some(d) => d,
+            None => return Err("No day is selected".to_string())
+        };
+        let solver = match alternatives(day).into_iter().find(|(name, _)| *name == self.implementation) {
+            Some((_, s)) => s,
+            None => return Err(format!("Day {} has no implementation '{}'", day, self.implementation))
+        };
+
+        let (part, input) = (self.part, self.editor.clone());
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            let progress_sender = sender.clone();
+            // A window which has gone away no longer wants updates, this should not stop the solver
+            let mut publish = |e: &ProgressEvent| {
+                let _ = progress_sender.send(Update::Progress(e.clone()));
+            };
+            let result = run_part_with_progress(solver.as_ref(), part, &input, &mut publish);
+            let _ = sender.send(Update::Finished(result));
+        });
+
+        self.job = Some(Job { day, part, receiver, progress: None });
+        self.message = None;
+        Ok(())
+    }
+
+    pub fn poll(&mut self) {
+        /* Take the updates published by the part being solved, keeping its result once it has finished. */
+        let job = match &mut self.job {
+            Some(j) => j,
+            None => return
+        };
+
+        let (mut finished, mut disconnected) = (None, false);
+        loop {
+            match job.receiver.try_recv() {
+                Ok(Update::Progress(e)) => job.progress = Some(e),
+                Ok(Update::Finished(r)) => {
+                    finished = Some(r);
+                    break;
+                },
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    disconnected = true;
+                    break;
+                }
+            };
+        }
+
+        match finished {
+            Some(result) => {
+                self.results.insert((job.day, job.part), result);
+                self.job = None;
+            },
+            // A thread which went away without a result has panicked
+            None if disconnected => {
+                self.message = Some(format!("Solving day {} part {} failed", job.day, job.part));
+                self.job = None;
+            },
+            None => ()
+        };
+    }
+
+    fn days_panel(&mut self, ui: &mut egui::Ui, data_dir: &Path) {
+        ui.heading("Days");
+        for index in 0..self.days.len() {
+            let day = self.days[index];
+            let solved = [1, 2].iter().filter(|&&p| matches!(self.result(day, p), Some(PartResult { answer: Some(_), .. }))).count();
+            let label = format!("Day {:>2}  {}", day, "*".repeat(solved));
+            if ui.selectable_label(index == self.selected, label).clicked() && index != self.selected {
+                self.select(index, data_dir);
+            }
+        }
+    }
+
+    fn controls(&mut self, ui: &mut egui::Ui) {
+        let day = self.selected_day().unwrap_or(0);
+
+        ui.horizontal(|ui| {
+            for part in [1, 2] {
+                if ui.selectable_label(self.part == part, format!("Part {}", part)).clicked() && self.part != part {
+                    self.set_part(part);
+                }
+            }
+
+            let names: Vec<&'static str> = alternatives(day).into_iter().map(|(name, _)| name).collect();
+            egui::ComboBox::from_id_salt("implementation")
+                .selected_text(self.implementation)
+                .show_ui(ui, |ui| {
+                    for name in names {
+                        ui.selectable_value(&mut self.implementation, name, name);
+                    }
+                });
+
+            let solve = ui.add_enabled(!self.is_running(), egui::Button::new("Solve"));
+            if solve.clicked() {
+                if let Err(e) = self.start() {
+                    self.message = Some(e);
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Input file");
+            ui.text_edit_singleline(&mut self.input_path);
+            if ui.button("Open").clicked() {
+                let input_file = PathBuf::from(&self.input_path);
+                self.message = self.open(&input_file).err().map(|e| e.to_string());
+            }
+        });
+
+        if let Some(job) = &self.job {
+            let text = match &job.progress {
+                Some(e) => format!("Day {} part {}: {} {}/{}", job.day, job.part, e.stage, e.done, e.total),
+                None => format!("Solving day {} part {}", job.day, job.part)
+            };
+            let fraction = job.progress.as_ref().map(|e| e.fraction() as f32).unwrap_or(0.0);
+            ui.add(egui::ProgressBar::new(fraction).text(text).animate(true));
+        }
+
+        match self.result(day, self.part) {
+            Some(PartResult { answer: Some(a), elapsed_ms, diagnostics, .. }) => {
+                ui.label(egui::RichText::new(format!("Answer: {}  ({:.3} ms)", a, elapsed_ms)).strong());
+                for d in diagnostics {
+                    ui.colored_label(Color32::YELLOW, d);
+                }
+            },
+            Some(PartResult { error: Some(e), .. }) => {
+                ui.colored_label(Color32::LIGHT_RED, e);
+            },
+            _ => ()
+        };
+
+        if let Some(m) = &self.message {
+            ui.colored_label(Color32::LIGHT_RED, m);
+        }
+    }
+
+    fn draw_visual(&mut self, ui: &mut egui::Ui) {
+        let visual = match &self.visual {
+            Ok(Some(v)) => v,
+            Ok(None) => {
+                ui.label("There is no drawing for this day");
+                return;
+            },
+            Err(e) => {
+                ui.colored_label(Color32::LIGHT_RED, format!("The input could not be drawn: {}", e));
+                return;
+            }
+        };
+
+        match visual {
+            Visual::Grid { rows, cells } => {
+                ui.add(egui::Slider::new(&mut self.cell_size, 4.0..=24.0).text("Cell size"));
+                egui::ScrollArea::both().id_salt("grid").show(ui, |ui| draw_grid(ui, rows, cells, self.cell_size));
+            },
+            Visual::Ranges(categories) => {
+                egui::ScrollArea::vertical().id_salt("ranges").show(ui, |ui| draw_ranges(ui, categories));
+            }
+        };
+    }
+}
+
+fn cell_colour(cell: Option<&Cell>) -> Color32 {
+    match cell {
+        Some(Cell::Part) => Color32::from_rgb(80, 170, 90),
+        Some(Cell::Number) => Color32::from_rgb(110, 110, 110),
+        Some(Cell::Symbol) => Color32::from_rgb(220, 160, 50),
+        Some(Cell::Gear) => Color32::from_rgb(220, 70, 60),
+        None => Color32::from_gray(30)
+    }
+}
+
+fn draw_grid(ui: &mut egui::Ui, rows: &[Vec<char>], cells: &FxHashMap<(usize, usize), Cell>, cell_size: f32) {
+    /* Draw a schematic cell by cell, with the characters shown once the cells are large enough to read. */
+    let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let size = Vec2::new(columns as f32 * cell_size, rows.len() as f32 * cell_size);
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    let origin = response.rect.min;
+
+    for (i, row) in rows.iter().enumerate() {
+        for (j, c) in row.iter().enumerate() {
+            let min = origin + Vec2::new(j as f32 * cell_size, i as f32 * cell_size);
+            let cell_rect = Rect::from_min_size(min, Vec2::splat(cell_size - 1.0));
+            painter.rect_filled(cell_rect, 0.0, cell_colour(cells.get(&(i, j))));
+            if cell_size >= 10.0 && *c != '.' {
+                painter.text(cell_rect.center(), Align2::CENTER_CENTER, c, FontId::monospace(cell_size * 0.8), Color32::WHITE);
+            }
+        }
+    }
+
+    if let Some(pointer) = response.hover_pos() {
+        let offset = pointer - origin;
+        let (i, j) = ((offset.y / cell_size) as usize, (offset.x / cell_size) as usize);
+        if let Some(c) = rows.get(i).and_then(|r| r.get(j)) {
+            let kind = match cells.get(&(i, j)) {
+                Some(Cell::Part) => "part number",
+                Some(Cell::Number) => "not a part",
+                Some(Cell::Symbol) => "symbol",
+                Some(Cell::Gear) => "gear",
+                None => "empty"
+            };
+            response.on_hover_text(format!("Row {}, column {}: '{}' {}", i, j, c, kind));
+        }
+    }
+}
+
+fn draw_ranges(ui: &mut egui::Ui, categories: &[(String, Vec<(i64, i64)>)]) {
+    /* Draw the ranges reached in each category as bars along a shared axis of values. */
+    let (lowest, highest) = categories
+        .iter()
+        .flat_map(|(_, ranges)| ranges)
+        .fold((i64::MAX, i64::MIN), |(lo, hi), &(lower, upper)| (lo.min(lower), hi.max(upper)));
+    if lowest > highest {
+        ui.label("The almanac lists no seeds");
+        return;
+    }
+
+    let (label_width, row_height) = (100.0, 28.0);
+    let width = ui.available_width().max(label_width + 100.0);
+    let size = Vec2::new(width, categories.len() as f32 * row_height);
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    let origin = response.rect.min;
+
+    // The span is at least one so that a single seed still has an axis to be drawn on
+    let span = (highest - lowest).max(1) as f64;
+    let x = |value: i64| origin.x + label_width + ((value - lowest) as f64 / span) as f32 * (width - label_width);
+
+    let mut hovered: Option<String> = None;
+    for (i, (category, ranges)) in categories.iter().enumerate() {
+        let top = origin.y + i as f32 * row_height;
+        painter.text(Pos2::new(origin.x, top + row_height / 2.0), Align2::LEFT_CENTER, category, FontId::proportional(14.0), ui.visuals().text_color());
+
+        for &(lower, upper) in ranges {
+            // Every range is drawn at least a couple of points wide, however narrow
+            let bar = Rect::from_min_max(Pos2::new(x(lower), top + 4.0), Pos2::new(x(upper).max(x(lower) + 2.0), top + row_height - 4.0));
+            painter.rect_filled(bar, 2.0, Color32::from_rgb(70, 130, 200));
+
+            if response.hover_pos().is_some_and(|p| bar.expand2(Vec2::new(2.0, 0.0)).contains(p)) {
+                hovered = Some(format!("{}: {} to {} ({} values)", category, lower, upper, upper - lower + 1));
+            }
+        }
+    }
+
+    painter.text(origin + Vec2::new(label_width, size.y), Align2::LEFT_TOP, lowest.to_string(), FontId::monospace(11.0), ui.visuals().weak_text_color());
+    painter.text(origin + size, Align2::RIGHT_TOP, highest.to_string(), FontId::monospace(11.0), ui.visuals().weak_text_color());
+    ui.add_space(16.0);
+
+    if let Some(h) = hovered {
+        response.on_hover_text(h);
+    }
+}
+
+// The viewer as an eframe application, with the directory files are opened from by default
+struct ViewerApp {
+    viewer: Viewer,
+    data_dir: PathBuf
+}
+
+impl eframe::App for ViewerApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let viewer = &mut self.viewer;
+        viewer.poll();
+        if viewer.is_running() {
+            // Updates from the solving thread are only taken while the window is drawn
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        }
+
+        let dropped: Vec<PathBuf> = ctx.input(|i| i.raw.dropped_files.iter().filter_map(|f| f.path.clone()).collect());
+        if let Some(input_file) = dropped.first() {
+            viewer.message = viewer.open(input_file).err().map(|e| e.to_string());
+        }
+
+        egui::SidePanel::left("days").resizable(false).show(ctx, |ui| viewer.days_panel(ui, &self.data_dir));
+
+        egui::TopBottomPanel::top("controls").show(ctx, |ui| viewer.controls(ui));
+
+        egui::SidePanel::right("input").default_width(360.0).show(ctx, |ui| {
+            ui.heading("Input");
+            egui::ScrollArea::vertical().id_salt("editor").show(ui, |ui| {
+                let editor = egui::TextEdit::multiline(&mut viewer.editor)
+                    .code_editor()
+                    .hint_text("Paste an input here, or drop a file onto the window")
+                    .desired_width(f32::INFINITY);
+                if ui.add(editor).changed() {
+                    viewer.input_changed();
+                }
+            });
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| viewer.draw_visual(ui));
+    }
+}
+
+pub fn run(inputs: InputSet, data_dir: impl AsRef<Path>) -> Result<(), String> {
+    /* Show the viewer until its window is closed, over the given inputs. */
+    let data_dir = data_dir.as_ref().to_path_buf();
+    let viewer = Viewer::new(inputs, &data_dir);
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([1200.0, 800.0]),
+        ..Default::default()
+    };
+
+    match eframe::run_native("Advent of Code 2023", options, Box::new(|_| Ok(Box::new(ViewerApp { viewer, data_dir })))) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Failed to open the viewer: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answer::Answer;
+    use std::time::{Duration, Instant};
+
+    fn wait_for(viewer: &mut Viewer) {
+        let start = Instant::now();
+        while viewer.is_running() {
+            assert!(start.elapsed() < Duration::from_secs(60), "the part was not solved in time");
+            thread::sleep(Duration::from_millis(5));
+            viewer.poll();
+        }
+    }
+
+    #[test]
+    fn test_viewer() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let mut viewer = Viewer::new(InputSet::from_dir(&test_dir).unwrap(), &test_dir);
+
+        assert_eq!(viewer.days, vec![1, 2, 3, 4, 5]);
+        assert_eq!(viewer.selected_day(), Some(1));
+        assert!(viewer.editor.is_empty());
+        assert!(viewer.input_path.ends_with("day_1.dat"));
+
+        viewer.select(2, &test_dir);
+        viewer.set_part(2);
+        viewer.start().unwrap();
+        assert!(viewer.start().is_err());
+        wait_for(&mut viewer);
+        assert_eq!(viewer.result(3, 2).unwrap().answer, Some(Answer::Int(467835)));
+        assert!(viewer.result(3, 1).is_none());
+
+        viewer.select(4, &test_dir);
+        viewer.implementation = "bruteforce";
+        viewer.start().unwrap();
+        wait_for(&mut viewer);
+        assert_eq!(viewer.result(5, 2).unwrap().answer, Some(Answer::Int(46)));
+
+        viewer.set_input("seeds: 1 x");
+        viewer.set_part(1);
+        viewer.start().unwrap();
+        wait_for(&mut viewer);
+        assert!(viewer.result(5, 1).unwrap().error.is_some());
+        assert!(viewer.visual().is_err());
+
+        // Edits are kept when moving to another day and back
+        viewer.select(0, &test_dir);
+        viewer.select(4, &test_dir);
+        assert_eq!(viewer.editor, "seeds: 1 x");
+        assert_eq!(viewer.implementation, "default");
+    }
+
+    #[test]
+    fn test_poll() {
+        let mut viewer = Viewer::new(InputSet::new(), "data");
+        let (sender, receiver) = channel();
+        viewer.job = Some(Job { day: 5, part: 2, receiver, progress: None });
+
+        let event = ProgressEvent { day: 5, stage: "seeds", done: 1, total: 4 };
+        sender.send(Update::Progress(event.clone())).unwrap();
+        viewer.poll();
+        assert!(viewer.is_running());
+        assert_eq!(viewer.progress(), Some(&event));
+
+        let result = PartResult { part: 2, answer: Some(Answer::Int(46)), error: None, diagnostics: Vec::new(), elapsed_ms: 1.0 };
+        sender.send(Update::Finished(result.clone())).unwrap();
+        viewer.poll();
+        assert!(!viewer.is_running());
+        assert_eq!(viewer.result(5, 2), Some(&result));
+
+        // A thread which stops without sending a result is reported rather than waited on forever
+        let (sender, receiver) = channel();
+        viewer.job = Some(Job { day: 5, part: 1, receiver, progress: None });
+        drop(sender);
+        viewer.poll();
+        assert!(!viewer.is_running());
+        assert!(viewer.message.as_ref().unwrap().contains("day 5 part 1"));
+    }
+
+    #[test]
+    fn test_open() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let mut viewer = Viewer::new(InputSet::new(), &test_dir);
+        viewer.select(1, &test_dir);
+        assert!(viewer.editor.is_empty());
+
+        viewer.open(test_dir.join("day_2.dat")).unwrap();
+        assert!(viewer.editor.starts_with("Game 1:"));
+        assert_eq!(viewer.inputs.get(2), Some(viewer.editor.as_str()));
+        assert!(viewer.open(test_dir.join("day_0.dat")).is_err());
+        assert!(viewer.visual().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_visualise() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+
+        let schematic = std::fs::read_to_string(test_dir.join("day_3.dat")).unwrap();
+        let (rows, cells) = match visualise(3, 1, &schematic).unwrap() {
+            Some(Visual::Grid { rows, cells }) => (rows, cells),
+            v => panic!("Expected a grid, got {:?}", v)
+        };
+        assert_eq!(rows.len(), 10);
+        assert_eq!(cells.get(&(0, 0)), Some(&Cell::Part));
+        assert_eq!(cells.get(&(0, 5)), Some(&Cell::Number));
+        assert_eq!(cells.get(&(0, 3)), None);
+        assert_eq!(cells.get(&(1, 3)), Some(&Cell::Gear));
+        assert_eq!(cells.get(&(3, 6)), Some(&Cell::Symbol));
+        // A '*' beside a single part is not a gear
+        assert_eq!(cells.get(&(4, 3)), Some(&Cell::Symbol));
+
+        let almanac = std::fs::read_to_string(test_dir.join("day_5.dat")).unwrap();
+        match visualise(5, 2, &almanac).unwrap() {
+            Some(Visual::Ranges(categories)) => {
+                assert_eq!(categories.first().unwrap().1, vec![(55, 67), (79, 92)]);
+                assert_eq!(categories.last().unwrap().1[0].0, 46);
+            },
+            v => panic!("Expected ranges, got {:?}", v)
+        };
+
+        assert!(visualise(4, 1, "Card 1: 1 | 1").unwrap().is_none());
+        assert!(visualise(5, 1, "seeds: x").is_err());
+    }
+}
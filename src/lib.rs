@@ -1,5 +1,41 @@
-pub mod day_1;
-pub mod day_2;
-pub mod day_3;
-pub mod day_4;
-pub mod day_5;
\ No newline at end of file
+#[cfg(feature = "async")]
+pub mod async_runner;
+pub mod bench;
+pub mod budget;
+pub mod cache;
+pub mod config;
+pub mod detect;
+#[cfg(feature = "bundle")]
+pub mod embedded;
+pub mod event;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+#[cfg(feature = "gui")]
+pub mod gui;
+pub mod logging;
+pub mod notify;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+pub mod profiling;
+pub mod reference;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod selftest;
+pub mod stats;
+pub mod store;
+pub mod submit;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod unlock;
+pub mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod watch;
+// The day modules, answers, errors and solvers, see crates/aoc-core, as well as aoc_utils as util
+pub use aoc_core::*;
+pub use event::{event, events, Event};
+pub use runner::{run_all, DayResult, InputSet, PartResult};
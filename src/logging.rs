@@ -0,0 +1,150 @@
+/*                        LOGGING
+
+Configures a tracing subscriber for the command line and tests. Each day wraps its
+parse and solve stages in spans carrying the day and part, and the time spent
+within a span is reported when it closes. The verbosity is set using the
+RUST_LOG environment variable, e.g. `RUST_LOG=debug` or `RUST_LOG=aoc23::day_5=debug`.
+
+Logs are human readable text by default. Setting AOC23_LOG_FORMAT=json, the
+`log_format` setting or `--log-format json` on the command line instead writes
+one JSON object per line for ingestion by jq or a log pipeline, with the event
+fields flattened to the top level and the enclosing stage given by the span
+name, e.g.
+
+{"timestamp":"..","level":"INFO","message":"close","time.busy":"4.7ms","span":{"day":3,"part":2,"name":"solve"},..}
+
+*/
+
+use serde::Deserialize;
+use std::str::FromStr;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::EnvFilter;
+
+use crate::config::Config;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Text,
+    Json
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<LogFormat, String> {
+        match format.to_lowercase().as_str() {
+            "" | "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("Unrecognised log format '{}', expected 'text' or 'json'", format))
+        }
+    }
+}
+
+impl LogFormat {
+    pub fn from_env() -> Result<LogFormat, String> {
+        /* Read the log format from the AOC23_LOG_FORMAT environment variable, defaulting to text. */
+        Ok(LogFormat::env_format()?.unwrap_or(LogFormat::Text))
+    }
+
+    fn env_format() -> Result<Option<LogFormat>, String> {
+        match std::env::var("AOC23_LOG_FORMAT") {
+            Ok(f) => f.parse().map(Some),
+            Err(_) => Ok(None)
+        }
+    }
+}
+
+pub fn init() -> Result<(), String> {
+    /* Install the global subscriber in the format given by AOC23_LOG_FORMAT.
+
+    Logs at 'info' level unless RUST_LOG is set. Returns an error if a subscriber has
+    already been installed, e.g. by another test.
+
+    # Examples
+
+    ```
+    match aoc23::logging::init() {
+        Ok(l) => l,
+        Err(_) => ()
+    };
+    ```
+    */
+    init_with_format(LogFormat::from_env()?)
+}
+
+pub fn init_with_format(format: LogFormat) -> Result<(), String> {
+    install(format, "info", false)
+}
+
+pub fn init_for_cli(config: &Config, format: Option<LogFormat>) -> Result<(), String> {
+    /* Install the global subscriber for the command line runner.
+
+    Only warnings are logged unless RUST_LOG or the configured log level is set, and logs
+    are written to stderr so that the answers printed on stdout can be piped elsewhere.
+    The format given on the command line is used, else that of AOC23_LOG_FORMAT, else
+    the configured log format, else text.
+    */
+    let format = match (format, LogFormat::env_format()?, config.log_format) {
+        (Some(f), _, _) | (None, Some(f), _) | (None, None, Some(f)) => f,
+        (None, None, None) => LogFormat::Text
+    };
+    install(format, config.log_level.as_deref().unwrap_or("warn"), true)
+}
+
+pub fn init_with_writer<W>(writer: W, config: &Config) -> Result<(), String>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static
+{
+    /* Install the global subscriber writing plain text logs to the given writer.
+
+    Used where the logs are shown by the program itself, such as the dashboard,
+    so colours are left out. Logs at 'info' level unless RUST_LOG or the configured
+    log level is set.
+    */
+    let filter = default_filter(config.log_level.as_deref().unwrap_or("info"))?;
+
+    let result = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .with_writer(writer)
+        .try_init();
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Failed to initialise logging: {}", e))
+    }
+}
+
+fn default_filter(level: &str) -> Result<EnvFilter, String> {
+    /* The filter given by RUST_LOG, or else the given level, e.g. `debug` or `aoc23::day_5=debug`. */
+    match EnvFilter::try_from_default_env() {
+        Ok(f) => Ok(f),
+        Err(_) => match EnvFilter::try_new(level) {
+            Ok(f) => Ok(f),
+            Err(e) => Err(format!("Invalid log level '{}': {}", level, e))
+        }
+    }
+}
+
+fn install(format: LogFormat, level: &str, to_stderr: bool) -> Result<(), String> {
+    let filter = default_filter(level)?;
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE);
+
+    let result = match (format, to_stderr) {
+        (LogFormat::Text, false) => builder.try_init(),
+        (LogFormat::Text, true) => builder.with_writer(std::io::stderr).try_init(),
+        (LogFormat::Json, false) => builder.json().flatten_event(true).with_current_span(true).with_span_list(false).try_init(),
+        (LogFormat::Json, true) => builder.with_writer(std::io::stderr).json().flatten_event(true).with_current_span(true).with_span_list(false).try_init()
+    };
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Failed to initialise logging: {}", e))
+    }
+}
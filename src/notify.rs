@@ -0,0 +1,232 @@
+/*                        COMPLETION WEBHOOKS
+
+Posts the answers and times of `aoc23 all` and `aoc23 run` to a webhook once
+they finish, so that a brute force solution taking minutes need not be
+watched. The webhook is set in the configuration, see src/config.rs:
+
+[webhook]
+url = "https://discord.com/api/webhooks/..."
+format = "discord"                 # 'json' (default), 'slack' or 'discord'
+min_ms = 60000                     # only runs taking at least this long, 0 by default
+
+Slack and Discord are sent a message with a line for each part, as the text
+or content of their incoming webhooks. Any other endpoint is sent the results
+as JSON, in the form printed by `aoc23 all`, along with the message.
+
+Calling the webhook needs the fetch feature, which brings in the HTTP client.
+A webhook which cannot be called is logged as a warning, so the run itself
+still succeeds. Nothing is posted in offline mode, as with `--offline`.
+
+*/
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::error::AocError;
+use crate::runner::DayResult;
+
+// Discord rejects messages longer than this many characters
+const DISCORD_MAX_CONTENT: usize = 2000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    #[default]
+    Json,
+    Slack,
+    Discord
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Webhook {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    // Runs finishing sooner than this are not reported
+    #[serde(default)]
+    pub min_ms: u64
+}
+
+// A finished run of a command, as reported to the webhook
+#[derive(Clone, Debug, PartialEq)]
+pub struct Completion {
+    pub command: String,
+    pub year: u32,
+    pub elapsed: Duration,
+    pub results: Vec<DayResult>
+}
+
+impl Completion {
+    pub fn failed(&self) -> usize {
+        /* The number of parts which gave an error rather than an answer. */
+        self.results.iter().flat_map(|d| &d.parts).filter(|p| p.error.is_some()).count()
+    }
+
+    pub fn summary(&self) -> String {
+        /* A message with a line for the run and one for each part.
+
+        # Examples
+
+        ```
+        aoc23 all for 2023 finished in 93.412 s, 2 of 2 parts solved
+        Day 5 part 1: 51580674 (1.204 ms)
+        Day 5 part 2: 99751240 (93.402 s)
+        ```
+        */
+        let parts = self.results.iter().map(|d| d.parts.len()).sum::<usize>();
+        let mut message = format!(
+            "aoc23 {} for {} finished in {:.3} s, {} of {} parts solved",
+            self.command, self.year, self.elapsed.as_secs_f64(), parts - self.failed(), parts
+        );
+
+        for day in &self.results {
+            for part in &day.parts {
+                let outcome = match (&part.answer, &part.error) {
+                    (Some(answer), _) => answer.to_string(),
+                    (None, Some(error)) => format!("failed, {}", error),
+                    (None, None) => "no answer".to_string()
+                };
+                let elapsed = format_duration(Duration::from_secs_f64(part.elapsed_ms / 1000.0));
+                message.push_str(&format!("\nDay {} part {}: {} ({})", day.day, part.part, outcome, elapsed));
+            }
+        }
+        message
+    }
+}
+
+pub fn payload(format: WebhookFormat, completion: &Completion) -> Value {
+    /* The body posted to a webhook of the given kind. */
+    let summary = completion.summary();
+    match format {
+        WebhookFormat::Slack => json!({ "text": summary }),
+        WebhookFormat::Discord => {
+            let content = if summary.chars().count() > DISCORD_MAX_CONTENT {
+                summary.chars().take(DISCORD_MAX_CONTENT - 3).collect::<String>() + "..."
+            } else {
+                summary
+            };
+            json!({ "content": content })
+        },
+        WebhookFormat::Json => json!({
+            "command": completion.command,
+            "year": completion.year,
+            "elapsed_ms": completion.elapsed.as_secs_f64() * 1000.0,
+            "failed": completion.failed(),
+            "summary": summary,
+            "results": completion.results
+        })
+    }
+}
+
+pub fn send(webhook: &Webhook, completion: &Completion) -> Result<bool, AocError> {
+    /* Post a finished run to the webhook unless it was quicker than its minimum, returning whether it was posted.
+
+    # Examples
+
+    ```
+    let completion = Completion { command: "all".to_string(), year: 2023, elapsed: start.elapsed(), results };
+    if let Some(webhook) = &config.webhook {
+        send(webhook, &completion)?;
+    }
+    ```
+    */
+    #[cfg(feature = "fetch")]
+    {
+        if crate::fetch::is_offline() {
+            tracing::warn!("Not posting the results of {} to the webhook in offline mode", completion.command);
+            return Ok(false);
+        }
+    }
+
+    if completion.elapsed < Duration::from_millis(webhook.min_ms) {
+        return Ok(false);
+    }
+
+    let _span = tracing::info_span!("webhook", command = completion.command).entered();
+    post(&webhook.url, &payload(webhook.format, completion).to_string())?;
+    tracing::info!("Posted the results of {} to the webhook", completion.command);
+    Ok(true)
+}
+
+#[cfg(feature = "fetch")]
+fn post(url: &str, body: &str) -> Result<(), AocError> {
+    // The URL of a webhook is its password, so is left out of the error
+    match ureq::post(url).header("Content-Type", "application/json").send(body) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(AocError::InvalidArgument(format!("Failed to call the webhook: {}", e)))
+    }
+}
+
+#[cfg(not(feature = "fetch"))]
+fn post(_url: &str, _body: &str) -> Result<(), AocError> {
+    Err(AocError::InvalidArgument("Calling a webhook needs the fetch feature".to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::runner::PartResult;
+
+    fn completion() -> Completion {
+        let part = |part, answer: Option<Answer>, error: Option<&str>| PartResult {
+            part, answer, error: error.map(|e| e.to_string()), diagnostics: Vec::new(), elapsed_ms: 1.5
+        };
+        Completion {
+            command: "all".to_string(),
+            year: 2023,
+            elapsed: Duration::from_millis(93412),
+            results: vec![DayResult { day: 5, parts: vec![part(1, Some(Answer::Int(35)), None), part(2, None, Some("No seeds found"))] }]
+        }
+    }
+
+    #[test]
+    fn test_payload() {
+        let completion = completion();
+        assert_eq!(completion.failed(), 1);
+        assert_eq!(completion.summary(), "aoc23 all for 2023 finished in 93.412 s, 1 of 2 parts solved\n\
+            Day 5 part 1: 35 (1.500 ms)\nDay 5 part 2: failed, No seeds found (1.500 ms)");
+
+        assert_eq!(payload(WebhookFormat::Slack, &completion), json!({ "text": completion.summary() }));
+        assert_eq!(payload(WebhookFormat::Discord, &completion), json!({ "content": completion.summary() }));
+
+        let generic = payload(WebhookFormat::Json, &completion);
+        assert_eq!(generic["command"], "all");
+        assert_eq!(generic["failed"], 1);
+        assert_eq!(generic["results"][0]["parts"][0]["answer"], 35);
+
+        let mut long = completion.clone();
+        long.results = vec![long.results[0].clone(); 100];
+        let content = payload(WebhookFormat::Discord, &long)["content"].as_str().unwrap().to_string();
+        assert_eq!(content.chars().count(), DISCORD_MAX_CONTENT);
+        assert!(content.ends_with("..."));
+    }
+
+    #[test]
+    fn test_send_skips_quick_runs() {
+        #[cfg(feature = "fetch")]
+        let _lock = crate::fetch::OFFLINE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Nothing listens on the discard port, so a run which was posted fails
+        let webhook = Webhook { url: "http://127.0.0.1:9/hook".to_string(), format: WebhookFormat::Json, min_ms: 100_000 };
+        assert!(!send(&webhook, &completion()).unwrap());
+        assert!(send(&Webhook { min_ms: 0, ..webhook }, &completion()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "fetch")]
+    fn test_send_offline() {
+        let _lock = crate::fetch::OFFLINE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        // This would fail if it were posted, as nothing listens on the discard port
+        let webhook = Webhook { url: "http://127.0.0.1:9/hook".to_string(), format: WebhookFormat::Json, min_ms: 0 };
+        crate::fetch::set_offline(true);
+        let sent = send(&webhook, &completion());
+        crate::fetch::set_offline(false);
+
+        assert!(!sent.unwrap());
+    }
+}
@@ -0,0 +1,194 @@
+/*                        SOLVER PLUGINS
+
+With the plugins feature, solvers for a day can be loaded from shared
+libraries built outside this crate, e.g. another approach to a puzzle or one
+written in C, and then run or benchmarked alongside the built in solvers:
+
+aoc23 run --day 4 --plugin target/debug/examples/libplugin_day_4.so
+aoc23 compare --day 4 --plugin target/debug/examples/libplugin_day_4.so
+
+A plugin talks to the runner over a small C ABI, so that it need not be built
+with the same compiler as the runner, and exports
+
+aoc23_plugin_abi_version() -> u32
+aoc23_plugin_day() -> u32
+aoc23_plugin_name() -> *const c_char
+aoc23_plugin_solve(part: u32, input: *const u8, len: usize, out: *mut *mut c_char) -> i32
+aoc23_plugin_free(s: *mut c_char)
+
+`aoc23_plugin_abi_version` must return `PLUGIN_ABI_VERSION`. The name is a
+static nul terminated string. `aoc23_plugin_solve` is given the puzzle input
+as UTF-8 bytes and writes a nul terminated string allocated by the plugin to
+`out`, the answer if it returns 0 or why the part could not be solved
+otherwise, which the runner hands back to `aoc23_plugin_free`. An answer which
+is an integer is read as one, anything else as text. See
+examples/plugin_day_4.rs for a plugin written in Rust.
+
+*/
+
+use libloading::Library;
+use std::ffi::{c_char, CStr};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solver::Solver;
+
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type DayFn = unsafe extern "C" fn() -> u32;
+type NameFn = unsafe extern "C" fn() -> *const c_char;
+type SolveFn = unsafe extern "C" fn(u32, *const u8, usize, *mut *mut c_char) -> i32;
+type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+pub struct PluginSolver {
+    path: PathBuf,
+    name: String,
+    day: u32,
+    solve: SolveFn,
+    free: FreeFn,
+    // The functions above point into the library, so it is kept loaded for as long as they are
+    _library: Arc<Library>
+}
+
+impl PluginSolver {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn call(&self, part: u32, input: &str) -> Result<Answer, AocError> {
+        /* Solve a part with the plugin, taking ownership of the string it writes back. */
+        let mut out: *mut c_char = std::ptr::null_mut();
+        // SAFETY: the input outlives the call and the plugin only writes a string of its own to `out`
+        let status = unsafe { (self.solve)(part, input.as_ptr(), input.len(), &mut out) };
+
+        if out.is_null() {
+            return Err(AocError::InvalidArgument(format!("The plugin '{}' gave no answer for part {}", self.name, part)));
+        }
+        // SAFETY: `out` is a nul terminated string from the plugin, which is only freed after being copied
+        let text = unsafe { CStr::from_ptr(out) }.to_string_lossy().into_owned();
+        unsafe { (self.free)(out) };
+
+        if status != 0 {
+            return Err(AocError::InvalidArgument(format!("The plugin '{}' failed on part {}: {}", self.name, part, text)));
+        }
+        Ok(Answer::from_text(&text))
+    }
+}
+
+impl Solver for PluginSolver {
+    fn day(&self) -> u32 {
+        self.day
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        self.call(1, input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        self.call(2, input)
+    }
+}
+
+pub fn load(path: impl AsRef<Path>) -> Result<PluginSolver, AocError> {
+    /* Load the solver of a plugin library, checking that it was built for this runner.
+
+    # Examples
+
+    ```
+    let plugin = load("target/debug/examples/libplugin_day_4.so")?;
+    let answer = plugin.solve(1, &puzzle_input)?;
+    ```
+    */
+    let path = path.as_ref();
+    let failed = |reason: String| AocError::Plugin { path: path.to_path_buf(), reason };
+
+    // A bare file name would be looked for on the library search path rather than here
+    let local = match path.parent() {
+        Some(p) if p.as_os_str().is_empty() => Path::new(".").join(path),
+        _ => path.to_path_buf()
+    };
+
+    // SAFETY: loading a library runs its initialisers, plugins are trusted as much as the runner itself
+    let library = match unsafe { Library::new(&local) } {
+        Ok(l) => Arc::new(l),
+        Err(e) => return Err(failed(e.to_string()))
+    };
+
+    // SAFETY: each symbol is looked up with the signature the ABI above gives it
+    let (abi_version, day, name, solve, free) = unsafe {
+        let symbols = (
+            library.get::<AbiVersionFn>(b"aoc23_plugin_abi_version\0").map(|f| *f),
+            library.get::<DayFn>(b"aoc23_plugin_day\0").map(|f| *f),
+            library.get::<NameFn>(b"aoc23_plugin_name\0").map(|f| *f),
+            library.get::<SolveFn>(b"aoc23_plugin_solve\0").map(|f| *f),
+            library.get::<FreeFn>(b"aoc23_plugin_free\0").map(|f| *f)
+        );
+        match symbols {
+            (Ok(a), Ok(d), Ok(n), Ok(s), Ok(f)) => (a, d, n, s, f),
+            _ => return Err(failed("not a solver plugin, it is missing one of the aoc23_plugin_ functions".to_string()))
+        }
+    };
+
+    let version = unsafe { abi_version() };
+    if version != PLUGIN_ABI_VERSION {
+        return Err(failed(format!("it was built for version {} of the plugin ABI rather than {}", version, PLUGIN_ABI_VERSION)));
+    }
+
+    let day = unsafe { day() };
+    if !(1..=25).contains(&day) {
+        return Err(failed(format!("it solves day {}, which is not a day of the event", day)));
+    }
+
+    let name_ptr = unsafe { name() };
+    let name = if name_ptr.is_null() {
+        String::from("plugin")
+    } else {
+        // SAFETY: the ABI has the name be a static nul terminated string
+        unsafe { CStr::from_ptr(name_ptr) }.to_string_lossy().into_owned()
+    };
+
+    Ok(PluginSolver { path: path.to_path_buf(), name, day, solve, free, _library: library })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day_4::Day4;
+    use std::process::Command;
+
+    #[test]
+    fn test_load() {
+        let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        // A target directory of its own, as the one running the tests is locked while they build
+        let target_dir = manifest_dir.join("target/plugin_test");
+        let built = Command::new(env!("CARGO"))
+            .args(["build", "--offline", "--quiet", "--example", "plugin_day_4", "--target-dir"])
+            .arg(&target_dir)
+            .current_dir(&manifest_dir)
+            .status()
+            .unwrap();
+        assert!(built.success());
+
+        let library = target_dir.join("debug/examples").join(format!(
+            "{}plugin_day_4{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX
+        ));
+        let plugin = load(&library).unwrap();
+        let sample = std::fs::read_to_string(manifest_dir.join("data/test/day_4.dat")).unwrap();
+
+        assert_eq!(plugin.day(), 4);
+        assert_eq!(plugin.name(), "plugin_day_4");
+        assert_eq!(plugin.solve(1, &sample).unwrap(), Day4::default().solve(1, &sample).unwrap());
+        assert_eq!(plugin.solve(2, &sample).unwrap(), Day4::default().solve(2, &sample).unwrap());
+        assert!(plugin.solve(1, "Card one").is_err());
+
+        let not_a_plugin = load(manifest_dir.join("Cargo.toml"));
+        assert!(matches!(not_a_plugin, Err(AocError::Plugin { .. })));
+    }
+}
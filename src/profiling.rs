@@ -0,0 +1,145 @@
+/*                        HEAP PROFILING
+
+With the `profiling` feature enabled the crate installs a global allocator
+which counts allocations, so that the heap use of a solver can be measured,
+e.g.
+
+cargo run --release --features profiling -- run --day 3
+
+Counters are shared by the whole process, so measurements taken while other
+threads allocate will include their allocations too. Without the feature no
+allocator is installed and `measure` returns no statistics.
+
+*/
+
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    pub allocations: u64,
+    pub bytes_allocated: u64,
+    // Highest number of bytes live at once, relative to the start of the measurement
+    pub peak_bytes: u64
+}
+
+impl fmt::Display for AllocationStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} allocations, {} bytes allocated, peak {} bytes", self.allocations, self.bytes_allocated, self.peak_bytes)
+    }
+}
+
+#[cfg(feature = "profiling")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    pub static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    pub static BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+    pub static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+    pub static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+    pub struct CountingAllocator;
+
+    fn record_allocation(size: u64) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_allocation(layout.size() as u64);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+                record_allocation(new_size as u64);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+}
+
+#[cfg(feature = "profiling")]
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Option<AllocationStats>) {
+    /* Run a function, counting the heap allocations made while it runs.
+
+    # Examples
+
+    ```
+    let (part_numbers, stats) = measure(|| get_part_numbers("/path/to/file"));
+    ```
+    */
+    use std::sync::atomic::Ordering;
+    use self::counting::{ALLOCATIONS, BYTES_ALLOCATED, LIVE_BYTES, PEAK_BYTES};
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes_allocated = BYTES_ALLOCATED.load(Ordering::Relaxed);
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(live_bytes, Ordering::Relaxed);
+
+    let output = f();
+
+    let stats = AllocationStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed) - bytes_allocated,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(live_bytes)
+    };
+
+    (output, Some(stats))
+}
+
+#[cfg(not(feature = "profiling"))]
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Option<AllocationStats>) {
+    (f(), None)
+}
+
+pub fn report(label: &str, stats: &Option<AllocationStats>) {
+    /* Log allocation statistics, if they were collected. */
+    if let Some(s) = stats {
+        tracing::info!(
+            allocations = s.allocations,
+            bytes_allocated = s.bytes_allocated,
+            peak_bytes = s.peak_bytes,
+            "Heap use of {}", label
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (total, stats) = measure(|| (0..1000).collect::<Vec<u64>>().iter().sum::<u64>());
+        assert_eq!(total, 499500);
+
+        if cfg!(feature = "profiling") {
+            let stats = stats.unwrap();
+            assert!(stats.allocations >= 1);
+            assert!(stats.bytes_allocated >= 8000);
+            assert!(stats.peak_bytes >= 8000);
+        } else {
+            assert_eq!(stats, None);
+        }
+
+        let stats = AllocationStats { allocations: 2, bytes_allocated: 96, peak_bytes: 64 };
+        assert_eq!(stats.to_string(), "2 allocations, 96 bytes allocated, peak 64 bytes");
+    }
+}
@@ -0,0 +1,285 @@
+/*                        REFERENCE IMPLEMENTATIONS
+
+Checks the Rust solvers against reference implementations in other languages,
+e.g. a Python script for each day, solving the same inputs with both and
+reporting where their answers differ and how their times compare:
+
+aoc23 crosscheck [--year Y] [--data DIR] [--day N] [--runs N] [--reference NAME]...
+
+The reference implementations are commands given in the configuration, see
+src/config.rs, by name:
+
+[references]
+python = ["python3", "reference/day_{day}.py"]
+
+The command of a reference is run for each part with '{day}' in its arguments
+replaced by the day and the part, 1 or 2, added as its last argument. It is
+given the puzzle input on stdin and writes the answer as the last line of its
+output, an integer or text as with `Answer::from_text`. A command which exits
+with an error, e.g. as there is no script for the day, is reported as failed
+rather than stopping the check.
+
+*/
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::answer::Answer;
+use crate::bench::{format_duration, timed_runs, Contender};
+use crate::error::AocError;
+use crate::runner::InputSet;
+use crate::solver::Solver;
+
+// A reference implementation of one day, run as a separate process for each part
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReferenceSolver {
+    pub name: String,
+    pub day: u32,
+    command: Vec<String>
+}
+
+impl ReferenceSolver {
+    pub fn new(name: &str, day: u32, command: &[String]) -> Result<ReferenceSolver, AocError> {
+        /* The reference of a day, from a command whose arguments may contain '{day}'. */
+        if command.is_empty() {
+            return Err(AocError::InvalidArgument(format!("The reference '{}' has an empty command", name)));
+        }
+        let command = command.iter().map(|a| a.replace("{day}", &day.to_string())).collect();
+        Ok(ReferenceSolver { name: name.to_string(), day, command })
+    }
+
+    pub fn command(&self, part: u32) -> Vec<String> {
+        /* The program and arguments run to solve a part. */
+        let mut command = self.command.clone();
+        command.push(part.to_string());
+        command
+    }
+
+    fn run(&self, part: u32, input: &str) -> Result<Answer, AocError> {
+        /* Solve a part by running the command with the input on stdin. */
+        let command = self.command(part);
+        // The report gives the reference, day and part, so the reason only names the program
+        let failed = |reason: String| AocError::InvalidArgument(format!("'{}' {}", command[0], reason));
+
+        let mut child = match Command::new(&command[0])
+            .args(&command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn() {
+            Ok(c) => c,
+            Err(e) => return Err(AocError::io(&command[0], e))
+        };
+
+        // The input is written while the output is read, so that neither pipe fills up and blocks the other
+        let stdin = child.stdin.take();
+        let output = std::thread::scope(|s| {
+            s.spawn(move || {
+                // A command which stops reading early closes the pipe, which is not an error of its own
+                if let Some(mut stdin) = stdin {
+                    let _ = stdin.write_all(input.as_bytes());
+                }
+            });
+            child.wait_with_output()
+        });
+        let output = match output {
+            Ok(o) => o,
+            Err(e) => return Err(AocError::io(&command[0], e))
+        };
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+                Some(line) => format!("failed with {}, {}", output.status, line.trim()),
+                None => format!("failed with {}", output.status)
+            };
+            return Err(failed(reason));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        match stdout.lines().rev().find(|l| !l.trim().is_empty()) {
+            Some(line) => Ok(Answer::from_text(line)),
+            None => Err(failed("wrote no answer".to_string()))
+        }
+    }
+}
+
+impl Solver for ReferenceSolver {
+    fn day(&self) -> u32 {
+        self.day
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {
+        self.run(1, input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {
+        self.run(2, input)
+    }
+}
+
+// The answers and times of the Rust solver and each reference for one part
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrossCheck {
+    pub day: u32,
+    pub part: u32,
+    // The Rust solver first, then the references in the order given, each with why it failed if it did
+    pub results: Vec<(String, Result<Contender, String>)>
+}
+
+impl CrossCheck {
+    pub fn agrees(&self) -> bool {
+        /* Whether every implementation which gave an answer gave the same one. */
+        let mut answers = self.results.iter().filter_map(|(_, r)| r.as_ref().ok()).map(|c| &c.answer);
+        match answers.next() {
+            Some(first) => answers.all(|a| a == first),
+            None => true
+        }
+    }
+}
+
+pub fn cross_check(inputs: &InputSet, references: &[(String, Vec<String>)], days: &[u32], runs: usize) -> Result<Vec<CrossCheck>, AocError> {
+    /* Solve both parts of every day with an input with the Rust solver and each reference.
+
+    # Arguments
+
+    * `inputs` - the puzzle inputs, and the event whose solvers are checked
+    * `references` - the name and command of each reference implementation
+    * `days` - the days to check, or every day with an input if empty
+    * `runs` - the number of times each implementation solves each part
+
+    # Examples
+
+    ```
+    let references = vec![("python".to_string(), vec!["python3".to_string(), "reference/day_{day}.py".to_string()])];
+    let checks = cross_check(&InputSet::from_dir("data")?, &references, &[], 3)?;
+    print!("{}", report(&checks));
+    ```
+    */
+    if runs == 0 {
+        return Err(AocError::InvalidArgument("At least one run is needed for timing".to_string()));
+    }
+
+    let mut checks = Vec::<CrossCheck>::new();
+
+    for solver in inputs.solvers() {
+        let day = solver.day();
+        let input = match inputs.get(day) {
+            Some(i) if days.is_empty() || days.contains(&day) => i,
+            _ => continue
+        };
+
+        let mut implementations: Vec<(String, Box<dyn Solver>)> = vec![("rust".to_string(), solver)];
+        for (name, command) in references {
+            implementations.push((name.clone(), Box::new(ReferenceSolver::new(name, day, command)?)));
+        }
+
+        for part in [1, 2] {
+            let results = implementations
+                .iter()
+                .map(|(name, s)| {
+                    let result = timed_runs(s.as_ref(), part, input, runs)
+                        .map(|(answer, timing)| Contender { name: name.clone(), answer, timing })
+                        .map_err(|e| e.to_string());
+                    (name.clone(), result)
+                })
+                .collect();
+            checks.push(CrossCheck { day, part, results });
+        }
+    }
+
+    Ok(checks)
+}
+
+pub fn report(checks: &[CrossCheck]) -> String {
+    /* Lay out the cross checks with a row for each implementation of each part.
+
+    # Examples
+
+    ```
+    Day  Part  Solver                  Answer       Median   Relative  Result
+      4     1  rust                        13     1.201 ms       1.0x  ok
+      4     1  python                      13    20.482 ms      17.1x  ok
+    ```
+
+    The times are relative to the Rust solver, and each reference is marked as ok when
+    it agrees with the Rust solver, differs when it does not and failed when it gave no
+    answer, in which case the reason follows the table.
+    */
+    let mut rows = format!("{:>3}  {:>4}  {:<12}  {:>16}  {:>11}  {:>9}  {}\n", "Day", "Part", "Solver", "Answer", "Median", "Relative", "Result");
+    let mut failures = Vec::<String>::new();
+
+    for check in checks {
+        let rust = check.results.first().and_then(|(_, r)| r.as_ref().ok());
+        for (name, result) in &check.results {
+            match result {
+                Ok(contender) => {
+                    let relative = match rust {
+                        Some(r) if !r.timing.median.is_zero() => format!("{:.1}x", contender.timing.median.as_secs_f64() / r.timing.median.as_secs_f64()),
+                        _ => "-".to_string()
+                    };
+                    let verdict = match rust {
+                        Some(r) if r.answer != contender.answer => "differs",
+                        _ => "ok"
+                    };
+                    rows.push_str(&format!(
+                        "{:>3}  {:>4}  {:<12}  {:>16}  {:>11}  {:>9}  {}\n",
+                        check.day, check.part, name, contender.answer.to_string(),
+                        format_duration(contender.timing.median), relative, verdict
+                    ));
+                },
+                Err(e) => {
+                    rows.push_str(&format!("{:>3}  {:>4}  {:<12}  {:>16}  {:>11}  {:>9}  {}\n", check.day, check.part, name, "-", "-", "-", "failed"));
+                    failures.push(format!("Day {} part {} {}: {}", check.day, check.part, name, e));
+                }
+            };
+        }
+    }
+
+    // The reasons are too long for the table, so are listed after it
+    if !failures.is_empty() {
+        rows.push('\n');
+        for failure in failures {
+            rows.push_str(&failure);
+            rows.push('\n');
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    // The references are shell commands, so the test only runs where there is a POSIX shell
+    #[cfg(unix)]
+    #[test]
+    fn test_cross_check() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let inputs = InputSet::from_dir(&test_dir).unwrap();
+        let shell = |script: &str| vec!["sh".to_string(), "-c".to_string(), script.to_string(), "sh".to_string()];
+        let references = vec![
+            // Right for both parts of day 4 of the sample, after reading the whole input
+            ("agrees".to_string(), shell("cat > /dev/null; if [ \"$1\" = 1 ]; then echo 13; else echo 30; fi")),
+            ("differs".to_string(), shell("cat > /dev/null; echo working...; echo {day}")),
+            ("fails".to_string(), shell("echo 'no solution for day {day}' >&2; exit 3"))
+        ];
+
+        let checks = cross_check(&inputs, &references, &[4], 1).unwrap();
+        assert_eq!(checks.iter().map(|c| (c.day, c.part)).collect::<Vec<(u32, u32)>>(), vec![(4, 1), (4, 2)]);
+        assert!(!checks[0].agrees());
+
+        let answers: Vec<Option<Answer>> = checks[1].results.iter().map(|(_, r)| r.as_ref().ok().map(|c| c.answer.clone())).collect();
+        assert_eq!(answers, vec![Some(Answer::Int(30)), Some(Answer::Int(30)), Some(Answer::Int(4)), None]);
+        assert!(checks[1].results[3].1.as_ref().unwrap_err().contains("no solution for day 4"));
+
+        let table = report(&checks);
+        assert!(table.lines().any(|l| l.contains("agrees") && l.ends_with("ok")));
+        assert!(table.lines().any(|l| l.contains("differs") && l.ends_with("differs")));
+        assert!(table.contains("Day 4 part 1 fails: "));
+
+        assert!(cross_check(&inputs, &[("empty".to_string(), Vec::new())], &[4], 1).is_err());
+        assert!(cross_check(&inputs, &references, &[4], 0).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+/*                        REPL
+
+An interactive session for working on a day without recompiling, started with
+
+aoc23 repl [--day N] [--input FILE]
+
+An input file is loaded and can then be solved part by part, parsed to see the
+structure the solver works on, and for day 5 queried by converting or tracing
+values through the almanac. The file is read again with `reload`, so it can be
+edited in another window between runs. The commands are listed by `help`.
+
+*/
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::day_3::Schematic;
+use crate::day_5::Almanac;
+use crate::detect::DayDetector;
+use crate::error::AocError;
+use crate::solver::solver;
+use crate::util::io::InputSource;
+
+pub const HELP: &str = "Commands:
+    load FILE           read an input, finding its day if none has been chosen
+    reload              read the loaded input again
+    day N               solve the input as day N
+    part1, part2        solve a part, with its answer and time taken
+    parse               parse the input, showing what was found
+    convert V FROM TO   map a value between categories of the almanac (day 5)
+    trace SEED          show each stage a seed passes through to its location (day 5)
+    help                show this message
+    quit                leave the session";
+
+// What the session does after a command
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reply {
+    Output(String),
+    Quit
+}
+
+#[derive(Default)]
+pub struct Session {
+    detector: DayDetector,
+    day: Option<u32>,
+    input_file: Option<PathBuf>,
+    input: Option<String>
+}
+
+impl Session {
+    pub fn new(detector: DayDetector) -> Session {
+        /* An empty session, finding the day of loaded inputs with the given detector. */
+        Session { detector, ..Session::default() }
+    }
+
+    pub fn day(&self) -> Option<u32> {
+        self.day
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<Reply, AocError> {
+        /* Run one command of the session, returning what it printed.
+
+        A failed command leaves the session as it was, so the next can be tried.
+
+        # Examples
+
+        ```
+        let mut session = Session::default();
+        session.execute("load data/day_5.dat").unwrap();
+        match session.execute("part2").unwrap() {
+            Reply::Output(o) => println!("{}", o),
+            Reply::Quit => ()
+        };
+        ```
+        */
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let output = match words[..] {
+            [] => String::new(),
+            ["help"] => HELP.to_string(),
+            ["quit"] | ["exit"] => return Ok(Reply::Quit),
+            ["load", file] => self.load(Path::new(file))?,
+            ["reload"] => match self.input_file.clone() {
+                Some(f) => self.load(&f)?,
+                None => return Err(AocError::InvalidArgument("No input has been loaded yet, use 'load FILE'".to_string()))
+            },
+            ["day", day] => self.choose_day(day)?,
+            ["part1"] => self.solve(1)?,
+            ["part2"] => self.solve(2)?,
+            ["parse"] => self.parse()?,
+            ["convert", value, from, to] => {
+                let value = parse_number(value)?;
+                format!("{} {} is {} {}", from, value, to, self.almanac()?.convert(value, from, to)?)
+            },
+            ["trace", seed] => {
+                let steps = self.almanac()?.trace_seed(parse_number(seed)?)?;
+                steps
+                    .iter()
+                    .map(|s| format!("{} {} -> {} {}", s.source, s.input, s.destination, s.output))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            },
+            _ => return Err(AocError::InvalidArgument(format!("Unknown command '{}', try 'help'", line.trim())))
+        };
+
+        Ok(Reply::Output(output))
+    }
+
+    fn load(&mut self, input_file: &Path) -> Result<String, AocError> {
+        let input = InputSource::read(input_file).and_then(InputSource::into_string)?;
+
+        // A day chosen by hand is kept, so reloading an edited input does not change it
+        let day = match self.day {
+            Some(d) => Some(d),
+            None => self.detector.detect(input_file, &input)
+        };
+
+        let output = match day {
+            Some(d) => format!("Loaded {} lines from '{}' as day {}", input.lines().count(), input_file.display(), d),
+            None => format!("Loaded {} lines from '{}', choose its day with 'day N'", input.lines().count(), input_file.display())
+        };
+
+        self.day = day;
+        self.input_file = Some(input_file.to_path_buf());
+        self.input = Some(input);
+        Ok(output)
+    }
+
+    fn choose_day(&mut self, day: &str) -> Result<String, AocError> {
+        let day = match day.parse::<u32>() {
+            Ok(d) => d,
+            Err(e) => return Err(AocError::InvalidArgument(format!("Invalid day '{}': {}", day, e)))
+        };
+        match solver(day) {
+            Some(_) => {
+                self.day = Some(day);
+                Ok(format!("Solving as day {}", day))
+            },
+            None => Err(AocError::InvalidArgument(format!("Day {} has not been solved", day)))
+        }
+    }
+
+    fn current(&self) -> Result<(u32, &str), AocError> {
+        /* The chosen day and the loaded input, or an error saying which is missing. */
+        match (self.day, &self.input) {
+            (Some(d), Some(i)) => Ok((d, i.as_str())),
+            (_, None) => Err(AocError::InvalidArgument("No input has been loaded yet, use 'load FILE'".to_string())),
+            (None, Some(_)) => Err(AocError::InvalidArgument("The day of the input is not known, use 'day N'".to_string()))
+        }
+    }
+
+    fn solve(&self, part: u32) -> Result<String, AocError> {
+        let (day, input) = self.current()?;
+        let day_solver = match solver(day) {
+            Some(s) => s,
+            None => return Err(AocError::InvalidArgument(format!("Day {} has not been solved", day)))
+        };
+
+        let start = Instant::now();
+        let outcome = day_solver.solve_with_diagnostics(part, input)?;
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let mut lines: Vec<String> = outcome.diagnostics.iter().map(|d| format!("warning: {}", d)).collect();
+        lines.push(format!("Day {} part {}: {} ({:.3} ms)", day, part, outcome.answer, elapsed_ms));
+        Ok(lines.join("\n"))
+    }
+
+    fn parse(&self) -> Result<String, AocError> {
+        /* Parse the input into the structure the solver of its day works on and describe it. */
+        let (day, input) = self.current()?;
+        let start = Instant::now();
+
+        let mut lines = match day {
+            3 => {
+                let schematic = Schematic::from_reader(input.as_bytes())?;
+                schematic
+                    .parts_by_symbol()
+                    .into_iter()
+                    .map(|(s, parts)| format!("'{}' at row {} column {}: {:?}", s.symbol, s.position.0, s.position.1, parts))
+                    .collect::<Vec<String>>()
+            },
+            5 => {
+                let almanac = Almanac::parse(input)?;
+                let mut lines = vec![format!("seeds: {:?}", almanac.seeds())];
+                for map in almanac.maps() {
+                    lines.push(format!("{}-to-{}: {} ranges", map.source, map.destination, map.ranges.len()));
+                }
+                lines
+            },
+            _ => return Err(AocError::InvalidArgument(format!("There is nothing to show for day {}, only for days 3 and 5", day)))
+        };
+
+        lines.push(format!("Parsed in {:.3} ms", start.elapsed().as_secs_f64() * 1000.0));
+        Ok(lines.join("\n"))
+    }
+
+    fn almanac(&self) -> Result<Almanac, AocError> {
+        match self.current()? {
+            (5, input) => Almanac::parse(input),
+            (day, _) => Err(AocError::InvalidArgument(format!("Only day 5 has an almanac to query, the input is day {}", day)))
+        }
+    }
+}
+
+fn parse_number(value: &str) -> Result<i64, AocError> {
+    match value.parse::<i64>() {
+        Ok(v) => Ok(v),
+        Err(e) => Err(AocError::InvalidArgument(format!("Invalid number '{}': {}", value, e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn output(session: &mut Session, line: &str) -> String {
+        match session.execute(line).unwrap() {
+            Reply::Output(o) => o,
+            Reply::Quit => panic!("'{}' ended the session", line)
+        }
+    }
+
+    #[test]
+    fn test_session() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let mut session = Session::default();
+
+        assert!(session.execute("part1").unwrap_err().to_string().contains("load FILE"));
+        assert!(output(&mut session, &format!("load {}", test_dir.join("day_5.dat").display())).ends_with("as day 5"));
+        assert!(output(&mut session, "part2").starts_with("Day 5 part 2: 46 "));
+        assert_eq!(output(&mut session, "convert 79 seed soil"), "seed 79 is soil 81");
+        assert!(output(&mut session, "trace 79").ends_with("humidity 78 -> location 82"));
+        assert!(output(&mut session, "parse").starts_with("seeds: [79, 14, 55, 13]\nseed-to-soil: 2 ranges"));
+
+        // The day of an input is kept when another is loaded, until it is changed by hand
+        output(&mut session, &format!("load {}", test_dir.join("day_3.dat").display()));
+        assert!(session.execute("part1").is_err());
+        assert_eq!(output(&mut session, "day 3"), "Solving as day 3");
+        assert!(output(&mut session, "part1").starts_with("Day 3 part 1: 4361 "));
+        assert!(output(&mut session, "parse").starts_with("'*' at row 1 column 3: [467, 35]"));
+        assert!(session.execute("trace 79").unwrap_err().to_string().contains("Only day 5"));
+
+        assert!(session.execute("day 30").is_err());
+        assert!(session.execute("frobnicate").is_err());
+        assert_eq!(session.execute("quit").unwrap(), Reply::Quit);
+    }
+}
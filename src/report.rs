@@ -0,0 +1,247 @@
+/*                        HTML REPORT
+
+Writes the runs of a year kept in the results store, see src/store.rs, as a
+single HTML page with no scripts or external files, so that it can be opened
+straight from disk or attached to a message:
+
+aoc23 report [--year Y] [--output FILE]
+
+The page has a table of the latest answer and time of each part, with its best
+time and how many runs solved it, followed by a chart for each day of how long
+each part took over the runs, so that the effect of each optimisation can be
+seen at a glance. Times span several orders of magnitude between a first
+attempt and the final solution, so the charts use a logarithmic scale.
+
+*/
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::answer::Answer;
+use crate::bench::format_duration;
+use crate::store::{format_timestamp, RunRecord};
+
+// Size of each chart in pixels, and the space kept for the axis labels
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 220.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_BOTTOM: f64 = 30.0;
+const MARGIN_TOP: f64 = 10.0;
+const MARGIN_RIGHT: f64 = 20.0;
+
+// The colour of the line of each part
+const PART_COLOURS: [&str; 2] = ["#1f77b4", "#ff7f0e"];
+
+// Times shorter than this are drawn at it, as a logarithmic scale has no zero
+const MIN_CHART_MS: f64 = 0.001;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: right; }
+td.answer { font-family: monospace; }
+.changed { color: #b00; }
+svg text { font-size: 11px; fill: #555; }
+.legend span { display: inline-block; width: 1em; height: 0.6em; margin: 0 0.3em 0 1em; }";
+
+// A part solved by one run
+#[derive(Clone, Debug, PartialEq)]
+struct Solve {
+    // Index of the run among the runs of the year, oldest first
+    run: usize,
+    timestamp: u64,
+    answer: Answer,
+    elapsed_ms: f64
+}
+
+fn solves(runs: &[RunRecord]) -> BTreeMap<(u32, u32), Vec<Solve>> {
+    /* The answered parts of every run, by day and part. */
+    let mut solves = BTreeMap::<(u32, u32), Vec<Solve>>::new();
+    for (run, record) in runs.iter().enumerate() {
+        for day in &record.results {
+            for part in &day.parts {
+                if let Some(answer) = &part.answer {
+                    let solve = Solve { run, timestamp: record.timestamp, answer: answer.clone(), elapsed_ms: part.elapsed_ms };
+                    solves.entry((day.day, part.part)).or_default().push(solve);
+                }
+            }
+        }
+    }
+    solves
+}
+
+fn colour(part: u32) -> &'static str {
+    PART_COLOURS[(part as usize).saturating_sub(1) % PART_COLOURS.len()]
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn duration_ms(elapsed_ms: f64) -> String {
+    format_duration(std::time::Duration::from_secs_f64(elapsed_ms.max(0.0) / 1000.0))
+}
+
+pub fn html_report(year: u32, runs: &[RunRecord]) -> String {
+    /* The report of the runs of a year, oldest first, as a standalone HTML page.
+
+    # Examples
+
+    ```
+    let config = Config::load()?;
+    let runs = config.results_store().runs(2023, None)?;
+    std::fs::write("report.html", html_report(2023, &runs))?;
+    ```
+    */
+    let solves = solves(runs);
+    let mut page = String::new();
+
+    let _ = write!(page, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code {}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n", year, STYLE);
+    let _ = writeln!(page, "<h1>Advent of Code {}</h1>", year);
+    match (runs.first(), runs.last()) {
+        (Some(first), Some(last)) => {
+            let _ = writeln!(page, "<p>{} runs from {} to {} UTC</p>", runs.len(), format_timestamp(first.timestamp), format_timestamp(last.timestamp));
+        },
+        _ => {
+            let _ = writeln!(page, "<p>No runs have been recorded.</p>");
+        }
+    };
+
+    // The answer of a part is marked when it differs from one given before, e.g. after a bug was fixed
+    let _ = writeln!(page, "<table>\n<tr><th>Day</th><th>Part</th><th>Answer</th><th>Latest</th><th>Best</th><th>Runs</th></tr>");
+    for ((day, part), solved) in &solves {
+        let latest = &solved[solved.len() - 1];
+        let best = solved.iter().map(|s| s.elapsed_ms).fold(f64::INFINITY, f64::min);
+        let changed = solved.iter().any(|s| s.answer != latest.answer);
+        let _ = writeln!(
+            page,
+            "<tr><td>{}</td><td>{}</td><td class=\"answer{}\"{}>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            day, part,
+            if changed {" changed"} else {""},
+            if changed {" title=\"earlier runs gave other answers\""} else {""},
+            escape(&latest.answer.to_string()), duration_ms(latest.elapsed_ms), duration_ms(best), solved.len()
+        );
+    }
+    let _ = writeln!(page, "</table>");
+
+    let days: Vec<u32> = solves.keys().map(|&(day, _)| day).collect::<std::collections::BTreeSet<u32>>().into_iter().collect();
+    for day in days {
+        let parts: Vec<(u32, &Vec<Solve>)> = solves.iter().filter(|((d, _), _)| *d == day).map(|(&(_, p), s)| (p, s)).collect();
+        let _ = writeln!(page, "<h2>Day {}</h2>", day);
+        let _ = write!(page, "<p class=\"legend\">");
+        for (part, _) in &parts {
+            let _ = write!(page, "<span style=\"background: {}\"></span>Part {}", colour(*part), part);
+        }
+        let _ = writeln!(page, "</p>");
+        page.push_str(&chart(runs, &parts));
+    }
+
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+fn chart(runs: &[RunRecord], parts: &[(u32, &Vec<Solve>)]) -> String {
+    /* An SVG chart of the time taken by each part of a day over the runs which solved it. */
+    // Only the runs which solved the day take up room along the x axis
+    let mut day_runs: Vec<usize> = parts.iter().flat_map(|(_, s)| s.iter().map(|s| s.run)).collect();
+    day_runs.sort_unstable();
+    day_runs.dedup();
+
+    let times = parts.iter().flat_map(|(_, s)| s.iter().map(|s| s.elapsed_ms.max(MIN_CHART_MS).log10()));
+    let (low, high) = times.fold((f64::INFINITY, f64::NEG_INFINITY), |(l, h), t| (l.min(t), h.max(t)));
+    // The scale covers whole decades, at least one
+    let (low, high) = (low.floor(), high.ceil().max(low.floor() + 1.0));
+
+    let plot_width = CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let x = |run: usize| {
+        let index = day_runs.iter().position(|&r| r == run).unwrap_or(0);
+        match day_runs.len() {
+            1 => MARGIN_LEFT + plot_width / 2.0,
+            n => MARGIN_LEFT + plot_width * index as f64 / (n - 1) as f64
+        }
+    };
+    let y = |elapsed_ms: f64| MARGIN_TOP + plot_height * (high - elapsed_ms.max(MIN_CHART_MS).log10()) / (high - low);
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">", CHART_WIDTH, CHART_HEIGHT);
+
+    // A grid line and label for each power of ten
+    for decade in low as i32..=high as i32 {
+        let line_y = y(10f64.powi(decade));
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#ddd\"/><text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+            MARGIN_LEFT, line_y, CHART_WIDTH - MARGIN_RIGHT, line_y, MARGIN_LEFT - 6.0, line_y + 4.0, duration_ms(10f64.powi(decade))
+        );
+    }
+
+    // The first and last runs are labelled with their dates
+    if let (Some(&first), Some(&last)) = (day_runs.first(), day_runs.last()) {
+        let label_y = CHART_HEIGHT - MARGIN_BOTTOM + 18.0;
+        let _ = writeln!(svg, "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"start\">{}</text>", MARGIN_LEFT, label_y, format_timestamp(runs[first].timestamp));
+        if last != first {
+            let _ = writeln!(svg, "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>", CHART_WIDTH - MARGIN_RIGHT, label_y, format_timestamp(runs[last].timestamp));
+        }
+    }
+
+    for (part, solved) in parts {
+        let colour = colour(*part);
+        let points: Vec<String> = solved.iter().map(|s| format!("{:.1},{:.1}", x(s.run), y(s.elapsed_ms))).collect();
+        let _ = writeln!(svg, "<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"2\" points=\"{}\"/>", colour, points.join(" "));
+        for s in solved.iter() {
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"><title>{} part {}: {} in {}</title></circle>",
+                x(s.run), y(s.elapsed_ms), colour, format_timestamp(s.timestamp), part, escape(&s.answer.to_string()), duration_ms(s.elapsed_ms)
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::{DayResult, PartResult};
+
+    fn run(timestamp: u64, day: u32, answers: &[(Option<Answer>, f64)]) -> RunRecord {
+        let parts = answers.iter().enumerate().map(|(i, (answer, elapsed_ms))| PartResult {
+            part: i as u32 + 1,
+            answer: answer.clone(),
+            error: if answer.is_none() {Some("failed".to_string())} else {None},
+            diagnostics: Vec::new(),
+            elapsed_ms: *elapsed_ms
+        }).collect();
+        RunRecord { timestamp, command: "all".to_string(), year: 2023, profile: None, results: vec![DayResult { day, parts }] }
+    }
+
+    #[test]
+    fn test_html_report() {
+        let runs = vec![
+            run(1_701_752_400, 5, &[(Some(Answer::Int(35)), 1.2), (Some(Answer::Int(47)), 93_402.0)]),
+            run(1_701_756_000, 5, &[(Some(Answer::Int(35)), 0.9), (Some(Answer::Int(46)), 0.4)]),
+            run(1_701_759_600, 5, &[(Some(Answer::Int(35)), 1.0), (None, 0.1)]),
+            run(1_701_759_600, 3, &[(Some(Answer::Text("<a&b>".to_string())), 2.0)])
+        ];
+        let page = html_report(2023, &runs);
+
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<p>4 runs from 2023-12-05 05:00 to 2023-12-05 07:00 UTC</p>"));
+
+        // The latest answer and time with the best time, a changed answer being marked, and text escaped
+        assert!(page.contains("<tr><td>5</td><td>1</td><td class=\"answer\">35</td><td>1.000 ms</td><td>900.000 µs</td><td>3</td></tr>"));
+        assert!(page.contains("<tr><td>5</td><td>2</td><td class=\"answer changed\" title=\"earlier runs gave other answers\">46</td><td>400.000 µs</td><td>400.000 µs</td><td>2</td></tr>"));
+        assert!(page.contains("&lt;a&amp;b&gt;"));
+        assert!(!page.contains("<a&b>"));
+
+        // A chart for each day, with a point for each part solved and no scripts
+        assert_eq!(page.matches("<svg").count(), 2);
+        assert_eq!(page.matches("<circle").count(), 6);
+        assert!(page.contains("part 2: 47 in 93.402 s"));
+        assert!(!page.contains("<script"));
+
+        assert!(html_report(2023, &[]).contains("No runs have been recorded"));
+    }
+}
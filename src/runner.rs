@@ -0,0 +1,518 @@
+/*                        RUNNER
+
+Runs every registered solver over a set of puzzle inputs, collecting the
+answers and time taken for each part. The results derive `Serialize` so that
+a whole run can be written out as JSON for other tools to consume, and read
+back from the results store, see src/store.rs.
+
+An input set belongs to the event of a single year, and the days are solved
+with the solvers registered for that event, 2023 unless another is chosen.
+The inputs are read from a directory of files, one per day, or from a single
+bundle file with a '## day N' section for each day.
+
+With the `parallel` feature the days can also be solved on a rayon thread
+pool. The results are gathered in day order whichever finishes first, though
+the times of each part are then affected by the other days running alongside.
+
+A run can also be held to a budget of wall time and memory for each day, see
+src/budget.rs. A day which exceeds it is abandoned, its unfinished parts are
+reported with the limit which was exceeded as their error, and the run stops
+there. An abandoned solver cannot be killed and would slow down and inflate
+the memory use measured for every day after it, so the days left are reported
+as skipped for the caller to exit on rather than solved alongside it.
+
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::answer::Answer;
+use crate::budget::{run_with_budget, Budget};
+use crate::detect::split_bundle;
+use crate::error::AocError;
+use crate::event::{event, DEFAULT_YEAR};
+use crate::outcome::Outcome;
+use crate::progress::ProgressSink;
+use crate::solver::Solver;
+use crate::util::io::InputSource;
+
+// The puzzle input for each day of an event, keyed by day number
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputSet {
+    year: u32,
+    inputs: BTreeMap<u32, String>
+}
+
+impl Default for InputSet {
+    fn default() -> Self {
+        InputSet::for_year(DEFAULT_YEAR)
+    }
+}
+
+impl InputSet {
+    pub fn new() -> InputSet {
+        InputSet::default()
+    }
+
+    pub fn for_year(year: u32) -> InputSet {
+        /* An empty set of inputs for the event of the given year. */
+        InputSet { year, inputs: BTreeMap::new() }
+    }
+
+    pub fn from_dir(data_dir: impl AsRef<Path>) -> Result<InputSet, AocError> {
+        /* Read the inputs for the registered days of 2023 from a directory, see `from_dir_for_year`. */
+        InputSet::from_dir_for_year(DEFAULT_YEAR, data_dir)
+    }
+
+    pub fn from_dir_for_year(year: u32, data_dir: impl AsRef<Path>) -> Result<InputSet, AocError> {
+        /* Read the inputs for the registered days of an event from a directory.
+
+        The input for day N is read from `day_N.dat`, days without an input file
+        are left out of the set.
+
+        # Examples
+
+        ```
+        let inputs = InputSet::from_dir_for_year(2023, "data").unwrap();
+        ```
+        */
+        let mut inputs = InputSet::for_year(year);
+
+        for solver in inputs.solvers() {
+            let input_file = data_dir.as_ref().join(format!("day_{}.dat", solver.day()));
+            if !input_file.exists() {
+                tracing::warn!("No input found at '{}', skipping day {}", input_file.display(), solver.day());
+                continue;
+            }
+
+            inputs.insert(solver.day(), InputSource::read(&input_file).and_then(InputSource::into_string)?);
+        }
+
+        Ok(inputs)
+    }
+
+    pub fn from_bundle(bundle_file: impl AsRef<Path>) -> Result<InputSet, AocError> {
+        /* Read the inputs for the days of 2023 from a bundle file, see `from_bundle_for_year`. */
+        InputSet::from_bundle_for_year(DEFAULT_YEAR, bundle_file)
+    }
+
+    pub fn from_bundle_for_year(year: u32, bundle_file: impl AsRef<Path>) -> Result<InputSet, AocError> {
+        /* Read the inputs for the registered days of an event from a bundle file.
+
+        Each '## day N' section of the bundle is the input of day N, see src/detect.rs.
+        Sections for days without a solver are left out of the set.
+
+        # Examples
+
+        ```
+        let results = run_all(&InputSet::from_bundle_for_year(2023, "inputs.txt").unwrap());
+        ```
+        */
+        let mut inputs = InputSet::for_year(year);
+        let solved: Vec<u32> = inputs.solvers().iter().map(|s| s.day()).collect();
+
+        for section in split_bundle(bundle_file.as_ref())? {
+            if !solved.contains(&section.day) {
+                tracing::warn!("Day {} of '{}' has no solver for {}, skipping it", section.day, bundle_file.as_ref().display(), year);
+                continue;
+            }
+            inputs.insert(section.day, section.content);
+        }
+
+        Ok(inputs)
+    }
+
+    pub fn insert(&mut self, day: u32, input: String) {
+        self.inputs.insert(day, input);
+    }
+
+    pub fn get(&self, day: u32) -> Option<&str> {
+        self.inputs.get(&day).map(|i| i.as_str())
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.inputs.keys().copied()
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    pub fn solvers(&self) -> Vec<Box<dyn Solver>> {
+        /* The solvers registered for the event of the set, none if the year has no event. */
+        match event(self.year) {
+            Some(e) => e.solvers(),
+            None => Vec::new()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PartResult {
+    pub part: u32,
+    // Exactly one of the answer and error is set
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    // Warnings about the input which did not stop the part being solved
+    pub diagnostics: Vec<String>,
+    pub elapsed_ms: f64
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DayResult {
+    pub day: u32,
+    pub parts: Vec<PartResult>
+}
+
+// The day over its budget which stopped a run, see `run_all_with_budget`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Abandoned {
+    pub day: u32,
+    pub part: u32,
+    // The limit which was exceeded
+    pub reason: String,
+    // The days with an input which were not run as a result
+    pub skipped: Vec<u32>
+}
+
+impl fmt::Display for Abandoned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Day {} part {} was abandoned, stopping the run: {}", self.day, self.part, self.reason)?;
+        if !self.skipped.is_empty() {
+            let skipped: Vec<String> = self.skipped.iter().map(|d| d.to_string()).collect();
+            write!(f, ". Days not run: {}", skipped.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn run_all(inputs: &InputSet) -> Vec<DayResult> {
+    /* Solve both parts of every day of the event which has an input in the set.
+
+    A part which fails is reported with its error rather than stopping the run.
+
+    # Examples
+
+    ```
+    let results = run_all(&InputSet::from_dir("data").unwrap());
+    println!("{}", serde_json::to_string_pretty(&results).unwrap());
+    ```
+    */
+    inputs.solvers()
+        .iter()
+        .filter_map(|solver| inputs.get(solver.day()).map(|input| run_day(solver.as_ref(), input)))
+        .collect()
+}
+
+pub fn run_bundle(bundle_file: impl AsRef<Path>) -> Result<Vec<DayResult>, AocError> {
+    /* Solve every day of 2023 with a section in a bundle file, as `run_all`.
+
+    # Examples
+
+    ```
+    let results = run_bundle("inputs.txt").unwrap();
+    ```
+    */
+    Ok(run_all(&InputSet::from_bundle(bundle_file)?))
+}
+
+#[cfg(feature = "parallel")]
+pub fn run_all_parallel(inputs: &InputSet) -> Vec<DayResult> {
+    /* As `run_all`, solving the days in parallel on the global rayon thread pool.
+
+    # Examples
+
+    ```
+    let results = run_all_parallel(&InputSet::from_dir("data").unwrap());
+    ```
+    */
+    use rayon::prelude::*;
+
+    // Rayon collects into a Vec in the order of the solvers, not the order they finish
+    inputs.solvers()
+        .par_iter()
+        .filter_map(|solver| inputs.get(solver.day()).map(|input| run_day(solver.as_ref(), input)))
+        .collect()
+}
+
+#[cfg(feature = "parallel")]
+pub fn run_all_with_threads(inputs: &InputSet, threads: usize) -> Result<Vec<DayResult>, AocError> {
+    /* As `run_all_parallel` on a thread pool of its own, or as `run_all` for zero threads.
+
+    The pool lives only for this run and leaves the global rayon pool alone, so an
+    application which already sizes that pool for its own work is not affected.
+
+    # Examples
+
+    ```
+    let results = run_all_with_threads(&InputSet::from_dir("data").unwrap(), 4).unwrap();
+    ```
+    */
+    if threads == 0 {
+        return Ok(run_all(inputs));
+    }
+
+    match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+        Ok(pool) => Ok(pool.install(|| run_all_parallel(inputs))),
+        Err(e) => Err(AocError::InvalidArgument(format!("Failed to start {} threads: {}", threads, e)))
+    }
+}
+
+pub fn run_all_with_budget(inputs: &InputSet, budget: &Budget) -> (Vec<DayResult>, Option<Abandoned>) {
+    /* As `run_all`, stopping at the first day which exceeds the wall time or memory budget.
+
+    The wall time is shared by both parts of a day, while the memory limit applies to
+    the whole process whenever a day is running. The results of the days solved are
+    returned along with the day which was abandoned, if any, whose solver is still
+    running in the background, so the process should exit soon after.
+
+    # Examples
+
+    ```
+    let budget = Budget { wall_time: Some(Duration::from_secs(15)), memory_bytes: None };
+    let (results, abandoned) = run_all_with_budget(&InputSet::from_dir("data").unwrap(), &budget);
+    if let Some(a) = abandoned {
+        eprintln!("{}", a);
+    }
+    ```
+    */
+    run_solvers_with_budget(inputs.solvers(), inputs, budget)
+}
+
+fn run_solvers_with_budget(solvers: Vec<Box<dyn Solver>>, inputs: &InputSet, budget: &Budget) -> (Vec<DayResult>, Option<Abandoned>) {
+    /* Solve the days of the given solvers with an input within the budget, see `run_all_with_budget`. */
+    let mut results = Vec::<DayResult>::new();
+    let mut solvers = solvers.into_iter().filter_map(|s| inputs.get(s.day()).map(|input| (s, input)));
+
+    while let Some((solver, input)) = solvers.next() {
+        let (result, abandoned) = run_day_with_budget(solver, input, budget);
+        results.push(result);
+
+        if let Some(mut abandoned) = abandoned {
+            abandoned.skipped = solvers.map(|(s, _)| s.day()).collect();
+            return (results, Some(abandoned));
+        }
+    }
+    (results, None)
+}
+
+pub fn run_part(solver: &dyn Solver, part: u32, input: &str) -> PartResult {
+    /* Solve one part of a day, recording its answer or error and the time taken. */
+    let _span = tracing::info_span!("run", day = solver.day(), part).entered();
+
+    let start = Instant::now();
+    let outcome = solver.solve_with_diagnostics(part, input);
+    part_result(part, outcome, start)
+}
+
+pub fn run_part_with_progress(solver: &dyn Solver, part: u32, input: &str, progress: &mut dyn ProgressSink) -> PartResult {
+    /* Solve one part of a day as `run_part` does, publishing its progress rather than keeping its diagnostics. */
+    let _span = tracing::info_span!("run", day = solver.day(), part).entered();
+
+    let start = Instant::now();
+    let outcome = solver.solve_with_progress(part, input, progress).map(Outcome::new);
+    part_result(part, outcome, start)
+}
+
+fn part_result(part: u32, outcome: Result<Outcome<Answer>, AocError>, start: Instant) -> PartResult {
+    /* The answer or error of a part started at the given instant. */
+    let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    match outcome {
+        Ok(o) => {
+            let diagnostics = o.diagnostics.iter().map(|d| d.to_string()).collect();
+            PartResult { part, answer: Some(o.answer), error: None, diagnostics, elapsed_ms }
+        },
+        Err(e) => PartResult { part, answer: None, error: Some(e.to_string()), diagnostics: Vec::new(), elapsed_ms }
+    }
+}
+
+pub(crate) fn run_day(solver: &dyn Solver, input: &str) -> DayResult {
+    /* Solve both parts of a single day, recording the answer or error of each. */
+    DayResult { day: solver.day(), parts: [1, 2].iter().map(|&part| run_part(solver, part, input)).collect() }
+}
+
+fn run_day_with_budget(solver: Box<dyn Solver>, input: &str, budget: &Budget) -> (DayResult, Option<Abandoned>) {
+    /* Solve both parts of a single day within the budget, along with the part abandoned if one was. */
+    let day = solver.day();
+    let solver: Arc<dyn Solver> = Arc::from(solver);
+    let input: Arc<str> = Arc::from(input);
+
+    let mut remaining = budget.clone();
+    let mut abandoned: Option<Abandoned> = None;
+    let mut parts = Vec::new();
+
+    for part in [1, 2] {
+        let start = Instant::now();
+
+        // Once a part is abandoned the rest of the day is skipped rather than run alongside it
+        let result = match &abandoned {
+            Some(a) => Err(a.reason.clone()),
+            None => {
+                let (solver, input) = (Arc::clone(&solver), Arc::clone(&input));
+                run_with_budget(&remaining, move || run_part(solver.as_ref(), part, &input))
+            }
+        };
+
+        match result {
+            Ok((result, report)) => {
+                remaining.wall_time = remaining.wall_time.map(|t| t.saturating_sub(report.elapsed));
+                parts.push(result);
+            },
+            Err(e) => {
+                let error = format!("Day {} abandoned: {}", day, e);
+                let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+                parts.push(PartResult { part, answer: None, error: Some(error), diagnostics: Vec::new(), elapsed_ms });
+                if abandoned.is_none() {
+                    abandoned = Some(Abandoned { day, part, reason: e, skipped: Vec::new() });
+                }
+            }
+        };
+    }
+
+    (DayResult { day, parts }, abandoned)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_all() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+
+        let mut inputs = InputSet::from_dir(&test_dir).unwrap();
+        assert_eq!(inputs.days().collect::<Vec<u32>>(), vec![2, 3, 4, 5]);
+        inputs.insert(1, "1abc2\nno digits\n".to_string());
+
+        let results = run_all(&inputs);
+        assert_eq!(results.iter().map(|r| r.day).collect::<Vec<u32>>(), vec![1, 2, 3, 4, 5]);
+
+        // Day 1 fails on the line without digits, which does not stop the other days
+        assert!(results[0].parts[0].answer.is_none());
+        assert!(results[0].parts[0].error.as_ref().unwrap().contains("Line 2"));
+
+        let mut with_skipped_game = InputSet::new();
+        with_skipped_game.insert(2, "Game 1: 3 blue, 4 red\nGam 2: 1 blue\n".to_string());
+        let skipped = &run_all(&with_skipped_game)[0].parts[0];
+        assert_eq!(skipped.answer, Some(Answer::Int(1)));
+        assert_eq!(skipped.diagnostics.len(), 1);
+        assert!(skipped.diagnostics[0].starts_with("line 2: "));
+
+        let answers: Vec<Option<Answer>> = results[2].parts.iter().map(|p| p.answer.clone()).collect();
+        assert_eq!(answers, vec![Some(Answer::Int(4361)), Some(Answer::Int(467835))]);
+
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json[4]["day"], 5);
+        assert_eq!(json[4]["parts"][1]["answer"], 46);
+        assert!(json[4]["parts"][1]["error"].is_null());
+        assert_eq!(json[4]["parts"][1]["diagnostics"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_run_bundle() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let day_3 = std::fs::read_to_string(test_dir.join("day_3.dat")).unwrap();
+        let day_4 = std::fs::read_to_string(test_dir.join("day_4.dat")).unwrap();
+
+        let bundle_file = std::env::temp_dir().join(format!("aoc23_run_bundle_{}.txt", std::process::id()));
+        std::fs::write(&bundle_file, format!("## day 4\n{}\n## day 3\n{}\n## day 25\nno solver yet\n", day_4, day_3)).unwrap();
+
+        let inputs = InputSet::from_bundle(&bundle_file).unwrap();
+        assert_eq!(inputs.days().collect::<Vec<u32>>(), vec![3, 4]);
+
+        let results = run_bundle(&bundle_file).unwrap();
+        let answers: Vec<(u32, Vec<Option<Answer>>)> = results.into_iter().map(|r| (r.day, r.parts.into_iter().map(|p| p.answer).collect())).collect();
+        assert_eq!(answers, vec![
+            (3, vec![Some(Answer::Int(4361)), Some(Answer::Int(467835))]),
+            (4, vec![Some(Answer::Int(13)), Some(Answer::Int(30))])
+        ]);
+
+        std::fs::write(&bundle_file, "## day 3\n467..114..\n## day 3\n").unwrap();
+        assert!(matches!(run_bundle(&bundle_file), Err(AocError::Parse { line: Some(3), .. })));
+
+        std::fs::remove_file(&bundle_file).unwrap();
+    }
+
+    #[test]
+    fn test_run_all_with_budget() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let inputs = InputSet::from_dir(&test_dir).unwrap();
+
+        let generous = Budget { wall_time: Some(Duration::from_secs(60)), memory_bytes: None };
+        let answers = |results: Vec<DayResult>| -> Vec<Vec<Option<Answer>>> {
+            results.into_iter().map(|r| r.parts.into_iter().map(|p| p.answer).collect()).collect()
+        };
+        let (results, abandoned) = run_all_with_budget(&inputs, &generous);
+        assert_eq!(answers(results), answers(run_all(&inputs)));
+        assert_eq!(abandoned, None);
+
+        // A day whose second part overruns keeps the answer of its first
+        struct Slow;
+        impl Solver for Slow {
+            fn day(&self) -> u32 {
+                9
+            }
+
+            fn part1(&self, _input: &str) -> Result<Answer, AocError> {
+                Ok(Answer::Int(1))
+            }
+
+            fn part2(&self, _input: &str) -> Result<Answer, AocError> {
+                std::thread::sleep(Duration::from_secs(2));
+                Ok(Answer::Int(2))
+            }
+        }
+
+        // The run stops at the day over its budget, skipping the days after it
+        let mut inputs = inputs.clone();
+        inputs.insert(9, String::new());
+        let mut solvers = inputs.solvers();
+        let after_day_2 = solvers.iter().position(|s| s.day() == 2).unwrap() + 1;
+        solvers.insert(after_day_2, Box::new(Slow));
+
+        // Loose enough for the real days under the load of the other tests, the slow part taking twice as long
+        let tight = Budget { wall_time: Some(Duration::from_secs(1)), memory_bytes: None };
+        let (results, abandoned) = run_solvers_with_budget(solvers, &inputs, &tight);
+        assert_eq!(results.iter().map(|r| r.day).collect::<Vec<u32>>(), vec![2, 9]);
+        assert_eq!(results[1].parts[0].answer, Some(Answer::Int(1)));
+        assert!(results[1].parts[1].answer.is_none());
+        assert!(results[1].parts[1].error.as_ref().unwrap().starts_with("Day 9 abandoned: Wall time budget"));
+
+        let abandoned = abandoned.unwrap();
+        assert_eq!((abandoned.day, abandoned.part, abandoned.skipped.clone()), (9, 2, vec![3, 4, 5]));
+        assert!(abandoned.to_string().ends_with(". Days not run: 3, 4, 5"));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_run_all_parallel() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let mut inputs = InputSet::from_dir(&test_dir).unwrap();
+        inputs.insert(1, "1abc2\npqr3stu8vwx\n".to_string());
+
+        // Days come back in the order of the input set however the threads finish
+        let parallel = run_all_parallel(&inputs);
+        assert_eq!(parallel.iter().map(|r| r.day).collect::<Vec<u32>>(), inputs.days().collect::<Vec<u32>>());
+
+        type Outcomes = Vec<(u32, Vec<Option<Answer>>, Vec<Option<String>>)>;
+        let answers = |results: Vec<DayResult>| -> Outcomes {
+            results
+                .into_iter()
+                .map(|r| (r.day, r.parts.iter().map(|p| p.answer.clone()).collect(), r.parts.into_iter().map(|p| p.error).collect()))
+                .collect()
+        };
+
+        assert_eq!(answers(parallel), answers(run_all(&inputs)));
+
+        for threads in [0, 1, 3] {
+            assert_eq!(answers(run_all_with_threads(&inputs, threads).unwrap()), answers(run_all(&inputs)));
+        }
+    }
+}
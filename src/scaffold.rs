@@ -0,0 +1,228 @@
+/*                        NEW DAY SCAFFOLDING
+
+Creates the boilerplate for a new day: a `day_N.rs` module of the aoc-core
+crate with parse, part 1 and part 2 functions and a `Solver`, a test module
+reading `data/test/day_N.dat`, and the declaration of the module in the
+crate root. The module uses only core and alloc, as the other days do, and
+submits its solver to the registry itself, so `solver.rs` is left alone. The
+generated tests are ignored until the example answers are filled in.
+
+*/
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+
+// Where the days live within the repository, see crates/aoc-core
+const CORE_SOURCE_DIR: &str = "crates/aoc-core/src";
+
+pub fn day_template(day: u32) -> String {
+    /* The source of a new day module with placeholder solutions. */
+    format!(r#"/*                        ADVENT OF CODE DAY {day}
+
+Describe the puzzle here.
+
+*/
+
+use alloc::{{boxed::Box, string::{{String, ToString}}, vec::Vec}};
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solver::{{Registration, Solver}};
+
+pub fn parse(input: &str) -> Result<Vec<String>, AocError> {{
+    /* Read the puzzle input into the form used by both parts.
+
+    # Arguments
+
+    * `input` - the puzzle input
+
+    */
+    let _span = tracing::info_span!("parse", day = {day}).entered();
+
+    Ok(input.lines().map(|l| l.to_string()).collect())
+}}
+
+pub fn part1(input: &str) -> Result<Answer, AocError> {{
+    /* Solve part 1 of the puzzle. */
+    let _span = tracing::info_span!("solve", day = {day}, part = 1).entered();
+
+    let _puzzle = parse(input)?;
+    Err(AocError::MissingData("Day {day} part 1 has not been solved".to_string()))
+}}
+
+pub fn part2(input: &str) -> Result<Answer, AocError> {{
+    /* Solve part 2 of the puzzle. */
+    let _span = tracing::info_span!("solve", day = {day}, part = 2).entered();
+
+    let _puzzle = parse(input)?;
+    Err(AocError::MissingData("Day {day} part 2 has not been solved".to_string()))
+}}
+
+pub struct Day{day};
+
+impl Solver for Day{day} {{
+    fn day(&self) -> u32 {{
+        {day}
+    }}
+
+    fn part1(&self, input: &str) -> Result<Answer, AocError> {{
+        part1(input)
+    }}
+
+    fn part2(&self, input: &str) -> Result<Answer, AocError> {{
+        part2(input)
+    }}
+}}
+
+inventory::submit! {{
+    Registration {{ name: "default", build: || Box::new(Day{day}) }}
+}}
+
+#[cfg(test)]
+mod test {{
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_input() -> String {{
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push("../../data/test/day_{day}.dat");
+        std::fs::read_to_string(&test_file).unwrap()
+    }}
+
+    #[test]
+    #[ignore = "fill in the answer to the example once part 1 is solved"]
+    fn test_part1() {{
+        assert_eq!(part1(&test_input()).unwrap(), 0);
+    }}
+
+    #[test]
+    #[ignore = "fill in the answer to the example once part 2 is solved"]
+    fn test_part2() {{
+        assert_eq!(part2(&test_input()).unwrap(), 0);
+    }}
+}}
+"#, day = day)
+}
+
+pub fn register_module(lib_source: &str, day: u32) -> Result<String, AocError> {
+    /* Add `pub mod day_N;` to the crate root, after the modules of the earlier days. */
+    let module = format!("pub mod day_{};", day);
+    if lib_source.lines().any(|l| l.trim() == module) {
+        return Err(AocError::InvalidArgument(format!("Module day_{} is already declared", day)));
+    }
+
+    let day_re = match Regex::new(r"^pub mod day_(\d+);$") {
+        Ok(r) => r,
+        Err(e) => return Err(AocError::Regex(e))
+    };
+
+    let mut lines: Vec<&str> = lib_source.lines().collect();
+    let insert_at = match lines.iter().rposition(|l| match day_re.captures(l.trim()).and_then(|c| c[1].parse::<u32>().ok()) {
+        Some(d) => d < day,
+        None => false
+    }) {
+        Some(i) => i + 1,
+        None => 0
+    };
+    lines.insert(insert_at, &module);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+pub fn new_day(root: impl AsRef<Path>, day: u32) -> Result<Vec<PathBuf>, AocError> {
+    /* Scaffold a new day within the repository at the given root directory.
+
+    # Returns
+
+    The files written or changed.
+
+    # Examples
+
+    ```
+    for file in new_day(".", 6).unwrap() {
+        println!("Wrote {}", file.display());
+    }
+    ```
+    */
+    if !(1..=25).contains(&day) {
+        return Err(AocError::InvalidArgument(format!("There is no day {}", day)));
+    }
+
+    let root = root.as_ref();
+    let module_file = root.join(CORE_SOURCE_DIR).join(format!("day_{}.rs", day));
+    if module_file.exists() {
+        return Err(AocError::InvalidArgument(format!("'{}' already exists", module_file.display())));
+    }
+
+    let lib_file = root.join(CORE_SOURCE_DIR).join("lib.rs");
+    let test_file = root.join("data").join("test").join(format!("day_{}.dat", day));
+
+    let read = |path: &Path| match std::fs::read_to_string(path) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(AocError::io(path, e))
+    };
+    let write = |path: &Path, contents: &str| match std::fs::write(path, contents) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(AocError::io(path, e))
+    };
+
+    // The registration is checked before anything is written
+    let lib_source = register_module(&read(&lib_file)?, day)?;
+
+    write(&module_file, &day_template(day))?;
+    write(&lib_file, &lib_source)?;
+
+    let mut written = vec![module_file, lib_file];
+    if !test_file.exists() {
+        if let Some(dir) = test_file.parent() {
+            match std::fs::create_dir_all(dir) {
+                Ok(_) => (),
+                Err(e) => return Err(AocError::io(dir, e))
+            };
+        }
+        write(&test_file, "")?;
+        written.push(test_file);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_register_module() {
+        let lib_source = "pub mod bench;\npub mod day_1;\npub mod day_2;\npub mod detect;\npub use error::AocError;\n";
+
+        assert_eq!(
+            register_module(lib_source, 3).unwrap(),
+            "pub mod bench;\npub mod day_1;\npub mod day_2;\npub mod day_3;\npub mod detect;\npub use error::AocError;\n"
+        );
+        assert!(register_module(lib_source, 2).is_err());
+        assert!(register_module(&register_module(include_str!("../crates/aoc-core/src/lib.rs"), 6).unwrap(), 6).is_err());
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc23_scaffold_{}", std::process::id()));
+        std::fs::create_dir_all(root.join(CORE_SOURCE_DIR)).unwrap();
+        std::fs::write(root.join(CORE_SOURCE_DIR).join("lib.rs"), include_str!("../crates/aoc-core/src/lib.rs")).unwrap();
+
+        let written = new_day(&root, 7).unwrap();
+        let module = std::fs::read_to_string(root.join(CORE_SOURCE_DIR).join("day_7.rs")).unwrap();
+        let test_data_exists = root.join("data/test/day_7.dat").exists();
+        let repeated = new_day(&root, 7);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(written.len(), 3);
+        assert!(module.contains("impl Solver for Day7"));
+        assert!(module.contains("Registration { name: \"default\", build: || Box::new(Day7) }"));
+        assert!(module.contains("data/test/day_7.dat"));
+        assert!(test_data_exists);
+        assert!(repeated.is_err());
+        assert!(new_day(&root, 26).is_err());
+    }
+}
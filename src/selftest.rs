@@ -0,0 +1,112 @@
+/*                        SELF TEST
+
+Checks every solver against the sample inputs of data/test, which are built
+into the crate along with their answers, so that a packaged binary can confirm
+it works without any puzzle inputs on disk, e.g.
+
+aoc23 selftest
+
+Day 1 has a different example for each part, so each example carries the
+answers of only the parts it is an example for. A registered solver without
+an example in EXAMPLES, such as a day just created with `aoc23 new-day`, is
+reported by `untested_days` so that it fails the self test rather than being
+silently left out.
+
+*/
+
+use crate::answer::Answer;
+use crate::runner::run_part;
+use crate::solver::{solver, solvers};
+use crate::verify::PartCheck;
+
+// A sample input and its known answers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub day: u32,
+    pub input: &'static str,
+    pub part1: Option<i64>,
+    pub part2: Option<i64>
+}
+
+pub const EXAMPLES: &[Example] = &[
+    Example { day: 1, input: include_str!("../data/test/day_1_1.dat"), part1: Some(142), part2: None },
+    Example { day: 1, input: include_str!("../data/test/day_1_2.dat"), part1: None, part2: Some(281) },
+    Example { day: 2, input: include_str!("../data/test/day_2.dat"), part1: Some(8), part2: Some(2268) },
+    Example { day: 3, input: include_str!("../data/test/day_3.dat"), part1: Some(4361), part2: Some(467835) },
+    Example { day: 4, input: include_str!("../data/test/day_4.dat"), part1: Some(13), part2: Some(30) },
+    Example { day: 5, input: include_str!("../data/test/day_5.dat"), part1: Some(35), part2: Some(46) }
+];
+
+pub fn selftest() -> Vec<PartCheck> {
+    /* Solve every example with the registered solver of its day and check the answers.
+
+    An example for a day without a solver fails with an error.
+
+    # Examples
+
+    ```
+    let checks = selftest();
+    print!("{}", verify::report(&checks));
+    ```
+    */
+    let mut checks = Vec::<PartCheck>::new();
+
+    for example in EXAMPLES {
+        let day_solver = solver(example.day);
+
+        for (part, expected) in [(1, example.part1), (2, example.part2)] {
+            let expected = match expected {
+                Some(e) => Answer::Int(e),
+                None => continue
+            };
+
+            let (actual, error) = match &day_solver {
+                Some(s) => {
+                    let result = run_part(s.as_ref(), part, example.input);
+                    (result.answer, result.error)
+                },
+                None => (None, Some(format!("No solver for day {}", example.day)))
+            };
+
+            checks.push(PartCheck { day: example.day, part, expected, actual, error });
+        }
+    }
+
+    checks
+}
+
+pub fn untested_days() -> Vec<u32> {
+    /* The days with a registered solver but no example answer for one of their parts. */
+    let days: Vec<u32> = solvers().iter().map(|s| s.day()).collect();
+    without_examples(&days, EXAMPLES)
+}
+
+fn without_examples(days: &[u32], examples: &[Example]) -> Vec<u32> {
+    days.iter()
+        .copied()
+        .filter(|&day| {
+            let of_day = || examples.iter().filter(move |e| e.day == day);
+            of_day().all(|e| e.part1.is_none()) || of_day().all(|e| e.part2.is_none())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_selftest() {
+        let checks = selftest();
+        assert_eq!(checks.len(), 10);
+        assert!(checks.iter().all(|c| c.passed()), "{}", crate::verify::report(&checks));
+
+        let day_1: Vec<(u32, Option<Answer>)> = checks.iter().filter(|c| c.day == 1).map(|c| (c.part, c.actual.clone())).collect();
+        assert_eq!(day_1, vec![(1, Some(Answer::Int(142))), (2, Some(Answer::Int(281)))]);
+
+        // Every registered solver has an example for both parts, unlike a day just scaffolded
+        assert!(untested_days().is_empty());
+        let part1_only = Example { day: 6, input: "", part1: Some(288), part2: None };
+        assert_eq!(without_examples(&[1, 5, 6, 7], &[EXAMPLES[0], EXAMPLES[1], EXAMPLES[5], part1_only]), vec![6, 7]);
+    }
+}
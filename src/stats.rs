@@ -0,0 +1,126 @@
+/*                        SOLVE STATISTICS
+
+Sums up the runs kept in the results store, see src/store.rs, for each day:
+how many runs included it, when it was first solved, i.e. the first run in
+which none of its parts failed, and the best time of each part over every run
+which answered it, so that progress over the month can be looked back on with
+`aoc23 stats`:
+
+Day  Runs  First solved           Part 1       Part 2
+  4     2  2023-12-04 05:12   187.000 µs   201.000 µs
+  5     7  2023-12-05 05:31     1.201 ms     93.402 s
+
+*/
+
+use std::collections::BTreeMap;
+
+use crate::bench::format_duration;
+use crate::store::{format_timestamp, RunRecord};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayStats {
+    pub day: u32,
+    // The runs which included the day, whether or not they solved it
+    pub runs: usize,
+    // When the first run which solved every part it tried finished, in seconds since the Unix epoch
+    pub first_solved: Option<u64>,
+    // The best time of each part answered, in milliseconds
+    pub best_ms: BTreeMap<u32, f64>
+}
+
+pub fn day_stats(runs: &[RunRecord]) -> Vec<DayStats> {
+    /* The statistics of each day found in the runs, ordered by day.
+
+    # Examples
+
+    ```
+    let runs = config.results_store().runs(2023, None).unwrap();
+    for stats in day_stats(&runs) {
+        println!("Day {} was run {} times", stats.day, stats.runs);
+    }
+    ```
+    */
+    let mut days = BTreeMap::<u32, DayStats>::new();
+    for run in runs {
+        for result in &run.results {
+            let stats = days.entry(result.day).or_insert_with(|| DayStats { day: result.day, runs: 0, first_solved: None, best_ms: BTreeMap::new() });
+            stats.runs += 1;
+
+            // The store is kept oldest first, but the earliest is taken should it have been edited
+            if !result.parts.is_empty() && result.parts.iter().all(|p| p.answer.is_some()) {
+                stats.first_solved = Some(stats.first_solved.map_or(run.timestamp, |t| t.min(run.timestamp)));
+            }
+            for part in result.parts.iter().filter(|p| p.answer.is_some()) {
+                let best = stats.best_ms.entry(part.part).or_insert(part.elapsed_ms);
+                *best = best.min(part.elapsed_ms);
+            }
+        }
+    }
+    days.into_values().collect()
+}
+
+pub fn table(stats: &[DayStats]) -> String {
+    /* Lay out the statistics as a table with a row for each day, as shown at the top of this module. */
+    let mut rows = format!("{:>3}  {:>4}  {:<16}  {:>11}  {:>11}\n", "Day", "Runs", "First solved", "Part 1", "Part 2");
+    for day in stats {
+        let best = |part: u32| match day.best_ms.get(&part) {
+            Some(ms) => format_duration(std::time::Duration::from_secs_f64(ms.max(0.0) / 1000.0)),
+            None => "-".to_string()
+        };
+        let first_solved = match day.first_solved {
+            Some(t) => format_timestamp(t),
+            None => "-".to_string()
+        };
+        rows.push_str(&format!("{:>3}  {:>4}  {:<16}  {:>11}  {:>11}\n", day.day, day.runs, first_solved, best(1), best(2)));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::runner::{DayResult, PartResult};
+
+    // The answer, if any, and time in milliseconds of each part of a day
+    type Parts<'a> = &'a [(Option<i64>, f64)];
+
+    fn run(timestamp: u64, days: &[(u32, Parts)]) -> RunRecord {
+        let results = days.iter().map(|&(day, parts)| DayResult {
+            day,
+            parts: parts.iter().enumerate().map(|(i, &(answer, elapsed_ms))| PartResult {
+                part: i as u32 + 1,
+                answer: answer.map(Answer::Int),
+                error: if answer.is_none() {Some("failed".to_string())} else {None},
+                diagnostics: Vec::new(),
+                elapsed_ms
+            }).collect()
+        }).collect();
+        RunRecord { timestamp, command: "all".to_string(), year: 2023, profile: None, results }
+    }
+
+    #[test]
+    fn test_day_stats() {
+        let runs = vec![
+            run(1_701_752_400, &[(5, &[(Some(35), 1.2), (None, 3.0)]), (4, &[(Some(13), 0.3), (Some(30), 0.2)])]),
+            run(1_701_756_000, &[(5, &[(Some(35), 0.9), (Some(46), 93_402.0)])]),
+            run(1_701_759_600, &[(5, &[(Some(35), 1.0), (Some(46), 0.4)]), (3, &[(None, 1.0)])])
+        ];
+        let stats = day_stats(&runs);
+
+        // Day 5 was first solved by the second run, its failed part 2 of the first not counting
+        assert_eq!(stats.iter().map(|s| (s.day, s.runs, s.first_solved)).collect::<Vec<_>>(), vec![
+            (3, 1, None),
+            (4, 1, Some(1_701_752_400)),
+            (5, 3, Some(1_701_756_000))
+        ]);
+        assert_eq!(stats[2].best_ms, BTreeMap::from([(1, 0.9), (2, 0.4)]));
+        assert!(stats[0].best_ms.is_empty());
+
+        let rows = table(&stats);
+        assert_eq!(rows.lines().next().unwrap(), "Day  Runs  First solved           Part 1       Part 2");
+        assert_eq!(rows.lines().nth(1).unwrap(), "  3     1  -                           -            -");
+        assert_eq!(rows.lines().nth(3).unwrap(), "  5     3  2023-12-05 06:00   900.000 µs   400.000 µs");
+        assert!(day_stats(&[]).is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+/*                        RESULTS STORE
+
+Keeps the answers and times of every `aoc23 all` and `aoc23 run`, so that how
+the solutions changed over the month can be looked back on, e.g. with
+`aoc23 report`. Each run is a line of JSON appended to results.jsonl, holding
+when it finished, the command, year and profile, and its results as printed
+by `aoc23 all`:
+
+{"timestamp":1701753012,"command":"run","year":2023,"profile":null,"results":[...]}
+
+The store lives in $XDG_DATA_HOME/aoc23 (or ~/.local/share/aoc23) unless
+another directory is configured with `results_dir`. A line is appended with a
+single write, and a line which cannot be read back, e.g. one cut short when
+the disk filled up, is skipped with a warning rather than losing the rest.
+
+The answers submitted to the site are kept alongside, in submissions.jsonl,
+see src/submit.rs.
+
+*/
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::AocError;
+use crate::runner::DayResult;
+use crate::submit::Submission;
+
+const RESULTS_FILE: &str = "results.jsonl";
+const SUBMISSIONS_FILE: &str = "submissions.jsonl";
+
+// A finished run of `aoc23 all` or `aoc23 run`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    // Seconds since the Unix epoch at which the run finished
+    pub timestamp: u64,
+    pub command: String,
+    pub year: u32,
+    // The profile in use, as answers and times differ between accounts
+    pub profile: Option<String>,
+    pub results: Vec<DayResult>
+}
+
+impl RunRecord {
+    pub fn now(command: &str, year: u32, profile: Option<&str>, results: Vec<DayResult>) -> RunRecord {
+        /* The record of a run which has just finished. */
+        RunRecord { timestamp: now(), command: command.to_string(), year, profile: profile.map(|p| p.to_string()), results }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResultsStore {
+    root: PathBuf
+}
+
+impl ResultsStore {
+    pub fn new(root: impl AsRef<Path>) -> ResultsStore {
+        ResultsStore { root: root.as_ref().to_path_buf() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn results_path(&self) -> PathBuf {
+        self.root.join(RESULTS_FILE)
+    }
+
+    pub fn submissions_path(&self) -> PathBuf {
+        self.root.join(SUBMISSIONS_FILE)
+    }
+
+    pub fn append(&self, record: &RunRecord) -> Result<(), AocError> {
+        /* Add a run to the end of the store, creating it if need be. */
+        self.append_line(&self.results_path(), record)
+    }
+
+    pub fn load(&self) -> Result<Vec<RunRecord>, AocError> {
+        /* Every run in the store, oldest first, none if nothing has been stored yet. */
+        self.load_lines(&self.results_path())
+    }
+
+    pub fn runs(&self, year: u32, profile: Option<&str>) -> Result<Vec<RunRecord>, AocError> {
+        /* The runs of one year and profile, oldest first. */
+        Ok(self.load()?.into_iter().filter(|r| r.year == year && r.profile.as_deref() == profile).collect())
+    }
+
+    pub fn append_submission(&self, submission: &Submission) -> Result<(), AocError> {
+        /* Add an answer submitted to the site to the end of the store, creating it if need be. */
+        self.append_line(&self.submissions_path(), submission)
+    }
+
+    pub fn submissions(&self) -> Result<Vec<Submission>, AocError> {
+        /* Every answer submitted to the site, oldest first. */
+        self.load_lines(&self.submissions_path())
+    }
+
+    fn append_line<T: Serialize>(&self, path: &Path, value: &T) -> Result<(), AocError> {
+        let mut line = match serde_json::to_string(value) {
+            Ok(l) => l,
+            Err(e) => return Err(AocError::parse(format!("Failed to write '{}': {}", path.display(), e)))
+        };
+        line.push('\n');
+
+        match std::fs::create_dir_all(&self.root) {
+            Ok(_) => (),
+            Err(e) => return Err(AocError::io(&self.root, e))
+        };
+
+        let written = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut f| f.write_all(line.as_bytes()));
+        match written {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AocError::io(path, e))
+        }
+    }
+
+    fn load_lines<T: DeserializeOwned>(&self, path: &Path) -> Result<Vec<T>, AocError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(AocError::io(path, e))
+        };
+
+        let mut values = Vec::<T>::new();
+        for (i, line) in content.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(v) => values.push(v),
+                Err(e) => tracing::warn!("Skipping line {} of '{}', which cannot be read: {}", i + 1, path.display(), e)
+            };
+        }
+        Ok(values)
+    }
+}
+
+pub fn default_dir() -> Option<PathBuf> {
+    /* The aoc23 directory within the user's data directory. */
+    let data_dir = match std::env::var_os("XDG_DATA_HOME") {
+        Some(d) if !d.is_empty() => PathBuf::from(d),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".local/share")
+    };
+    Some(data_dir.join("aoc23"))
+}
+
+pub fn format_timestamp(timestamp: u64) -> String {
+    /* A time given in seconds since the Unix epoch as a UTC date and time.
+
+    # Examples
+
+    ```
+    assert_eq!(format_timestamp(1_701_752_400), "2023-12-05 05:00");
+    ```
+    */
+    // Howard Hinnant's `civil_from_days`, counting from March so the leap day ends the year
+    let days = (timestamp / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {month_index + 3} else {month_index - 9};
+    let year = year_of_era + era * 400 + if month <= 2 {1} else {0};
+
+    let seconds = timestamp % 86400;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, seconds / 3600, seconds / 60 % 60)
+}
+
+fn now() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs(),
+        Err(_) => 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::runner::PartResult;
+    use crate::submit::Verdict;
+
+    #[test]
+    fn test_store() {
+        let store = ResultsStore::new(std::env::temp_dir().join(format!("aoc23_store_{}", std::process::id())));
+        let missing = store.load();
+
+        let part = PartResult { part: 1, answer: Some(Answer::Int(35)), error: None, diagnostics: Vec::new(), elapsed_ms: 1.5 };
+        let first = RunRecord { timestamp: 10, command: "all".to_string(), year: 2023, profile: None, results: vec![DayResult { day: 5, parts: vec![part] }] };
+        let other = RunRecord { timestamp: 20, command: "run".to_string(), year: 2023, profile: Some("work".to_string()), results: Vec::new() };
+        store.append(&first).unwrap();
+        store.append(&other).unwrap();
+
+        // A line cut short is skipped, keeping the runs either side of it
+        let mut file = std::fs::OpenOptions::new().append(true).open(store.results_path()).unwrap();
+        file.write_all(b"{\"timestamp\":30,\"comm\n").unwrap();
+        store.append(&RunRecord { timestamp: 40, ..first.clone() }).unwrap();
+
+        // Submissions are kept apart from the runs
+        let submission = Submission::now(2023, 5, 1, None, &Answer::Int(35), Verdict::Correct);
+        store.append_submission(&submission).unwrap();
+
+        let loaded = store.load();
+        let mine = store.runs(2023, None);
+        let work = store.runs(2023, Some("work"));
+        let submissions = store.submissions();
+        std::fs::remove_dir_all(store.root()).unwrap();
+
+        assert!(missing.unwrap().is_empty());
+        assert_eq!(loaded.unwrap().iter().map(|r| r.timestamp).collect::<Vec<u64>>(), vec![10, 20, 40]);
+        assert_eq!(mine.unwrap(), vec![first.clone(), RunRecord { timestamp: 40, ..first }]);
+        assert_eq!(work.unwrap(), vec![other]);
+        assert_eq!(submissions.unwrap(), vec![submission]);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_701_752_400), "2023-12-05 05:00");
+        assert_eq!(format_timestamp(951_782_400 + 86399), "2000-02-29 23:59");
+        assert_eq!(format_timestamp(1_735_102_800), "2024-12-25 05:00");
+    }
+}
@@ -0,0 +1,321 @@
+/*                        ANSWER SUBMISSION
+
+Submits answers to adventofcode.com, keeping every answer submitted and the
+verdict of the site in the results store, see src/store.rs, as a line of
+submissions.jsonl each:
+
+{"timestamp":1701753012,"year":2023,"day":5,"part":2,"profile":null,"answer":"47","verdict":"too_high"}
+
+The site only says whether an answer is right, sometimes adding that a wrong
+one is too high or too low, and makes each wrong answer wait longer before the
+next. The history of a part is used to refuse an answer rejected before, or
+one outside the bounds set by the answers found too high or too low, without
+asking the site again, and `aoc23 run` shows those bounds next to each answer
+it finds.
+
+Submitting needs the fetch feature, the history can be read without it.
+
+*/
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::answer::Answer;
+use crate::config::Config;
+use crate::error::AocError;
+use crate::store::format_timestamp;
+
+// What the site made of a submitted answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    // The answer was not judged, as the wait after a wrong answer had not passed
+    TooSoon,
+    // The answer was not judged, as the part is already solved or not yet unlocked
+    WrongLevel,
+    Unrecognised
+}
+
+impl Verdict {
+    pub fn from_response(page: &str) -> Verdict {
+        /* The verdict given in the page sent back for a submitted answer.
+
+        # Examples
+
+        ```
+        let page = "<article><p>That's not the right answer; your answer is too high.</p></article>";
+        assert_eq!(Verdict::from_response(page), Verdict::TooHigh);
+        ```
+        */
+        if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("That's not the right answer") {
+            if page.contains("too high") {
+                Verdict::TooHigh
+            } else if page.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::TooSoon
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unrecognised
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Verdict::Correct => "the right answer",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Incorrect => "not the right answer",
+            Verdict::TooSoon => "not judged, as it came too soon after a wrong answer",
+            Verdict::WrongLevel => "not judged, as the part is already solved or not yet unlocked",
+            Verdict::Unrecognised => "given a response which was not understood"
+        };
+        write!(f, "{}", text)
+    }
+}
+
+// An answer submitted for a part, as kept in the results store
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    // Seconds since the Unix epoch at which the site answered
+    pub timestamp: u64,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    // The profile in use, as each account has its own input and answers
+    pub profile: Option<String>,
+    pub answer: String,
+    pub verdict: Verdict
+}
+
+impl Submission {
+    pub fn now(year: u32, day: u32, part: u32, profile: Option<&str>, answer: &Answer, verdict: Verdict) -> Submission {
+        /* The record of an answer the site has just judged. */
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        Submission { timestamp, year, day, part, profile: profile.map(|p| p.to_string()), answer: answer.to_string(), verdict }
+    }
+
+    fn number(&self) -> Option<i128> {
+        self.answer.trim().parse::<i128>().ok()
+    }
+}
+
+// The range a numeric answer must lie within, given the answers found too low and too high
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bounds {
+    // The highest answer found too low, which the answer is above
+    pub above: Option<i128>,
+    // The lowest answer found too high, which the answer is below
+    pub below: Option<i128>
+}
+
+impl Bounds {
+    pub fn is_empty(&self) -> bool {
+        self.above.is_none() && self.below.is_none()
+    }
+
+    pub fn contains(&self, answer: i128) -> bool {
+        self.above.is_none_or(|a| answer > a) && self.below.is_none_or(|b| answer < b)
+    }
+}
+
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.above, self.below) {
+            (Some(a), Some(b)) => write!(f, "above {} and below {}", a, b),
+            (Some(a), None) => write!(f, "above {}", a),
+            (None, Some(b)) => write!(f, "below {}", b),
+            (None, None) => write!(f, "unbounded")
+        }
+    }
+}
+
+// The answers submitted for one part, oldest first
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    year: u32,
+    day: u32,
+    part: u32,
+    submissions: Vec<Submission>
+}
+
+impl History {
+    pub fn new(submissions: &[Submission], year: u32, day: u32, part: u32, profile: Option<&str>) -> History {
+        /* The history of a part of a puzzle for a profile, out of every submission in the store. */
+        let submissions = submissions
+            .iter()
+            .filter(|s| s.year == year && s.day == day && s.part == part && s.profile.as_deref() == profile)
+            .cloned()
+            .collect();
+        History { year, day, part, submissions }
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    pub fn correct(&self) -> Option<&Submission> {
+        self.submissions.iter().find(|s| s.verdict == Verdict::Correct)
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        let judged = |verdict: Verdict| self.submissions.iter().filter(move |s| s.verdict == verdict).filter_map(|s| s.number());
+        Bounds { above: judged(Verdict::TooLow).max(), below: judged(Verdict::TooHigh).min() }
+    }
+
+    pub fn check(&self, answer: &Answer) -> Result<(), AocError> {
+        /* Refuse an answer which the history shows would be wrong, or which is already accepted.
+
+        # Examples
+
+        ```
+        let history = History::new(&store.submissions()?, 2023, 5, 2, None);
+        history.check(&Answer::Int(46))?;
+        ```
+        */
+        let part = format!("{} day {} part {}", self.year, self.day, self.part);
+        let answer_text = answer.to_string();
+        if let Some(correct) = self.correct() {
+            return Err(AocError::InvalidArgument(match correct.answer == answer_text {
+                true => format!("{} is already the accepted answer of {}", answer_text, part),
+                false => format!("{} was solved on {} with {}, so {} is not the answer", part, format_timestamp(correct.timestamp), correct.answer, answer_text)
+            }));
+        }
+
+        if let Some(earlier) = self.submissions.iter().find(|s| s.verdict.is_wrong() && s.answer == answer_text) {
+            return Err(AocError::InvalidArgument(format!(
+                "{} was submitted for {} on {} and was {}", answer_text, part, format_timestamp(earlier.timestamp), earlier.verdict
+            )));
+        }
+
+        let bounds = self.bounds();
+        match answer_text.parse::<i128>() {
+            Ok(n) if !bounds.contains(n) => Err(AocError::InvalidArgument(format!(
+                "{} cannot be the answer of {}, which earlier submissions put {}", answer_text, part, bounds
+            ))),
+            _ => Ok(())
+        }
+    }
+
+    pub fn note(&self, answer: &Answer) -> Option<String> {
+        /* What the history says of a new answer, if anything, as shown by `aoc23 run`. */
+        match (self.correct(), self.check(answer)) {
+            (Some(c), _) if c.answer == answer.to_string() => Some("matches the accepted answer".to_string()),
+            (_, Err(e)) => Some(e.to_string()),
+            (_, Ok(_)) if !self.bounds().is_empty() => Some(format!("earlier submissions put the answer {}", self.bounds())),
+            _ => None
+        }
+    }
+}
+
+#[cfg(feature = "fetch")]
+pub fn submit(year: u32, day: u32, part: u32, answer: &Answer, config: &Config) -> Result<Verdict, AocError> {
+    /* Submit an answer unless its history shows it to be wrong, keeping the verdict in the results store.
+
+    # Examples
+
+    ```
+    let verdict = submit(2023, 5, 1, &Answer::Int(35), &Config::load().unwrap()).unwrap();
+    ```
+    */
+    let store = config.results_store();
+    let profile = config.profile.as_deref();
+    History::new(&store.submissions()?, year, day, part, profile).check(answer)?;
+
+    if crate::fetch::is_offline() {
+        return Err(AocError::InvalidArgument(format!("Cannot submit {} for {} day {} part {} in offline mode", answer, year, day, part)));
+    }
+    let session = crate::fetch::session_token(config)?;
+    let verdict = crate::fetch::post_answer(year, day, part, &answer.to_string(), &session, &config.cache())?;
+    tracing::info!(year, day, part, "Submitted {}, which was {}", answer, verdict);
+
+    // The site has judged the answer by now, so failing to keep it is only a warning
+    match store.append_submission(&Submission::now(year, day, part, profile, answer, verdict)) {
+        Ok(_) => (),
+        Err(e) => tracing::warn!("Failed to keep the submission in '{}': {}", store.root().display(), e)
+    };
+    Ok(verdict)
+}
+
+#[cfg(not(feature = "fetch"))]
+pub fn submit(_year: u32, _day: u32, _part: u32, _answer: &Answer, _config: &Config) -> Result<Verdict, AocError> {
+    Err(AocError::InvalidArgument("Submitting an answer needs the fetch feature".to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn submitted(timestamp: u64, part: u32, answer: &str, verdict: Verdict) -> Submission {
+        Submission { timestamp, year: 2023, day: 5, part, profile: None, answer: answer.to_string(), verdict }
+    }
+
+    #[test]
+    fn test_verdict() {
+        let article = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        assert_eq!(Verdict::from_response(&article("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.")), Verdict::Correct);
+        assert_eq!(Verdict::from_response(&article("That's not the right answer; your answer is too high.  If you're stuck, ...")), Verdict::TooHigh);
+        assert_eq!(Verdict::from_response(&article("That's not the right answer; your answer is too low.")), Verdict::TooLow);
+        assert_eq!(Verdict::from_response(&article("That's not the right answer.  If you're stuck, ...")), Verdict::Incorrect);
+        assert_eq!(Verdict::from_response(&article("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 47s left to wait.")), Verdict::TooSoon);
+        assert_eq!(Verdict::from_response(&article("You don't seem to be solving the right level.  Did you already complete it?")), Verdict::WrongLevel);
+        assert_eq!(Verdict::from_response("<html></html>"), Verdict::Unrecognised);
+
+        assert!(Verdict::TooLow.is_wrong());
+        assert!(!Verdict::TooSoon.is_wrong());
+        assert_eq!(serde_json::to_string(&Verdict::TooHigh).unwrap(), "\"too_high\"");
+    }
+
+    #[test]
+    fn test_history() {
+        let all = vec![
+            submitted(1_701_752_400, 2, "100", Verdict::TooHigh),
+            submitted(1_701_752_460, 2, "10", Verdict::TooLow),
+            submitted(1_701_752_520, 2, "60", Verdict::TooHigh),
+            submitted(1_701_752_580, 2, "46", Verdict::TooSoon),
+            submitted(1_701_752_640, 2, "EZFCHJAB", Verdict::Incorrect),
+            submitted(1_701_752_700, 1, "35", Verdict::Correct),
+            Submission { profile: Some("work".to_string()), ..submitted(1_701_752_760, 2, "46", Verdict::Correct) }
+        ];
+
+        let history = History::new(&all, 2023, 5, 2, None);
+        assert_eq!(history.submissions().len(), 5);
+        assert_eq!(history.correct(), None);
+        assert_eq!(history.bounds(), Bounds { above: Some(10), below: Some(60) });
+        assert_eq!(history.bounds().to_string(), "above 10 and below 60");
+
+        // An answer not judged before, within the bounds, may be submitted, even one the site did not judge
+        assert!(history.check(&Answer::Int(46)).is_ok());
+        assert!(history.check(&Answer::Text("ABC".to_string())).is_ok());
+        assert_eq!(history.check(&Answer::Int(60)).unwrap_err().to_string(), "60 was submitted for 2023 day 5 part 2 on 2023-12-05 05:02 and was too high");
+        assert!(history.check(&Answer::Int(75)).unwrap_err().to_string().contains("which earlier submissions put above 10 and below 60"));
+        assert!(history.check(&Answer::Int(10)).is_err());
+        assert!(history.check(&Answer::Text("EZFCHJAB".to_string())).is_err());
+        assert_eq!(history.note(&Answer::Int(46)).unwrap(), "earlier submissions put the answer above 10 and below 60");
+
+        let solved = History::new(&all, 2023, 5, 1, None);
+        assert!(solved.bounds().is_empty());
+        assert!(solved.check(&Answer::Int(35)).unwrap_err().to_string().contains("already the accepted answer"));
+        assert!(solved.check(&Answer::Int(36)).unwrap_err().to_string().contains("solved on 2023-12-05 05:05 with 35"));
+        assert_eq!(solved.note(&Answer::Int(35)).unwrap(), "matches the accepted answer");
+
+        assert_eq!(History::new(&all, 2023, 6, 1, None).note(&Answer::Int(288)), None);
+    }
+}
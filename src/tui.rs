@@ -0,0 +1,377 @@
+/*                        DASHBOARD
+
+An interactive terminal dashboard listing every registered day with the last
+answer and time of each part. A day can be re-run one part at a time and the
+log output of the selected day is tailed below the table, so a solver can be
+changed and checked without leaving the terminal.
+
+Keys:
+    up/down, k/j    select a day
+    tab, p          switch between part 1 and part 2
+    enter, r        re-run the selected part of the selected day
+    a               re-run both parts of every day
+    l               show or hide the log
+    q, esc          quit
+
+*/
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use regex::Regex;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::runner::{run_part, InputSet, PartResult};
+use crate::solver::Solver;
+
+// How many lines of log output are kept for the dashboard
+const LOG_CAPACITY: usize = 1000;
+
+#[derive(Debug, Default)]
+struct LogLines {
+    lines: VecDeque<String>,
+    // Whether the last line is still being written, as a line can arrive over several writes
+    partial: bool
+}
+
+// Collects log output in memory, one entry per line, dropping the oldest lines once full
+#[derive(Clone, Debug, Default)]
+pub struct LogBuffer {
+    lines: Arc<Mutex<LogLines>>
+}
+
+impl LogBuffer {
+    pub fn new() -> LogBuffer {
+        LogBuffer::default()
+    }
+
+    pub fn tail(&self, count: usize) -> Vec<String> {
+        /* The most recent lines of the log, oldest first. */
+        let log = match self.lines.lock() {
+            Ok(l) => l,
+            Err(poisoned) => poisoned.into_inner()
+        };
+        log.lines.iter().skip(log.lines.len().saturating_sub(count)).cloned().collect()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.tail(LOG_CAPACITY)
+    }
+}
+
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut log = match self.lines.lock() {
+            Ok(l) => l,
+            Err(poisoned) => poisoned.into_inner()
+        };
+
+        let text = String::from_utf8_lossy(buf);
+        for (i, line) in text.split('\n').enumerate() {
+            match (i, log.partial, log.lines.back_mut()) {
+                (0, true, Some(last)) => last.push_str(line),
+                _ => log.lines.push_back(line.to_string())
+            };
+        }
+
+        // Splitting on the newline ending a line leaves an empty line to carry on with
+        log.partial = true;
+        if text.ends_with('\n') {
+            log.lines.pop_back();
+            log.partial = false;
+        }
+
+        while log.lines.len() > LOG_CAPACITY {
+            log.lines.pop_front();
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'w> MakeWriter<'w> for LogBuffer {
+    type Writer = LogBuffer;
+
+    fn make_writer(&'w self) -> LogBuffer {
+        self.clone()
+    }
+}
+
+// The last result of each part of a day, if it has been run
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayRow {
+    pub day: u32,
+    pub parts: [Option<PartResult>; 2]
+}
+
+pub struct Dashboard {
+    inputs: InputSet,
+    solvers: Vec<Box<dyn Solver>>,
+    rows: Vec<DayRow>,
+    selected: usize,
+    part: u32,
+    show_log: bool,
+    logs: LogBuffer
+}
+
+impl Dashboard {
+    pub fn new(inputs: InputSet, logs: LogBuffer) -> Dashboard {
+        /* A dashboard over every registered day, none of which have been run yet. */
+        let solvers = inputs.solvers();
+        let rows = solvers.iter().map(|s| DayRow { day: s.day(), parts: [None, None] }).collect();
+
+        Dashboard { inputs, solvers, rows, selected: 0, part: 1, show_log: true, logs }
+    }
+
+    pub fn rows(&self) -> &[DayRow] {
+        &self.rows
+    }
+
+    pub fn selected_day(&self) -> Option<u32> {
+        self.rows.get(self.selected).map(|r| r.day)
+    }
+
+    pub fn part(&self) -> u32 {
+        self.part
+    }
+
+    pub fn run_selected(&mut self) {
+        /* Re-run the selected part of the selected day. */
+        self.run(self.selected, self.part);
+    }
+
+    pub fn run_everything(&mut self) {
+        /* Re-run both parts of every day. */
+        for index in 0..self.rows.len() {
+            for part in [1, 2] {
+                self.run(index, part);
+            }
+        }
+    }
+
+    fn run(&mut self, index: usize, part: u32) {
+        let solver = match self.solvers.get(index) {
+            Some(s) => s.as_ref(),
+            None => return
+        };
+
+        let result = match self.inputs.get(solver.day()) {
+            Some(input) => run_part(solver, part, input),
+            None => PartResult { part, answer: None, error: Some(format!("No input for day {}", solver.day())), diagnostics: Vec::new(), elapsed_ms: 0.0 }
+        };
+        self.rows[index].parts[part as usize - 1] = Some(result);
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        /* Act on a key press, returning false once the dashboard should close. */
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(self.rows.len().saturating_sub(1)),
+            KeyCode::Tab | KeyCode::Char('p') => self.part = if self.part == 1 {2} else {1},
+            KeyCode::Enter | KeyCode::Char('r') => self.run_selected(),
+            KeyCode::Char('a') => self.run_everything(),
+            KeyCode::Char('l') => self.show_log = !self.show_log,
+            _ => ()
+        };
+        true
+    }
+
+    fn day_logs(&self, day: u32, count: usize) -> Vec<String> {
+        /* The last lines of the log mentioning the given day, e.g. within a span `run{day=3 part=1}`. */
+        let day_re = match Regex::new(&format!(r"\bday={}\b", day)) {
+            Ok(r) => r,
+            Err(_) => return Vec::new()
+        };
+
+        let lines: Vec<String> = self.logs.lines().into_iter().filter(|l| day_re.is_match(l)).collect();
+        lines[lines.len().saturating_sub(count)..].to_vec()
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let log_height = if self.show_log {12} else {0};
+        let [table_area, log_area, help_area] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(log_height),
+            Constraint::Length(1)
+        ]).areas(frame.area());
+
+        let cell = |result: &Option<PartResult>| -> (String, String) {
+            match result {
+                Some(PartResult { answer: Some(a), elapsed_ms, .. }) => (a.to_string(), format!("{:.3} ms", elapsed_ms)),
+                Some(PartResult { error: Some(e), elapsed_ms, .. }) => (e.clone(), format!("{:.3} ms", elapsed_ms)),
+                _ => ("-".to_string(), "".to_string())
+            }
+        };
+
+        let rows = self.rows.iter().map(|r| {
+            let (answer_1, time_1) = cell(&r.parts[0]);
+            let (answer_2, time_2) = cell(&r.parts[1]);
+            Row::new(vec![r.day.to_string(), answer_1, time_1, answer_2, time_2])
+        });
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let header_style = |part: u32| if part == self.part {bold.add_modifier(Modifier::UNDERLINED)} else {bold};
+        let header = Row::new(vec![
+            Line::styled("Day", bold),
+            Line::styled("Part 1", header_style(1)),
+            Line::styled("Time", bold),
+            Line::styled("Part 2", header_style(2)),
+            Line::styled("Time", bold)
+        ]);
+
+        let widths = [Constraint::Length(5), Constraint::Fill(1), Constraint::Length(14), Constraint::Fill(1), Constraint::Length(14)];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered().title(" Advent of Code 2023 "))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, table_area, &mut state);
+
+        if self.show_log {
+            // Leave room for the border above and below the log
+            let day = self.selected_day().unwrap_or(0);
+            let lines: Vec<Line> = self.day_logs(day, log_height.saturating_sub(2) as usize).into_iter().map(Line::from).collect();
+            frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(format!(" Day {} log ", day))), log_area);
+        }
+
+        let help = "↑/↓ select  tab part  enter run  a run all  l log  q quit";
+        frame.render_widget(Paragraph::new(help), help_area);
+    }
+}
+
+pub fn run(inputs: InputSet, logs: LogBuffer) -> Result<(), String> {
+    /* Show the dashboard until it is closed, solving every day first.
+
+    The logs written to the buffer, e.g. by installing it with
+    `logging::init_with_writer`, are shown for the selected day.
+    */
+    let mut terminal = match ratatui::try_init() {
+        Ok(t) => t,
+        Err(e) => return Err(format!("Failed to start the dashboard: {}", e))
+    };
+
+    let mut dashboard = Dashboard::new(inputs, logs);
+    let result = event_loop(&mut terminal, &mut dashboard);
+
+    // The terminal is given back even if drawing failed
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, dashboard: &mut Dashboard) -> Result<(), String> {
+    let draw = |terminal: &mut DefaultTerminal, dashboard: &Dashboard| match terminal.draw(|frame| dashboard.draw(frame)) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Failed to draw the dashboard: {}", e))
+    };
+
+    // Fill in the days one at a time so the table is not blank until every day is solved
+    for index in 0..dashboard.rows.len() {
+        draw(terminal, dashboard)?;
+        for part in [1, 2] {
+            dashboard.run(index, part);
+        }
+    }
+
+    loop {
+        draw(terminal, dashboard)?;
+
+        let key = match event::read() {
+            Ok(Event::Key(k)) if k.kind == KeyEventKind::Press => k.code,
+            Ok(_) => continue,
+            Err(e) => return Err(format!("Failed to read from the terminal: {}", e))
+        };
+        if !dashboard.handle_key(key) {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answer::Answer;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_log_buffer() {
+        let mut logs = LogBuffer::new();
+        logs.write_all(b"first\nsecond\n").unwrap();
+        logs.clone().write_all(b"third\n").unwrap();
+
+        assert_eq!(logs.tail(2), vec!["second", "third"]);
+
+        for i in 0..LOG_CAPACITY {
+            writeln!(logs, "line {}", i).unwrap();
+        }
+        assert_eq!(logs.lines().len(), LOG_CAPACITY);
+        assert_eq!(logs.lines()[0], "line 0");
+    }
+
+    #[test]
+    fn test_dashboard() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let logs = LogBuffer::new();
+        let mut dashboard = Dashboard::new(InputSet::from_dir(&test_dir).unwrap(), logs.clone());
+
+        assert_eq!(dashboard.rows().iter().map(|r| r.day).collect::<Vec<u32>>(), vec![1, 2, 3, 4, 5]);
+        assert!(dashboard.rows().iter().all(|r| r.parts == [None, None]));
+
+        // Day 1 has no input in the test directory
+        assert!(dashboard.handle_key(KeyCode::Enter));
+        assert!(dashboard.rows()[0].parts[0].as_ref().unwrap().error.as_ref().unwrap().contains("No input"));
+
+        dashboard.handle_key(KeyCode::Down);
+        dashboard.handle_key(KeyCode::Char('j'));
+        dashboard.handle_key(KeyCode::Tab);
+        dashboard.handle_key(KeyCode::Char('r'));
+        assert_eq!(dashboard.selected_day(), Some(3));
+        assert_eq!(dashboard.part(), 2);
+        assert_eq!(dashboard.rows()[2].parts[1].as_ref().unwrap().answer, Some(Answer::Int(467835)));
+        assert!(dashboard.rows()[2].parts[0].is_none());
+
+        for _ in 0..10 {
+            dashboard.handle_key(KeyCode::Down);
+        }
+        assert_eq!(dashboard.selected_day(), Some(5));
+
+        dashboard.handle_key(KeyCode::Char('a'));
+        assert_eq!(dashboard.rows()[4].parts[0].as_ref().unwrap().answer, Some(Answer::Int(35)));
+        assert!(!dashboard.handle_key(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_draw() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let mut logs = LogBuffer::new();
+        writeln!(logs, "INFO run{{day=3 part=2}}: aoc23::runner: close").unwrap();
+        writeln!(logs, "INFO run{{day=30 part=2}}: aoc23::runner: close").unwrap();
+
+        let mut dashboard = Dashboard::new(InputSet::from_dir(&test_dir).unwrap(), logs);
+        dashboard.handle_key(KeyCode::Down);
+        dashboard.handle_key(KeyCode::Down);
+        dashboard.handle_key(KeyCode::Enter);
+        assert_eq!(dashboard.day_logs(3, 10).len(), 1);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| dashboard.draw(frame)).unwrap();
+        let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+
+        assert!(screen.contains("4361"));
+        assert!(screen.contains("Day 3 log"));
+        assert!(screen.contains("run{day=3 part=2}"));
+    }
+}
@@ -0,0 +1,116 @@
+/*                        PUZZLE UNLOCKS
+
+Each puzzle unlocks at midnight US Eastern Standard Time, 05:00 UTC, on its
+day of December. `aoc23 wait --day N` counts down to the unlock and then
+downloads the input, and scaffolds the day with --scaffold, so that release
+night only takes starting it a few minutes early.
+
+Times are worked out from the Unix epoch with the civil calendar rather than
+through a date library, as December in UTC is all that is needed.
+
+*/
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::AocError;
+
+// The hour of the day in UTC at which puzzles unlock, midnight in UTC-5
+pub const UNLOCK_HOUR_UTC: u64 = 5;
+
+// The first Advent of Code
+pub const FIRST_YEAR: u32 = 2015;
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    /* The number of days from 1970-01-01 to a date of the proleptic Gregorian calendar.
+
+    Counts from March so that the leap day falls at the end of the year, as in
+    Howard Hinnant's `days_from_civil`.
+    */
+    let year = if month <= 2 {year - 1} else {year};
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+pub fn unlock_time(year: u32, day: u32) -> Result<SystemTime, AocError> {
+    /* When the puzzle of a day unlocks.
+
+    # Examples
+
+    ```
+    // 2023-12-05T05:00:00Z
+    assert_eq!(unlock_time(2023, 5).unwrap(), UNIX_EPOCH + Duration::from_secs(1_701_752_400));
+    ```
+    */
+    if year < FIRST_YEAR {
+        return Err(AocError::InvalidArgument(format!("There was no Advent of Code in {}, the first was in {}", year, FIRST_YEAR)));
+    }
+    if !(1..=25).contains(&day) {
+        return Err(AocError::InvalidArgument(format!("Day {} is not a day of the event, which runs from day 1 to 25", day)));
+    }
+
+    let days = days_from_civil(year as i64, 12, day as i64) as u64;
+    Ok(UNIX_EPOCH + Duration::from_secs(days * 86400 + UNLOCK_HOUR_UTC * 3600))
+}
+
+pub fn until_unlock(year: u32, day: u32, now: SystemTime) -> Result<Option<Duration>, AocError> {
+    /* How long there is to wait at the given time for a puzzle to unlock, none once it has. */
+    match unlock_time(year, day)?.duration_since(now) {
+        Ok(remaining) if !remaining.is_zero() => Ok(Some(remaining)),
+        _ => Ok(None)
+    }
+}
+
+pub fn countdown(remaining: Duration) -> String {
+    /* The time left as hours, minutes and seconds, with days in front when there are any.
+
+    Part seconds are rounded up, so that the countdown reads 00:00:00 only once the
+    puzzle has unlocked.
+
+    # Examples
+
+    ```
+    assert_eq!(countdown(Duration::from_secs(3 * 86400 + 3723)), "3d 01:02:03");
+    ```
+    */
+    let seconds = remaining.as_secs() + if remaining.subsec_nanos() > 0 {1} else {0};
+    let (days, hours, minutes, seconds) = (seconds / 86400, seconds / 3600 % 24, seconds / 60 % 60, seconds % 60);
+    match days {
+        0 => format!("{:02}:{:02}:{:02}", hours, minutes, seconds),
+        _ => format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+
+        assert_eq!(unlock_time(2023, 1).unwrap(), UNIX_EPOCH + Duration::from_secs(1_701_406_800));
+        assert_eq!(unlock_time(2023, 5).unwrap(), UNIX_EPOCH + Duration::from_secs(1_701_752_400));
+        assert_eq!(unlock_time(2024, 25).unwrap(), UNIX_EPOCH + Duration::from_secs(1_735_102_800));
+        assert!(unlock_time(2014, 1).is_err());
+        assert!(unlock_time(2023, 0).is_err());
+        assert!(unlock_time(2023, 26).is_err());
+
+        let unlock = unlock_time(2023, 5).unwrap();
+        assert_eq!(until_unlock(2023, 5, unlock - Duration::from_secs(90)).unwrap(), Some(Duration::from_secs(90)));
+        assert_eq!(until_unlock(2023, 5, unlock).unwrap(), None);
+        assert_eq!(until_unlock(2023, 5, unlock + Duration::from_secs(1)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_countdown() {
+        assert_eq!(countdown(Duration::from_secs(3 * 86400 + 3723)), "3d 01:02:03");
+        assert_eq!(countdown(Duration::from_secs(59 * 60 + 59)), "00:59:59");
+        assert_eq!(countdown(Duration::from_millis(100)), "00:00:01");
+        assert_eq!(countdown(Duration::ZERO), "00:00:00");
+    }
+}
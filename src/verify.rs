@@ -0,0 +1,302 @@
+/*                        VERIFICATION
+
+Checks the solvers against known-correct answers, so that a refactor of a day
+can be confirmed not to have changed its results. The answers are kept in a
+TOML file with a table for each day, either part being optional, and answers
+which are not numbers given as strings:
+
+[1]
+part1 = 142
+part2 = 281
+
+Answers can also be recorded from a run, filling in the parts which have no
+expected answer yet, so that the file grows as days are solved. Recorded files
+are written out afresh, so comments in them are not kept.
+
+*/
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::runner::{run_all, DayResult, InputSet};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Answer>
+}
+
+impl ExpectedAnswers {
+    pub fn part(&self, part: u32) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerKey {
+    answers: BTreeMap<u32, ExpectedAnswers>
+}
+
+impl AnswerKey {
+    pub fn parse(content: &str) -> Result<AnswerKey, AocError> {
+        /* Read the expected answers from the contents of an answers file.
+
+        # Examples
+
+        ```
+        let key = AnswerKey::parse("[4]\npart1 = 13\npart2 = 30\n").unwrap();
+        ```
+        */
+        let tables: BTreeMap<String, ExpectedAnswers> = match toml::from_str(content) {
+            Ok(t) => t,
+            Err(e) => return Err(AocError::parse(format!("Invalid answers file: {}", e)))
+        };
+
+        let mut answers = BTreeMap::<u32, ExpectedAnswers>::new();
+        for (day, expected) in tables {
+            match day.parse::<u32>() {
+                Ok(d) => answers.insert(d, expected),
+                Err(e) => return Err(AocError::parse(format!("Invalid day '{}' in answers file: {}", day, e)))
+            };
+        }
+
+        Ok(AnswerKey { answers })
+    }
+
+    pub fn from_file(file_name: impl AsRef<Path>) -> Result<AnswerKey, AocError> {
+        /* Read the expected answers from an answers file, e.g. `answers.toml`. */
+        match std::fs::read_to_string(file_name.as_ref()) {
+            Ok(contents) => AnswerKey::parse(&contents),
+            Err(e) => Err(AocError::io(file_name.as_ref(), e))
+        }
+    }
+
+    pub fn get(&self, day: u32) -> Option<&ExpectedAnswers> {
+        self.answers.get(&day)
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.answers.keys().copied()
+    }
+
+    pub fn record(&mut self, day: u32, part: u32, answer: &Answer) -> bool {
+        /* Keep an answer as the expected one for a part which does not have one yet.
+
+        # Returns
+
+        Whether the answer was recorded, an existing expected answer is never replaced.
+        */
+        let expected = self.answers.entry(day).or_default();
+        let slot = match part {
+            1 => &mut expected.part1,
+            2 => &mut expected.part2,
+            _ => return false
+        };
+        match slot {
+            Some(_) => false,
+            None => {
+                *slot = Some(answer.clone());
+                true
+            }
+        }
+    }
+
+    pub fn record_results(&mut self, results: &[DayResult]) -> Vec<(u32, u32)> {
+        /* Record the answers of every solved part without an expected answer, returning the day and part of each. */
+        let mut recorded = Vec::<(u32, u32)>::new();
+
+        for result in results {
+            for part in &result.parts {
+                match &part.answer {
+                    Some(a) if self.record(result.day, part.part, a) => recorded.push((result.day, part.part)),
+                    _ => ()
+                };
+            }
+        }
+
+        recorded
+    }
+
+    pub fn to_toml(&self) -> Result<String, AocError> {
+        /* The answers in the form read by `parse`, a table for each day in day order. */
+        let mut tables = Vec::<String>::new();
+
+        for (day, expected) in &self.answers {
+            match toml::to_string(expected) {
+                Ok(t) => tables.push(format!("[{}]\n{}", day, t)),
+                Err(e) => return Err(AocError::parse(format!("Failed to write the answers of day {}: {}", day, e)))
+            };
+        }
+
+        Ok(tables.join("\n"))
+    }
+
+    pub fn save(&self, file_name: impl AsRef<Path>) -> Result<(), AocError> {
+        /* Write the answers to an answers file, replacing its contents.
+
+        # Examples
+
+        ```
+        let mut key = AnswerKey::from_file("answers.toml").unwrap_or_default();
+        key.record_results(&run_all(&InputSet::from_dir("data").unwrap()));
+        key.save("answers.toml").unwrap();
+        ```
+        */
+        match std::fs::write(file_name.as_ref(), self.to_toml()?) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AocError::io(file_name.as_ref(), e))
+        }
+    }
+}
+
+// The result of checking one part against its expected answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartCheck {
+    pub day: u32,
+    pub part: u32,
+    pub expected: Answer,
+    pub actual: Option<Answer>,
+    // Why there is no answer, if the part could not be solved
+    pub error: Option<String>
+}
+
+impl PartCheck {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref() == Some(&self.expected)
+    }
+}
+
+pub fn verify(inputs: &InputSet, key: &AnswerKey) -> Vec<PartCheck> {
+    /* Solve every part with an expected answer and compare the results.
+
+    A day in the answer key without an input fails with an error, while parts
+    without an expected answer are not checked.
+
+    # Examples
+
+    ```
+    let checks = verify(&InputSet::from_dir("data").unwrap(), &AnswerKey::from_file("answers.toml").unwrap());
+    assert!(checks.iter().all(|c| c.passed()));
+    ```
+    */
+    check(&run_all(inputs), key)
+}
+
+pub fn check(results: &[DayResult], key: &AnswerKey) -> Vec<PartCheck> {
+    /* Compare the results of a run with the expected answers, as `verify`. */
+    let mut checks = Vec::<PartCheck>::new();
+
+    for day in key.days() {
+        let expected = match key.get(day) {
+            Some(e) => e,
+            None => continue
+        };
+        let day_result = results.iter().find(|r| r.day == day);
+
+        for part in [1, 2] {
+            let expected_answer = match expected.part(part) {
+                Some(a) => a.clone(),
+                None => continue
+            };
+
+            let (actual, error) = match day_result.and_then(|r| r.parts.iter().find(|p| p.part == part)) {
+                Some(p) => (p.answer.clone(), p.error.clone()),
+                None => (None, Some(format!("No input or solver for day {}", day)))
+            };
+
+            checks.push(PartCheck { day, part, expected: expected_answer, actual, error });
+        }
+    }
+
+    checks
+}
+
+pub fn report(checks: &[PartCheck]) -> String {
+    /* Describe the outcome of each check, one line per part, followed by a summary. */
+    let mut lines = String::new();
+
+    for check in checks {
+        let outcome = match (&check.actual, &check.error) {
+            (Some(a), _) if *a == check.expected => format!("pass ({})", a),
+            (Some(a), _) => format!("FAIL, expected {} but got {}", check.expected, a),
+            (None, Some(e)) => format!("FAIL, {}", e),
+            (None, None) => "FAIL, no answer".to_string()
+        };
+        lines.push_str(&format!("Day {} part {}: {}\n", check.day, check.part, outcome));
+    }
+
+    let passed = checks.iter().filter(|c| c.passed()).count();
+    lines.push_str(&format!("{} of {} parts passed\n", passed, checks.len()));
+
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_answer_key() {
+        let key = AnswerKey::parse("[4]\npart1 = 13\npart2 = 30\n\n[5]\npart2 = 46\n\n[10]\npart1 = \"EZFCHJAB\"\n").unwrap();
+        assert_eq!(key.days().collect::<Vec<u32>>(), vec![4, 5, 10]);
+        assert_eq!(key.get(5), Some(&ExpectedAnswers { part1: None, part2: Some(Answer::Int(46)) }));
+        assert_eq!(key.get(10).unwrap().part(1), Some(&Answer::from("EZFCHJAB")));
+
+        assert!(AnswerKey::parse("[four]\npart1 = 13\n").is_err());
+        assert!(AnswerKey::parse("[4]\npart1 = 13.5\n").is_err());
+        assert!(AnswerKey::from_file("/no/such/answers.toml").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let inputs = InputSet::from_dir(&test_dir).unwrap();
+
+        // Day 1 has no input in the test directory
+        let key = AnswerKey::parse("[1]\npart1 = 142\n\n[3]\npart1 = 4361\npart2 = 1\n\n[5]\npart1 = 35\n").unwrap();
+        let checks = verify(&inputs, &key);
+
+        let outcomes: Vec<(u32, u32, bool)> = checks.iter().map(|c| (c.day, c.part, c.passed())).collect();
+        assert_eq!(outcomes, vec![(1, 1, false), (3, 1, true), (3, 2, false), (5, 1, true)]);
+        assert!(checks[0].error.is_some());
+        assert_eq!(checks[2].actual, Some(Answer::Int(467835)));
+
+        let lines = report(&checks);
+        assert!(lines.contains("Day 3 part 2: FAIL, expected 1 but got 467835"));
+        assert!(lines.ends_with("2 of 4 parts passed\n"));
+    }
+
+    #[test]
+    fn test_record() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test");
+        let results = run_all(&InputSet::from_dir(&test_dir).unwrap());
+
+        // Only parts without an expected answer are filled in, a wrong one is left to fail
+        let mut key = AnswerKey::parse("[3]\npart2 = 1\n\n[5]\npart1 = 35\n").unwrap();
+        let recorded = key.record_results(&results);
+        assert_eq!(recorded, vec![(2, 1), (2, 2), (3, 1), (4, 1), (4, 2), (5, 2)]);
+        assert!(key.record_results(&results).is_empty());
+        assert!(!key.record(3, 3, &Answer::Int(1)));
+
+        let answers_file = std::env::temp_dir().join(format!("aoc23_answers_{}.toml", std::process::id()));
+        key.save(&answers_file).unwrap();
+        let saved = AnswerKey::from_file(&answers_file).unwrap();
+        let contents = std::fs::read_to_string(&answers_file).unwrap();
+        std::fs::remove_file(&answers_file).unwrap();
+
+        assert_eq!(saved, key);
+        assert!(contents.starts_with("[2]\npart1 = 8\n"));
+        assert!(contents.contains("[3]\npart1 = 4361\npart2 = 1\n"));
+        assert_eq!(check(&results, &saved).iter().filter(|c| !c.passed()).count(), 1);
+    }
+}
@@ -0,0 +1,111 @@
+/*                        WEBASSEMBLY BINDINGS
+
+Exposes the solvers to JavaScript with wasm-bindgen, so that the solutions can
+be run in a web page from pasted puzzle input. Inputs are passed in as strings
+and answers come back as strings, or as JSON when solving both parts of a day.
+Build with `wasm-pack build --target web --no-default-features --features wasm`,
+as downloading inputs is not available in the browser. Then from JavaScript:
+
+import init, { days, solve, solveDay } from "./pkg/aoc23.js";
+
+await init();
+const answer = solve(3, 1, input);
+const { parts } = JSON.parse(solveDay(3, input));
+
+*/
+
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+use crate::error::AocError;
+use crate::solver::{solver, solvers};
+
+#[wasm_bindgen]
+pub fn days() -> Vec<u32> {
+    /* The days which can be solved, in order. */
+    solvers().iter().map(|s| s.day()).collect()
+}
+
+#[wasm_bindgen]
+pub fn solve(day: u32, part: u32, input: &str) -> Result<String, JsError> {
+    /* Solve one part of a day, throwing an `Error` with the reason if it cannot be solved. */
+    match answer(day, part, input) {
+        Ok(a) => Ok(a),
+        Err(e) => Err(JsError::new(&e.to_string()))
+    }
+}
+
+#[wasm_bindgen(js_name = solveDay)]
+pub fn solve_day(day: u32, input: &str) -> Result<String, JsError> {
+    /* Solve both parts of a day, returning JSON of the form
+    `{"day": 3, "parts": [{"part": 1, "answer": "4361", "error": null}, ..]}`.
+
+    A part which fails is reported with its error, only an unknown day throws.
+    */
+    match day_json(day, input) {
+        Ok(j) => Ok(j),
+        Err(e) => Err(JsError::new(&e.to_string()))
+    }
+}
+
+fn answer(day: u32, part: u32, input: &str) -> Result<String, AocError> {
+    let solver = match solver(day) {
+        Some(s) => s,
+        None => return Err(AocError::InvalidArgument(format!("Day {} has not been solved", day)))
+    };
+
+    match solver.solve(part, input) {
+        Ok(a) => Ok(a.to_string()),
+        Err(e) => Err(e)
+    }
+}
+
+fn day_json(day: u32, input: &str) -> Result<String, AocError> {
+    let solver = match solver(day) {
+        Some(s) => s,
+        None => return Err(AocError::InvalidArgument(format!("Day {} has not been solved", day)))
+    };
+
+    // Answers are given as strings as JavaScript numbers cannot hold every i64
+    let parts: Vec<serde_json::Value> = [1, 2].iter().map(|&part| match solver.solve(part, input) {
+        Ok(a) => json!({ "part": part, "answer": a.to_string(), "error": null }),
+        Err(e) => json!({ "part": part, "answer": null, "error": e.to_string() })
+    }).collect();
+
+    Ok(json!({ "day": day, "parts": parts }).to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_input(day: u32) -> String {
+        let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_file.push(format!("data/test/day_{}.dat", day));
+        std::fs::read_to_string(&test_file).unwrap()
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(days(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(answer(3, 1, &test_input(3)).unwrap(), "4361");
+        assert_eq!(answer(4, 2, &test_input(4)).unwrap(), "30");
+        assert!(answer(3, 3, &test_input(3)).is_err());
+        assert!(answer(25, 1, "").is_err());
+    }
+
+    #[test]
+    fn test_day_json() {
+        let result: serde_json::Value = serde_json::from_str(&day_json(5, &test_input(5)).unwrap()).unwrap();
+        assert_eq!(result["day"], 5);
+        assert_eq!(result["parts"][0]["answer"], "35");
+        assert_eq!(result["parts"][1]["answer"], "46");
+
+        let failed: serde_json::Value = serde_json::from_str(&day_json(1, "no digits\n").unwrap()).unwrap();
+        assert!(failed["parts"][0]["answer"].is_null());
+        assert!(failed["parts"][0]["error"].as_str().unwrap().contains("Line 1"));
+
+        assert!(day_json(25, "").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+/*                        INBOX
+
+Solves puzzle inputs as they are dropped into a directory, for a "drop the
+input, get the answer" routine on release night, started with
+
+aoc23 watch [--year Y] [--dir DIR] [--interval MS]
+
+The directory is polled rather than watched through the operating system, so
+that it behaves the same on every platform and on network drives. A file is
+only solved once its size and modification time are unchanged between two
+polls, so that one still being written is not read half way through.
+
+The day of a file is found from its name, or else its content, see
+src/detect.rs. The answers for 'day_7.txt' are written as JSON to
+'day_7.txt.answers.json' beside it, or why it could not be solved, and the
+file is solved again only once it is newer than its answers.
+
+*/
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::detect::DayDetector;
+use crate::error::AocError;
+use crate::event::event;
+use crate::runner::{run_day, PartResult};
+use crate::util::io::InputSource;
+
+pub const ANSWERS_SUFFIX: &str = ".answers.json";
+
+// The answers for a file of the inbox, written beside it
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Delivery {
+    pub input: PathBuf,
+    pub day: Option<u32>,
+    pub parts: Vec<PartResult>,
+    // Why the file could not be solved, in which case it has no parts
+    pub error: Option<String>
+}
+
+pub struct Inbox {
+    dir: PathBuf,
+    year: u32,
+    detector: DayDetector,
+    // Size and modification time of each unanswered file at the last poll
+    pending: HashMap<PathBuf, (u64, SystemTime)>
+}
+
+impl Inbox {
+    pub fn new(dir: impl AsRef<Path>, year: u32, detector: DayDetector) -> Inbox {
+        /* An inbox solving the files of a directory as days of the event of the given year. */
+        Inbox { dir: dir.as_ref().to_path_buf(), year, detector, pending: HashMap::new() }
+    }
+
+    pub fn poll(&mut self) -> Result<Vec<Delivery>, AocError> {
+        /* Solve the files which have finished arriving since the last poll, in name order.
+
+        # Examples
+
+        ```
+        let mut inbox = Inbox::new("inbox", 2023, DayDetector::default());
+        loop {
+            for delivery in inbox.poll()? {
+                tracing::info!("Solved '{}'", delivery.input.display());
+            }
+            std::thread::sleep(Duration::from_secs(1));
+        }
+        ```
+        */
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(e) => e,
+            Err(e) => return Err(AocError::io(&self.dir, e))
+        };
+
+        let mut inputs: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .filter(|p| match p.file_name().and_then(|n| n.to_str()) {
+                // Hidden files are left alone, as editors and downloads write to them first
+                Some(name) => !name.starts_with('.') && !name.ends_with(ANSWERS_SUFFIX),
+                None => false
+            })
+            .collect();
+        inputs.sort();
+
+        let mut waiting = HashMap::new();
+        let mut delivered = Vec::<Delivery>::new();
+
+        for input in inputs {
+            let stamp = match std::fs::metadata(&input).and_then(|m| Ok((m.len(), m.modified()?))) {
+                Ok(s) => s,
+                Err(e) => return Err(AocError::io(&input, e))
+            };
+
+            let answers = answers_path(&input);
+            let answered = match std::fs::metadata(&answers).and_then(|m| m.modified()) {
+                Ok(modified) => modified >= stamp.1,
+                Err(_) => false
+            };
+            if answered {
+                continue;
+            }
+
+            // A file is solved on the poll after the one which first saw it as it is now
+            if self.pending.get(&input) != Some(&stamp) {
+                waiting.insert(input, stamp);
+                continue;
+            }
+
+            let delivery = self.deliver(&input);
+            let json = match serde_json::to_string_pretty(&delivery) {
+                Ok(j) => j,
+                Err(e) => return Err(AocError::InvalidArgument(format!("Failed to write the answers for '{}': {}", input.display(), e)))
+            };
+            if let Err(e) = std::fs::write(&answers, json + "\n") {
+                return Err(AocError::io(&answers, e));
+            }
+            delivered.push(delivery);
+        }
+
+        self.pending = waiting;
+        Ok(delivered)
+    }
+
+    fn deliver(&self, input: &Path) -> Delivery {
+        /* Solve both parts of a file with the solver of its day. */
+        let unsolved = |day, error: String| Delivery { input: input.to_path_buf(), day, parts: Vec::new(), error: Some(error) };
+
+        let content = match InputSource::read(input).and_then(InputSource::into_string) {
+            Ok(c) => c,
+            Err(e) => return unsolved(None, AocError::from(e).to_string())
+        };
+
+        let day = match self.detector.detect(input, &content) {
+            Some(d) => d,
+            None => return unsolved(None, "Could not tell the day from the name or content of the file".to_string())
+        };
+
+        match event(self.year).and_then(|e| e.solver(day)) {
+            Some(solver) => {
+                let result = run_day(solver.as_ref(), &content);
+                Delivery { input: input.to_path_buf(), day: Some(day), parts: result.parts, error: None }
+            },
+            None => unsolved(Some(day), format!("Day {} of {} has not been solved", day, self.year))
+        }
+    }
+}
+
+pub fn answers_path(input: &Path) -> PathBuf {
+    /* Where the answers for a file of the inbox are written. */
+    let mut name = input.as_os_str().to_os_string();
+    name.push(ANSWERS_SUFFIX);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answer::Answer;
+
+    #[test]
+    fn test_inbox() {
+        let dir = std::env::temp_dir().join(format!("aoc23_inbox_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let sample = std::fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/test/day_4.dat")).unwrap();
+        std::fs::write(dir.join("day_4.txt"), &sample).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a puzzle").unwrap();
+        std::fs::write(dir.join(".day_5.txt.part"), "").unwrap();
+
+        let mut inbox = Inbox::new(&dir, 2023, DayDetector::default());
+        let first = inbox.poll().unwrap();
+        let second = inbox.poll().unwrap();
+        let third = inbox.poll().unwrap();
+        let answers = std::fs::read_to_string(answers_path(&dir.join("day_4.txt"))).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(first.is_empty());
+        assert_eq!(second.len(), 2);
+        assert_eq!(second[0].day, Some(4));
+        assert_eq!(second[0].parts.iter().map(|p| p.answer.clone()).collect::<Vec<Option<Answer>>>(), vec![Some(Answer::Int(13)), Some(Answer::Int(30))]);
+        assert_eq!(second[1].input, dir.join("notes.txt"));
+        assert!(second[1].error.is_some());
+        assert!(third.is_empty());
+        assert!(answers.contains("\"day\": 4"));
+    }
+}